@@ -0,0 +1,159 @@
+//! Benchmarks of the most expensive parsing paths.
+//!
+//! Run with `cargo bench`. The fonts in `tests/fixtures/` and `benches/fonts/` are always
+//! benchmarked; the larger fonts downloaded by `tests/get-fonts.sh` are used when available
+//! and skipped otherwise.
+//!
+//! The baseline targets in the comments are for an optimized build on a recent x86-64
+//! machine, rounded up from measurements of the fixtures; treat a 2x slowdown as a regression.
+//! Targets for the downloaded fonts are estimates.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rustotf::{FontContainer, Tag};
+use std::hint::black_box;
+use std::path::Path;
+
+const FIXTURES_PATH: &str = "./tests/fixtures/";
+const BENCH_FONTS_PATH: &str = "./benches/fonts/";
+const FONTS_PATH: &str = "./tests/fonts/";
+
+fn fixture(name: &str) -> String {
+    [FIXTURES_PATH, name].join("")
+}
+
+fn bench_font(name: &str) -> String {
+    [BENCH_FONTS_PATH, name].join("")
+}
+
+fn font(name: &str) -> String {
+    [FONTS_PATH, name].join("")
+}
+
+fn read_font(name: &str, path: &str) -> Option<FontContainer> {
+    if Path::new(path).exists() {
+        FontContainer::read(path).ok()
+    } else {
+        println!("{:<48} skipped (missing {})", name, path);
+        None
+    }
+}
+
+/// Benchmark parsing the tables `tags` in order, so that the last one can depend on the
+/// others (e.g. `glyf` on `loca`, `head` and `maxp`).
+fn bench_parse(c: &mut Criterion, name: &str, path: &str, tags: &[&[u8; 4]]) {
+    if let Some(mut font_container) = read_font(name, path) {
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                for &tag in tags {
+                    font_container.parse_table(Tag::new(tag)).unwrap();
+                }
+                black_box(&font_container);
+            })
+        });
+    }
+}
+
+/// (1) TrueType outlines. Target: 20 ms for DejaVu Sans (6,253 glyphs).
+fn parse_glyf(c: &mut Criterion) {
+    let tags: &[&[u8; 4]] = &[b"head", b"maxp", b"loca", b"glyf"];
+    bench_parse(c, "parse_glyf/DejaVuSans", &fixture("DejaVuSans.ttf"), tags);
+    bench_parse(
+        c,
+        "parse_glyf/NotoSans-SemiCondensed",
+        &font("NotoSans-SemiCondensed.ttf"),
+        tags,
+    );
+}
+
+/// (2) CFF with heavy subroutine use. Target: 150 ms for Source Han Sans SC (65,535 glyphs).
+fn parse_cff(c: &mut Criterion) {
+    bench_parse(
+        c,
+        "parse_CFF_/SourceHanSansSC-Regular",
+        &font("SourceHanSansSC-Regular.otf"),
+        &[b"CFF "],
+    );
+}
+
+/// (3) Layout tables. Target: 1 ms for each table of DejaVu Sans.
+fn parse_layout(c: &mut Criterion) {
+    bench_parse(
+        c,
+        "parse_GSUB/DejaVuSans",
+        &fixture("DejaVuSans.ttf"),
+        &[b"GSUB"],
+    );
+    bench_parse(
+        c,
+        "parse_GPOS/DejaVuSans",
+        &fixture("DejaVuSans.ttf"),
+        &[b"GPOS"],
+    );
+    bench_parse(
+        c,
+        "parse_GSUB/DejaVuMathTeXGyre",
+        &fixture("DejaVuMathTeXGyre.ttf"),
+        &[b"GSUB"],
+    );
+}
+
+/// (4) Character map lookups with `Table_cmap::lookup`, covering format 4 (BMP) and format 12
+/// (full repertoire) subtables. Target: 10 µs for the ASCII range.
+fn cmap_lookup(c: &mut Criterion) {
+    for (name, path) in &[
+        ("cmap_lookup/DejaVuSans", fixture("DejaVuSans.ttf")),
+        (
+            "cmap_lookup/DejaVuMathTeXGyre",
+            fixture("DejaVuMathTeXGyre.ttf"),
+        ),
+    ] {
+        if let Some(mut font_container) = read_font(name, path) {
+            font_container.parse_table(Tag::new(b"cmap")).unwrap();
+            let cmap = font_container.get(0).unwrap().cmap.as_ref().unwrap();
+            c.bench_function(name, |b| {
+                b.iter(|| {
+                    (0x20..0x7F).for_each(|codepoint| {
+                        black_box(cmap.lookup(black_box(codepoint)));
+                    })
+                })
+            });
+        }
+    }
+}
+
+/// (5) WOFF and WOFF2 fonts, whose tables are decompressed when they are parsed (and the
+/// `glyf` table of WOFF2 is reconstructed). Target: 40 ms for DejaVu Sans in WOFF, and 10 ms
+/// for Source Code Pro in WOFF2.
+fn parse_woff(c: &mut Criterion) {
+    for (name, path) in &[
+        ("parse_woff/DejaVuSans", bench_font("DejaVuSans.ttf.woff")),
+        (
+            "parse_woff/SourceSans3-ExtraLight",
+            font("SourceSans3-ExtraLight.ttf.woff"),
+        ),
+        (
+            "parse_woff2/SourceCodePro-Regular",
+            bench_font("SourceCodePro-Regular.ttf.woff2"),
+        ),
+    ] {
+        if read_font(name, path).is_some() {
+            c.bench_function(name, |b| {
+                b.iter(|| {
+                    let mut font_container = FontContainer::read(path).unwrap();
+                    font_container.parse().unwrap();
+                    black_box(font_container)
+                })
+            });
+        }
+    }
+}
+
+criterion_group!(
+    benches,
+    parse_glyf,
+    parse_cff,
+    parse_layout,
+    cmap_lookup,
+    parse_woff
+);
+criterion_main!(benches);
@@ -2,11 +2,21 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse, Data, DeriveInput, Fields};
+use syn::{parse, Data, DeriveInput, Field, Fields, Ident};
 
 // See https://github.com/dtolnay/syn/blob/master/examples/heapsize/heapsize_derive/src/lib.rs.
 
-#[proc_macro_derive(ReadBuffer)]
+/// Derive `ReadBuffer` for a struct, by reading each field from the buffer in the order of
+/// declaration. Fields marked with `#[read_buffer(default)]` are not read, but set to
+/// `Default::default()`, e.g. for data that are computed from offsets after reading.
+///
+/// Limitations:
+///
+/// - Only structs are supported; enums and unions are not.
+/// - There is no way to read a field conditionally (e.g. depending on the version) or to
+///   follow an offset. Implement `ReadBuffer` manually in such cases.
+/// - `Buffer` and `ReadBuffer` must be in scope where the macro is used.
+#[proc_macro_derive(ReadBuffer, attributes(read_buffer))]
 pub fn read_buffer_derive(input: TokenStream) -> TokenStream {
     let ast = parse(input).unwrap();
     impl_read_buffer(ast)
@@ -22,12 +32,13 @@ fn impl_read_buffer(ast: DeriveInput) -> TokenStream {
         Fields::Named(fields) => {
             let recurse = fields.named.iter().map(|f| {
                 let name = &f.ident;
-                quote!(#name)
+                let value = read_field(f);
+                quote!(#name: #value)
             });
-            quote!(Self { #(#recurse: buffer.get(),)* })
+            quote!(Self { #(#recurse,)* })
         }
         Fields::Unnamed(fields) => {
-            let recurse = fields.unnamed.iter().map(|_| quote!(buffer.get()));
+            let recurse = fields.unnamed.iter().map(read_field);
             quote!(Self (#(#recurse),*))
         }
         Fields::Unit => {
@@ -43,3 +54,21 @@ fn impl_read_buffer(ast: DeriveInput) -> TokenStream {
     );
     gen.into()
 }
+
+fn read_field(field: &Field) -> proc_macro2::TokenStream {
+    if is_default(field) {
+        quote!(Default::default())
+    } else {
+        quote!(buffer.get())
+    }
+}
+
+/// Whether the field is marked with `#[read_buffer(default)]`.
+fn is_default(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| {
+        attr.path.is_ident("read_buffer")
+            && attr
+                .parse_args::<Ident>()
+                .is_ok_and(|ident| ident == "default")
+    })
+}
@@ -1,88 +1,440 @@
+use crate::error::FontError;
 use crate::font::{Font, FontContainer};
-use std::io;
+use crate::types::Tag;
+use crate::unicode::UNICODE_BLOCKS;
+use crate::util::Buffer;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
 use std::path::Path;
 
-pub fn print_font_info(input_path: &str, ttc_indices: &[usize]) -> io::Result<()> {
-    let font_container = FontContainer::read(input_path)?;
+/// Output format of the parsed tables.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// The debug output of the tables.
+    Text,
+    /// JSON, which is only available with the `serde` feature.
+    Json,
+}
+
+pub fn print_font_info(
+    input_path: &Path,
+    ttc_indices: &[usize],
+    output_path: Option<&Path>,
+) -> Result<(), FontError> {
+    let mut font_container = read_font_container(input_path)?;
     let font_num = font_container.len();
+    let mut writer = create_writer(output_path)?;
+    // Only the tables needed by the summary header are parsed
+    for i in 0..font_num {
+        for tag in &["head", "maxp", "name"] {
+            let _ = font_container.parse_nth_table(i, Tag::from(*tag));
+        }
+    }
     let indent = "    ";
-    let init = || println!("Listing table info for {:?}:\n", input_path);
-    match font_num {
-        0 => eprintln!("Invalid font files."),
-        1 => {
-            if !ttc_indices.is_empty() {
-                eprintln!("WARNING: Your font number specification will be ignored.");
-            }
-            init();
-            println!("{}\n", font_container.get(0).unwrap().fmt_font_info(indent));
+    if font_num > 0 {
+        writeln!(writer, "{}\n", font_container)?;
+        writeln!(writer, "Listing table info for {:?}:\n", input_path)?;
+    }
+    let file_name = input_path.file_name().unwrap_or_default().to_string_lossy();
+    for_each_font(font_num, ttc_indices, |i| {
+        let font = font_container.get(i).unwrap();
+        if font_num == 1 {
+            writeln!(writer, "{}\n", font.fmt_font_info(indent))
+        } else {
+            writeln!(
+                writer,
+                "{}#{}:\n{}\n",
+                file_name,
+                i,
+                font.fmt_font_info(indent)
+            )
         }
-        _ => {
-            init();
-            let file_name = Path::new(input_path).file_name().unwrap().to_str().unwrap();
-            let print_font = |(i, font): (usize, &Font)| {
-                println!("{}#{}:\n{}\n", file_name, i, font.fmt_font_info(indent))
-            };
-            if ttc_indices.is_empty() {
-                font_container.into_iter().enumerate().for_each(print_font);
+    })?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Verify the table checksums of the fonts and report the mismatched tables, to the file
+/// `output_path` or the standard output. The fonts are not parsed. Return the number of the
+/// mismatched tables.
+pub fn print_checksum_mismatches(
+    input_path: &Path,
+    ttc_indices: &[usize],
+    output_path: Option<&Path>,
+) -> Result<usize, FontError> {
+    let font_container = read_font_container(input_path)?;
+    let mismatches = font_container.verify_checksums();
+    let mut writer = create_writer(output_path)?;
+    let file_name = input_path.file_name().unwrap_or_default().to_string_lossy();
+    let font_num = mismatches.len();
+    let mut count = 0;
+    for_each_font(font_num, ttc_indices, |i| {
+        count += mismatches[i].len();
+        for tag in &mismatches[i] {
+            if font_num == 1 {
+                writeln!(
+                    writer,
+                    "{}: checksum mismatch in table '{}'",
+                    file_name, tag
+                )?;
             } else {
-                let max_index = font_container.len() - 1;
-                let index_error = |i: usize| {
-                    eprintln!(
-                        "The font number should be between 0 and {}, but you specify {}.",
-                        max_index, i
-                    )
-                };
-                ttc_indices
-                    .iter()
-                    .for_each(|&i| match font_container.get(i) {
-                        Some(font) => print_font((i, font)),
-                        _ => index_error(i),
-                    })
+                writeln!(
+                    writer,
+                    "{}#{}: checksum mismatch in table '{}'",
+                    file_name, i, tag
+                )?;
             }
         }
+        Ok::<_, io::Error>(())
+    })?;
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Print the tables `tables` (or all the tables, if it is empty) in `format`, to the file
+/// `output_path` or the standard output. In JSON, a font collection is printed as an array.
+pub fn print_tables(
+    input_path: &Path,
+    ttc_indices: &[usize],
+    tables: &[&str],
+    format: OutputFormat,
+    output_path: Option<&Path>,
+) -> Result<(), FontError> {
+    let mut font_container = read_font_container(input_path)?;
+    let font_num = font_container.len();
+    let mut writer = create_writer(output_path)?;
+    // TODO: don't parse all the tables
+    font_container.parse()?;
+    if format == OutputFormat::Json {
+        write_tables_json(&mut writer, &font_container, ttc_indices, tables)?;
+        writer.flush()?;
+        return Ok(());
     }
+    if font_num > 0 {
+        writeln!(writer, "Dumping {:?}:\n", input_path)?;
+    }
+    let file_name = input_path.file_name().unwrap_or_default().to_string_lossy();
+    for_each_font(font_num, ttc_indices, |i| {
+        if font_num > 1 {
+            writeln!(writer, "{}#{}:", file_name, i)?;
+        }
+        font_container
+            .get(i)
+            .unwrap()
+            .dump_tables_to_writer(tables, &mut writer)?;
+        writeln!(writer)
+    })?;
+    writer.flush()?;
     Ok(())
 }
 
-pub fn print_tables(input_path: &str, ttc_indices: &[usize], tables: &[&str]) -> io::Result<()> {
-    let mut font_container = FontContainer::read(input_path)?;
+pub fn print_char_coverage(
+    input_path: &Path,
+    ttc_indices: &[usize],
+    output_path: Option<&Path>,
+) -> Result<(), FontError> {
+    let mut font_container = read_font_container(input_path)?;
     let font_num = font_container.len();
-    let init = || println!("Dumping {:?}:\n", input_path);
-    // TODO: don't parse all the tables
-    font_container.parse();
+    let mut writer = create_writer(output_path)?;
+    let indent = "    ";
+    font_container.parse()?;
+    if font_num > 0 {
+        writeln!(writer, "Character coverage of {:?}:\n", input_path)?;
+    }
+    let file_name = input_path.file_name().unwrap_or_default().to_string_lossy();
+    for_each_font(font_num, ttc_indices, |i| {
+        let font = font_container.get(i).unwrap();
+        if font_num == 1 {
+            writeln!(writer, "{}\n", fmt_char_coverage(font, indent))
+        } else {
+            writeln!(
+                writer,
+                "{}#{}:\n{}\n",
+                file_name,
+                i,
+                fmt_char_coverage(font, indent)
+            )
+        }
+    })?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Print the metrics of `glyph_ids` (or all the glyphs, if it is empty) as tab-separated
+/// values, to the file `output_path` or the standard output.
+pub fn print_glyph_metrics(
+    input_path: &Path,
+    ttc_indices: &[usize],
+    glyph_ids: &[u16],
+    output_path: Option<&Path>,
+) -> Result<(), FontError> {
+    let mut font_container = read_font_container(input_path)?;
+    let font_num = font_container.len();
+    let mut writer = create_writer(output_path)?;
+    font_container.parse()?;
+    let file_name = input_path.file_name().unwrap_or_default().to_string_lossy();
+    for_each_font(font_num, ttc_indices, |i| {
+        if font_num > 1 {
+            writeln!(writer, "# {}#{}", file_name, i)?;
+        }
+        write_glyph_metrics(&mut writer, font_container.get(i).unwrap(), glyph_ids)
+    })?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Print a hex dump of the bytes around `offset` of the file.
+pub fn print_hex_dump(
+    input_path: &Path,
+    offset: usize,
+    output_path: Option<&Path>,
+) -> io::Result<()> {
+    const CONTEXT_BYTES: usize = 64;
+
+    let bytes = if is_stdin(input_path) {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        fs::read(input_path)?
+    };
+    let buffer = Buffer::new(bytes);
+    if offset >= buffer.len() {
+        eprintln!(
+            "The offset should be less than the file size {}, but you specify {}.",
+            buffer.len(),
+            offset
+        );
+    } else {
+        let mut writer = create_writer(output_path)?;
+        writeln!(writer, "{}", buffer.dump_hex_at(offset, CONTEXT_BYTES))?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Call `f` with the index of each font selected by `ttc_indices` out of `font_num` fonts. A
+/// single font is always selected, and all the fonts are selected if `ttc_indices` is empty.
+/// Invalid font numbers are reported to the standard error.
+fn for_each_font<E, F>(font_num: usize, ttc_indices: &[usize], mut f: F) -> Result<(), E>
+where
+    F: FnMut(usize) -> Result<(), E>,
+{
     match font_num {
         0 => eprintln!("Invalid font files."),
         1 => {
             if !ttc_indices.is_empty() {
                 eprintln!("WARNING: Your font number specification will be ignored.");
             }
-            init();
-            println!("{}", font_container.get(0).unwrap().fmt_tables(tables));
+            f(0)?;
         }
+        _ if ttc_indices.is_empty() => (0..font_num).try_for_each(f)?,
         _ => {
-            init();
-            let file_name = Path::new(input_path).file_name().unwrap().to_str().unwrap();
-            let print_font = |(i, font): (usize, &Font)| {
-                println!("{}#{}:\n{}", file_name, i, font.fmt_tables(tables));
-            };
-            if ttc_indices.is_empty() {
-                font_container.into_iter().enumerate().for_each(print_font);
-            } else {
-                let max_index = font_container.len() - 1;
-                let index_error = |i: usize| {
+            for &i in ttc_indices {
+                if i < font_num {
+                    f(i)?;
+                } else {
                     eprintln!(
                         "The font number should be between 0 and {}, but you specify {}.",
-                        max_index, i
-                    )
-                };
-                ttc_indices
-                    .iter()
-                    .for_each(|&i| match font_container.get(i) {
-                        Some(font) => print_font((i, font)),
-                        _ => index_error(i),
-                    })
+                        font_num - 1,
+                        i
+                    );
+                }
             }
         }
     }
     Ok(())
 }
+
+/// Create a buffered writer to the file `output_path`, or to the standard output if it is
+/// not specified.
+fn create_writer(output_path: Option<&Path>) -> io::Result<Box<dyn Write>> {
+    Ok(match output_path {
+        Some(path) => Box::new(BufWriter::new(fs::File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    })
+}
+
+#[cfg(feature = "serde")]
+fn write_tables_json<W: Write>(
+    writer: &mut W,
+    font_container: &FontContainer,
+    ttc_indices: &[usize],
+    tables: &[&str],
+) -> Result<(), FontError> {
+    let font_num = font_container.len();
+    let mut values = Vec::new();
+    for_each_font(font_num, ttc_indices, |i| {
+        values.push(font_container.get(i).unwrap().tables_to_json(tables)?);
+        Ok::<_, FontError>(())
+    })?;
+    let value = match font_num {
+        0 => return Ok(()),
+        1 => values.pop().unwrap(),
+        _ => serde_json::Value::Array(values),
+    };
+    serde_json::to_writer(&mut *writer, &value).map_err(io::Error::from)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn write_tables_json<W: Write>(
+    _writer: &mut W,
+    _font_container: &FontContainer,
+    _ttc_indices: &[usize],
+    _tables: &[&str],
+) -> Result<(), FontError> {
+    Err(FontError::Io(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "JSON output requires the `serde` feature",
+    )))
+}
+
+/// The input path `-` stands for the standard input.
+fn is_stdin(input_path: &Path) -> bool {
+    input_path == Path::new("-")
+}
+
+fn read_font_container(input_path: &Path) -> Result<FontContainer, FontError> {
+    if is_stdin(input_path) {
+        FontContainer::from_reader(io::stdin().lock())
+    } else {
+        FontContainer::read_from_path(input_path)
+    }
+}
+
+fn write_glyph_metrics<W: Write>(writer: &mut W, font: &Font, glyph_ids: &[u16]) -> io::Result<()> {
+    let num_glyphs = font.maxp.as_ref().map_or(0, |maxp| maxp.num_glyphs);
+    let all_glyph_ids: Vec<u16>;
+    let glyph_ids = if glyph_ids.is_empty() {
+        all_glyph_ids = (0..num_glyphs).collect();
+        &all_glyph_ids
+    } else {
+        glyph_ids
+    };
+    let mut code_points: HashMap<u32, Vec<u32>> = HashMap::new();
+    if let Some(char_map) = font.char_map() {
+        char_map
+            .iter()
+            .for_each(|(&c, &gid)| code_points.entry(gid).or_default().push(c));
+    }
+
+    writeln!(
+        writer,
+        "GID\tUnicode\tName\tAdvance\tLSB\tRSB\tx_min\ty_min\tx_max\ty_max"
+    )?;
+    for &glyph_id in glyph_ids {
+        if glyph_id >= num_glyphs {
+            eprintln!(
+                "The glyph ID should be less than {}, but you specify {}.",
+                num_glyphs, glyph_id
+            );
+            continue;
+        }
+        let unicode = match code_points.get_mut(&(glyph_id as u32)) {
+            Some(code_points) => {
+                code_points.sort_unstable();
+                code_points
+                    .iter()
+                    .map(|c| format!("U+{:04X}", c))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }
+            None => String::new(),
+        };
+        let name = font
+            .post
+            .as_ref()
+            .and_then(|post| post.glyph_name(glyph_id))
+            .unwrap_or("");
+        let metric = font.hmtx.as_ref().and_then(|hmtx| hmtx.metric(glyph_id));
+        // CFF fonts only provide the bounding box of the whole font.
+        let bbox = match font.glyf {
+            Some(_) => font.glyph_bounding_box(glyph_id),
+            None => None,
+        };
+        let rsb = match (metric, bbox) {
+            (Some((advance_width, lsb)), Some((x_min, _, x_max, _))) => {
+                Some(advance_width as i32 - lsb as i32 - (x_max as i32 - x_min as i32))
+            }
+            _ => None,
+        };
+        let fmt = |n: Option<i32>| n.map_or_else(String::new, |n| n.to_string());
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            glyph_id,
+            unicode,
+            name,
+            fmt(metric.map(|(advance_width, _)| advance_width as i32)),
+            fmt(metric.map(|(_, lsb)| lsb as i32)),
+            fmt(rsb),
+            fmt(bbox.map(|(x_min, _, _, _)| x_min as i32)),
+            fmt(bbox.map(|(_, y_min, _, _)| y_min as i32)),
+            fmt(bbox.map(|(_, _, x_max, _)| x_max as i32)),
+            fmt(bbox.map(|(_, _, _, y_max)| y_max as i32)),
+        )?;
+    }
+    Ok(())
+}
+
+fn fmt_char_coverage(font: &Font, indent: &str) -> String {
+    const BAR_WIDTH: usize = 20;
+
+    let mut lines = Vec::new();
+    let axes = font.axes_with_names();
+    if !axes.is_empty() {
+        lines.push(format!("{}Axes:", indent));
+        axes.iter().for_each(|(tag, name, min, default, max)| {
+            lines.push(format!(
+                "{0}{0}{1} ({2})  {3} .. {4} .. {5}",
+                indent, tag, name, min, default, max
+            ))
+        });
+        lines.push(String::new());
+    }
+
+    let char_map = match font.char_map() {
+        Some(char_map) => char_map,
+        None => {
+            lines.push(format!("{}No Unicode `cmap` subtable.", indent));
+            return lines.join("\n");
+        }
+    };
+    let mut counts = vec![0; UNICODE_BLOCKS.len()];
+    char_map
+        .iter()
+        .filter(|(_, &gid)| gid != 0)
+        .for_each(|(&c, _)| {
+            let i = UNICODE_BLOCKS.partition_point(|&(_, last, _)| last < c);
+            if let Some(&(first, _, _)) = UNICODE_BLOCKS.get(i) {
+                if first <= c {
+                    counts[i] += 1;
+                }
+            }
+        });
+
+    lines.push(format!("{}{:<48} {}", indent, "Block", "Coverage"));
+    lines.push(format!("{}{:-<48} {:-<42}", indent, "", ""));
+    UNICODE_BLOCKS
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .for_each(|(&(first, last, name), count)| {
+            let total = (last - first + 1) as usize;
+            let filled = count * BAR_WIDTH / total;
+            lines.push(format!(
+                "{}{:<48} {:>6}/{:<6} [{:<width$}] {:>3}%",
+                indent,
+                name,
+                count,
+                total,
+                "#".repeat(filled),
+                count * 100 / total,
+                width = BAR_WIDTH
+            ))
+        });
+    lines.join("\n")
+}
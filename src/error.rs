@@ -0,0 +1,132 @@
+use crate::types::Tag;
+use std::error::Error;
+use std::str::Utf8Error;
+use std::{fmt, io};
+
+/// Errors that can occur when reading or parsing fonts.
+#[derive(Debug)]
+pub enum FontError {
+    /// The font `index` is out of range for a container with `len` fonts.
+    FontIndexOutOfRange { index: usize, len: usize },
+    /// The table is not present in the font.
+    TableNotFound(Tag),
+    /// The signature of the file or the flavor of a font is not recognized.
+    InvalidMagic(u32),
+    /// The table (or one of its subtables) has a format that is not defined.
+    UnknownTableFormat { tag: Tag, format: u16 },
+    /// The data at `offset` with `length` bytes exceed the end of the buffer. The hex dump
+    /// shows the bytes around `offset`, if any.
+    BufferOverflow {
+        offset: usize,
+        length: usize,
+        hex_dump: Option<String>,
+    },
+    /// The offset of the table points to somewhere outside the file. The hex dump shows the
+    /// bytes around `offset`, if any.
+    InvalidOffset {
+        table: Tag,
+        offset: usize,
+        hex_dump: Option<String>,
+    },
+    /// The data of the table are not valid, e.g. a value is out of range.
+    Malformed { table: Tag, msg: String },
+    /// Failed to read the font file.
+    Io(io::Error),
+    /// A string is not encoded properly.
+    Encoding(Utf8Error),
+    /// Failed to decompress WOFF or WOFF2 data.
+    Decompression(String),
+    /// An error annotated with where it happens, e.g. the table name.
+    Context { msg: String, source: Box<FontError> },
+}
+
+impl FontError {
+    /// Annotate the error with `msg`, which is shown before the original message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustotf::{FontError, Tag};
+    /// let err = FontError::TableNotFound(Tag::new(b"loca")).context("parsing 'glyf'");
+    /// assert_eq!(err.to_string(), "parsing 'glyf': table 'loca' not found");
+    /// ```
+    pub fn context(self, msg: &str) -> Self {
+        Self::Context {
+            msg: msg.to_string(),
+            source: Box::new(self),
+        }
+    }
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FontIndexOutOfRange { index, len } => write!(
+                f,
+                "font index {} is out of range (the container has {} fonts)",
+                index, len
+            ),
+            Self::TableNotFound(tag) => write!(f, "table '{}' not found", tag),
+            Self::InvalidMagic(magic) => write!(f, "invalid signature 0x{:08X}", magic),
+            Self::UnknownTableFormat { tag, format } => {
+                write!(f, "unknown format {} in table '{}'", format, tag)
+            }
+            Self::BufferOverflow {
+                offset,
+                length,
+                hex_dump,
+            } => {
+                write!(
+                    f,
+                    "{} bytes at offset {} exceed the end of the buffer",
+                    length, offset
+                )?;
+                write_hex_dump(f, hex_dump)
+            }
+            Self::InvalidOffset {
+                table,
+                offset,
+                hex_dump,
+            } => {
+                write!(f, "invalid offset {} of table '{}'", offset, table)?;
+                write_hex_dump(f, hex_dump)
+            }
+            Self::Malformed { table, msg } => write!(f, "malformed table '{}': {}", table, msg),
+            Self::Io(err) => write!(f, "I/O error: {}", err),
+            Self::Encoding(err) => write!(f, "invalid string: {}", err),
+            Self::Decompression(msg) => write!(f, "decompression failed: {}", msg),
+            Self::Context { msg, source } => write!(f, "{}: {}", msg, source),
+        }
+    }
+}
+
+/// Write the hex dump of an error on the following lines.
+fn write_hex_dump(f: &mut fmt::Formatter, hex_dump: &Option<String>) -> fmt::Result {
+    match hex_dump {
+        Some(hex_dump) => write!(f, "\n{}", hex_dump),
+        None => Ok(()),
+    }
+}
+
+impl Error for FontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Encoding(err) => Some(err),
+            Self::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FontError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<Utf8Error> for FontError {
+    fn from(err: Utf8Error) -> Self {
+        Self::Encoding(err)
+    }
+}
@@ -1,10 +1,18 @@
+use crate::error::FontError;
 use crate::tables::*;
-use crate::types::{u32_var, Tag};
-use crate::util::{Buffer, ReadBuffer};
+use crate::types::{u16_var, u32_var, Tag};
+use crate::util::Buffer;
+use crate::woff2;
+use std::any::Any;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fmt;
 use std::fs;
-use std::io;
+use std::io::{self, Read};
 use std::iter::{FromIterator, Zip};
-use std::slice::Iter;
+use std::mem;
+use std::path::Path;
+use std::slice::{Iter, IterMut};
 
 /// The container of a OpenType/WOFF/WOFF2 font or font collection.
 ///
@@ -23,7 +31,10 @@ use std::slice::Iter;
 #[derive(Debug)]
 pub struct FontContainer {
     buffer: Buffer,
+    format: ContainerFormat,
     fonts: Vec<Font>,
+    /// Parse the tables on demand, see [`FontContainer::set_lazy`].
+    lazy: bool,
 }
 
 impl FontContainer {
@@ -39,51 +50,97 @@ impl FontContainer {
     /// # Errors
     ///
     /// This function will return an error if `path` does not already exist.
-    /// Other errors may also be returned according to [`fs::read`].
+    /// Other errors may also be returned according to [`fs::read`], or if the signature of
+    /// the file or any of its fonts is unknown.
     ///
     /// # Examples
     ///
     /// ```ignore
     /// use rustotf;
     ///
-    /// fn main() -> std::io::Result<()> {
+    /// fn main() -> Result<(), rustotf::FontError> {
     ///     let font = rustotf::FontContainer::read("SourceSerif4-Regular.otf")?;
     ///     Ok(())
     /// }
     /// ```
-    pub fn read(path: &str) -> io::Result<Self> {
-        let bytes = fs::read(path)?;
+    pub fn read(path: &str) -> Result<Self, FontError> {
+        Self::read_from_path(path)
+    }
+
+    /// Read and initializes a font container from a file. Same as [`FontContainer::read`],
+    /// but accepts any path type, such as [`Path`] and [`PathBuf`](std::path::PathBuf).
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, FontError> {
+        Self::from_bytes(fs::read(path)?)
+    }
+
+    /// Initialize a font container from the font data in memory. Same as
+    /// [`FontContainer::read`], but without reading a file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rustotf::FontContainer;
+    /// let bytes = std::fs::read("SourceSerif4-Regular.otf").unwrap();
+    /// let font_container = FontContainer::from_bytes(bytes).unwrap();
+    /// ```
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, FontError> {
         let mut font_container = Self::new(bytes);
-        font_container.init();
+        font_container.init()?;
         Ok(font_container)
     }
 
+    /// Read the whole `reader` (e.g. the standard input) and initialize a font container
+    /// from its data.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, FontError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Read and initializes a font container from a file, whose path is given as an OS string
+    /// (e.g. from command line arguments), which need not be valid Unicode.
+    pub fn read_from_path_lossy(path: &OsStr) -> Result<Self, FontError> {
+        Self::read_from_path(Path::new(path))
+    }
+
     /// Create an empty font container.
     fn new(bytes: Vec<u8>) -> Self {
         Self {
             buffer: Buffer::new(bytes),
+            format: ContainerFormat::Sfnt,
             fonts: Vec::new(),
+            lazy: false,
         }
     }
 
-    /// Initialize the font container.
-    fn init(&mut self) {
+    /// Initialize the font container. The file is invalid if the headers or table directories
+    /// are truncated.
+    fn init(&mut self) -> Result<(), FontError> {
         let signature = self.buffer.get();
+        if let Some(err) = self.buffer.take_error() {
+            return Err(err);
+        }
         self.buffer.set_offset(0);
         match signature {
             Self::SIGNATURE_TTC => self.init_ttc(),
             Self::SIGNATURE_WOFF => self.init_woff(),
             Self::SIGNATURE_WOFF2 => self.init_woff2(),
             _ => self.init_otf(),
+        }?;
+        match self.buffer.take_error() {
+            Some(err) => Err(err),
+            None => Ok(()),
         }
     }
 
-    fn init_otf(&mut self) {
-        self.fonts.push(Font::load_sfnt(&mut self.buffer));
+    fn init_otf(&mut self) -> Result<(), FontError> {
+        self.format = ContainerFormat::Sfnt;
+        self.fonts.push(Font::load_sfnt(&mut self.buffer)?);
+        Ok(())
     }
 
     #[allow(unused_variables)]
-    fn init_ttc(&mut self) {
+    fn init_ttc(&mut self) -> Result<(), FontError> {
         let ttc_tag: u32 = self.buffer.get(); // "ttcf"
         let major_version: u16 = self.buffer.get();
         let minor_version: u16 = self.buffer.get();
@@ -97,58 +154,138 @@ impl FontContainer {
         }
 
         for offset in offset_table {
+            // The offset table (12 bytes) of the font should be within the file
+            if offset as usize + 12 > self.buffer.len() {
+                return Err(FontError::BufferOverflow {
+                    offset: offset as usize,
+                    length: 12,
+                    hex_dump: self.buffer.error_hex_dump(offset as usize),
+                });
+            }
             self.buffer.set_offset(offset);
-            self.fonts.push(Font::load_sfnt(&mut self.buffer));
+            self.fonts.push(Font::load_sfnt(&mut self.buffer)?);
         }
+
+        // OpenType Collections share the `ttcf` tag with TrueType Collections.
+        self.format = match self.fonts.first().map(|font| font.flavor) {
+            Some(FontFlavor::Cff) | Some(FontFlavor::Cff2) => ContainerFormat::Otc,
+            _ => ContainerFormat::Ttc,
+        };
+        Ok(())
     }
 
-    fn init_woff(&mut self) {
-        self.fonts.push(Font::load_woff(&mut self.buffer));
+    fn init_woff(&mut self) -> Result<(), FontError> {
+        self.format = ContainerFormat::Woff;
+        self.fonts.push(Font::load_woff(&mut self.buffer)?);
+        Ok(())
     }
 
-    #[allow(unused_variables)]
-    fn init_woff2(&mut self) {
-        let signature: u32 = self.buffer.get();
-        let flavor: u32 = self.buffer.get();
-        match flavor {
-            // TODO: WOFF2 collections
-            Self::SIGNATURE_TTC => unimplemented!(),
-            _ => {
-                self.buffer.set_offset(0);
-                self.fonts.push(Font::load_woff2(&mut self.buffer));
-            }
-        }
+    fn init_woff2(&mut self) -> Result<(), FontError> {
+        self.format = ContainerFormat::Woff2;
+        self.fonts = Font::load_woff2(&mut self.buffer)?;
+        Ok(())
+    }
+
+    /// Parse the tables only when they are requested, which saves time and memory when a few
+    /// tables of a large font (collection) are needed. In lazy mode, [`FontContainer::parse`]
+    /// and [`FontContainer::parse_nth`] do nothing, and the tables are parsed by
+    /// [`FontContainer::parse_and_get_table`] (or [`FontContainer::parse_nth_table`]), together
+    /// with the tables they depend on, e.g. `hhea` and `maxp` for `hmtx`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rustotf::{FontContainer, Tag, Table_hmtx};
+    /// let mut font_container = FontContainer::read("font.ttc").unwrap();
+    /// font_container.set_lazy(true);
+    /// let hmtx: Option<&Table_hmtx> = font_container.parse_and_get_table(3, Tag::new(b"hmtx"));
+    /// ```
+    pub fn set_lazy(&mut self, lazy: bool) {
+        self.lazy = lazy;
     }
 
-    /// Parse all the tables in each font of the container.
-    pub fn parse(&mut self) {
+    /// Return `true` if the tables are parsed on demand. See [`FontContainer::set_lazy`].
+    pub fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    /// Parse all the tables in each font of the container. Parsing stops at the first table
+    /// that fails, leaving the fonts partially parsed. Nothing is parsed in lazy mode.
+    pub fn parse(&mut self) -> Result<(), FontError> {
+        if self.lazy {
+            return Ok(());
+        }
         let buffer = &mut self.buffer;
-        self.fonts.iter_mut().for_each(|font| font.parse(buffer));
+        self.fonts
+            .iter_mut()
+            .try_for_each(|font| font.parse(buffer))
     }
 
-    /// Parse all the tables in the font at `index` of the container.
-    pub fn parse_nth(&mut self, index: usize) {
+    /// Parse all the tables in the font at `index` of the container. Nothing is parsed in
+    /// lazy mode.
+    pub fn parse_nth(&mut self, index: usize) -> Result<(), FontError> {
+        let len = self.len();
         match self.fonts.get_mut(index) {
+            Some(_) if self.lazy => Ok(()),
             Some(font) => font.parse(&mut self.buffer),
-            None => panic!(),
+            None => Err(FontError::FontIndexOutOfRange { index, len }),
         }
     }
 
-    /// Parse the table with `tag` in each font of the container.
-    pub fn parse_table(&mut self, tag: Tag) {
-        // TODO: some tables depend on other tables
+    /// Parse the table with `tag` in each font of the container. In lazy mode, the tables it
+    /// depends on are parsed first (unless they have been parsed).
+    pub fn parse_table(&mut self, tag: Tag) -> Result<(), FontError> {
         let buffer = &mut self.buffer;
+        let lazy = self.lazy;
+        self.fonts.iter_mut().try_for_each(|font| {
+            if lazy {
+                font.parse_dependencies(tag, buffer)?;
+            }
+            font.parse_table(tag, buffer)
+        })
+    }
+
+    /// Parse the table with `tag` in the font at `index` of the container. In lazy mode, the
+    /// tables it depends on are parsed first (unless they have been parsed).
+    pub fn parse_nth_table(&mut self, index: usize, tag: Tag) -> Result<(), FontError> {
+        let len = self.len();
+        let font = self
+            .fonts
+            .get_mut(index)
+            .ok_or(FontError::FontIndexOutOfRange { index, len })?;
+        if !font.table_records.contains(&tag) {
+            return Err(FontError::TableNotFound(tag));
+        }
+        if self.lazy {
+            font.parse_dependencies(tag, &mut self.buffer)?;
+        }
+        font.parse_table(tag, &mut self.buffer)
+    }
+
+    /// Verify the table checksums of each font, and return the tags of the mismatched tables
+    /// for each font. See [`Font::verify_checksums`].
+    pub fn verify_checksums(&self) -> Vec<Vec<Tag>> {
         self.fonts
-            .iter_mut()
-            .for_each(|font| font.parse_table(tag, buffer));
+            .iter()
+            .map(|font| font.verify_checksums(&self.buffer))
+            .collect()
     }
 
-    /// Parse the table with `tag` in the font at `index` of the container.
-    pub fn parse_table_nth(&mut self, tag: Tag, index: usize) {
-        match self.fonts.get_mut(index) {
-            Some(font) => font.parse_table(tag, &mut self.buffer),
-            None => panic!(),
+    /// Parse the table with `tag` in the font at `font_index` of the container (unless it has
+    /// been parsed), and return it as type `T`. Return `None` if there is no such font or
+    /// table, or `T` is not the type of the table.
+    pub fn parse_and_get_table<T: Any>(&mut self, font_index: usize, tag: Tag) -> Option<&T> {
+        let font = self.fonts.get(font_index)?;
+        if font.table(tag)?.is_none() {
+            self.parse_nth_table(font_index, tag).ok()?;
         }
+        self.fonts[font_index].get_table(tag)
+    }
+
+    /// Parse the table with `tag` in the font at `index` of the container.
+    /// Same as [`FontContainer::parse_nth_table`] with the arguments swapped.
+    pub fn parse_table_nth(&mut self, tag: Tag, index: usize) -> Result<(), FontError> {
+        self.parse_nth_table(index, tag)
     }
 
     /// Return the number of [`Font`] objects in the container.
@@ -161,10 +298,78 @@ impl FontContainer {
         self.len() == 0
     }
 
+    /// Return `true` if there is a font at `index` of the container.
+    pub fn contains_font(&self, index: usize) -> bool {
+        index < self.len()
+    }
+
+    /// Return the format of the container.
+    pub fn format(&self) -> ContainerFormat {
+        self.format
+    }
+
+    /// Return `true` if the font container holds more than one font.
+    pub fn is_collection(&self) -> bool {
+        self.len() > 1
+    }
+
+    /// Return the summaries of all the fonts in the container.
+    pub fn infos(&self) -> Vec<FontInfo> {
+        self.fonts.iter().map(Font::info).collect()
+    }
+
     /// Return a reference to a [`Font`] object at given position, or `None` if out of bounds.
     pub fn get(&self, pos: usize) -> Option<&Font> {
         self.fonts.get(pos)
     }
+
+    /// Return a mutable reference to a [`Font`] object at given position, or `None` if out
+    /// of bounds.
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut Font> {
+        self.fonts.get_mut(pos)
+    }
+
+    /// Return the [`Font`] objects in the container as a slice.
+    pub fn fonts(&self) -> &[Font] {
+        &self.fonts
+    }
+
+    /// Return the [`Font`] objects in the container as a mutable slice.
+    pub fn fonts_mut(&mut self) -> &mut [Font] {
+        &mut self.fonts
+    }
+
+    /// Return an iterator over the [`Font`] objects in the container.
+    pub fn iter_fonts(&self) -> impl Iterator<Item = &Font> {
+        self.fonts.iter()
+    }
+
+    /// Return an iterator that allows modifying each [`Font`] object in the container.
+    pub fn iter_fonts_mut(&mut self) -> impl Iterator<Item = &mut Font> {
+        self.fonts.iter_mut()
+    }
+
+    /// Consume the container and return its [`Font`] objects.
+    pub fn into_fonts(self) -> Vec<Font> {
+        self.fonts
+    }
+
+    /// Return the only [`Font`] in the container, or `None` if it is a collection (or empty).
+    pub fn as_single_font(&self) -> Option<&Font> {
+        match self.fonts.as_slice() {
+            [font] => Some(font),
+            _ => None,
+        }
+    }
+
+    /// Consume the container and return its only [`Font`]. If there are no fonts or more than
+    /// one font, the container itself is returned as the error.
+    pub fn into_font(mut self) -> Result<Font, Self> {
+        match self.fonts.len() {
+            1 => Ok(self.fonts.remove(0)),
+            _ => Err(self),
+        }
+    }
 }
 
 impl<'a> IntoIterator for &'a FontContainer {
@@ -176,13 +381,86 @@ impl<'a> IntoIterator for &'a FontContainer {
     }
 }
 
+impl<'a> IntoIterator for &'a mut FontContainer {
+    type Item = &'a mut Font;
+    type IntoIter = IterMut<'a, Font>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fonts.iter_mut()
+    }
+}
+
+/// Print the container format and one line per font, with its index, PostScript name,
+/// outline type, number of glyphs and units per em. Fonts whose `head` table has not
+/// been parsed are shown as `<unparsed>`.
+impl fmt::Display for FontContainer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format = match self.format {
+            ContainerFormat::Sfnt => "SFNT",
+            ContainerFormat::Woff => "WOFF",
+            ContainerFormat::Woff2 => "WOFF2",
+            ContainerFormat::Ttc => "TTC",
+            ContainerFormat::Otc => "OTC",
+        };
+        let names: Vec<String> = self
+            .fonts
+            .iter()
+            .map(|font| match (&font.head, &font.name) {
+                (None, _) => String::from("<unparsed>"),
+                (Some(_), Some(name)) => name
+                    .postscript_name()
+                    .unwrap_or_else(|| String::from("Unknown")),
+                (Some(_), None) => String::from("Unknown"),
+            })
+            .collect();
+        let name_width = names.iter().map(String::len).fold(15, usize::max);
+        writeln!(f, "Format: {}", format)?;
+        writeln!(f, "Fonts:  {}", self.fonts.len())?;
+        write!(
+            f,
+            "\n{:<5}{:<width$}  {:<8}  {:>6}  {:>5}",
+            "#",
+            "PostScript name",
+            "Outlines",
+            "Glyphs",
+            "UPM",
+            width = name_width
+        )?;
+        for (i, (font, name)) in self.fonts.iter().zip(names).enumerate() {
+            let glyph_count = font
+                .maxp
+                .as_ref()
+                .map_or_else(|| String::from("-"), |maxp| maxp.num_glyphs().to_string());
+            let units_per_em = font
+                .head
+                .as_ref()
+                .map_or_else(|| String::from("-"), |head| head.units_per_em.to_string());
+            write!(
+                f,
+                "\n{:<5}{:<width$}  {:<8}  {:>6}  {:>5}",
+                i,
+                name,
+                format!("{:?}", font.outline_type()),
+                glyph_count,
+                units_per_em,
+                width = name_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
 #[allow(non_snake_case)]
 #[derive(Debug, Default)]
 #[rustfmt::skip]
 pub struct Font {
     format: Format,
-    flavor: Flavor,
+    flavor: FontFlavor,
     table_records: TableRecords,
+    /// Offset and length of the brotli-compressed table data in WOFF2 fonts.
+    woff2_stream: (usize, usize),
+    /// The decompressed table data of WOFF2 fonts, kept when the tables are parsed one by one.
+    woff2_tables: Option<woff2::Woff2Tables>,
 
     // Required tables
 
@@ -222,8 +500,8 @@ pub struct Font {
 
     /// Compact Font Format 1.0
     pub CFF_: Option<cff::cff_::Table_CFF_>,
-    // /// Compact Font Format 2.0
-    // pub CFF2: Option<Table_CFF2>,
+    /// Compact Font Format 2.0
+    pub CFF2: Option<cff::cff2::Table_CFF2>,
     /// Vertical Origin (optional table)
     pub VORG: Option<cff::vorg::Table_VORG>,
 
@@ -240,10 +518,10 @@ pub struct Font {
 
     /// Baseline data
     pub BASE: Option<layout::base::Table_BASE>,
-    // /// Glyph definition data
-    // pub GDEF: Option<layout::gdef::Table_GDEF>,
-    // /// Glyph positioning data
-    // pub GPOS: Option<layout::gpos::Table_GPOS>,
+    /// Glyph definition data
+    pub GDEF: Option<layout::gdef::Table_GDEF>,
+    /// Glyph positioning data
+    pub GPOS: Option<layout::gpos::Table_GPOS>,
     /// Glyph substitution data
     pub GSUB: Option<layout::gsub::Table_GSUB>,
     /// Justification data
@@ -255,8 +533,8 @@ pub struct Font {
 
     /// Axis variations.
     pub avar: Option<otvar::avar::Table_avar>,
-    // /// CVT variations (TrueType outlines only)
-    // pub cvar: Option<otvar::cvar::Table_cvar>,
+    /// CVT variations (TrueType outlines only)
+    pub cvar: Option<otvar::cvar::Table_cvar>,
     /// Font variations.
     pub fvar: Option<otvar::fvar::Table_fvar>,
     // /// Glyph variations (TrueType outlines only)
@@ -265,10 +543,10 @@ pub struct Font {
     pub HVAR: Option<otvar::hvar::Table_HVAR>,
     /// Metrics variations.
     pub MVAR: Option<otvar::mvar::Table_MVAR>,
-    // /// Style attributes
-    // pub STAT: Option<otvar::stat::Table_STAT>,
-    // /// Vertical metrics variations
-    // pub VVAR: Option<otvar::vvar::Table_VVAR>,
+    /// Style attributes.
+    pub STAT: Option<otvar::stat::Table_STAT>,
+    /// Vertical metrics variations.
+    pub VVAR: Option<otvar::vvar::Table_VVAR>,
 
     // Tables Related to Color Fonts
 
@@ -303,14 +581,71 @@ pub struct Font {
     // pub PCLT: Option<other::pclt::Table_PCLT>,
     // /// Vertical device metrics
     // pub VDMX: Option<other::vdmx::Table_VDMX>,
-    // /// Vertical Metrics header
-    // pub vhea: Option<other::vhea::Table_vhea>,
-    // /// Vertical Metrics
-    // pub vmtx: Option<other::vmtx::Table_vmtx>,
+    /// Vertical Metrics header
+    pub vhea: Option<other::vhea::Table_vhea>,
+    /// Vertical Metrics
+    pub vmtx: Option<other::vmtx::Table_vmtx>,
 }
 
 impl Font {
-    fn load_sfnt(buffer: &mut Buffer) -> Self {
+    /// Read and parse a single font from a file.
+    ///
+    /// For a font collection, only the offset of the font at `index` is read from the header,
+    /// so the other fonts are never loaded. Other files contain a single font, where `index`
+    /// must be `0`.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors returned by [`fs::read`], [`FontError::FontIndexOutOfRange`] is
+    /// returned if `index` is out of range. Errors in parsing the tables are also returned.
+    pub fn parse_from_ttc_index(path: &str, index: usize) -> Result<Self, FontError> {
+        let mut buffer = Buffer::new(fs::read(path)?);
+        let signature: u32 = buffer.get();
+        // The number of fonts in a WOFF2 collection is stored after the table directory, so
+        // all the fonts are loaded at once.
+        let mut woff2_fonts = Vec::new();
+        let num_fonts = match signature {
+            FontContainer::SIGNATURE_TTC => {
+                // Skip majorVersion and minorVersion.
+                buffer.skip::<u16>(2);
+                buffer.get::<u32>() as usize
+            }
+            FontContainer::SIGNATURE_WOFF2 => {
+                buffer.set_offset(0);
+                woff2_fonts = Self::load_woff2(&mut buffer)?;
+                woff2_fonts.len()
+            }
+            _ => 1,
+        };
+        if index >= num_fonts {
+            return Err(FontError::FontIndexOutOfRange {
+                index,
+                len: num_fonts,
+            });
+        }
+
+        let mut font = match signature {
+            FontContainer::SIGNATURE_TTC => {
+                buffer.skip::<u32>(index);
+                let offset: u32 = buffer.get();
+                buffer.set_offset(offset);
+                Self::load_sfnt(&mut buffer)?
+            }
+            FontContainer::SIGNATURE_WOFF => {
+                buffer.set_offset(0);
+                Self::load_woff(&mut buffer)?
+            }
+            FontContainer::SIGNATURE_WOFF2 => woff2_fonts.swap_remove(index),
+            _ => {
+                buffer.set_offset(0);
+                Self::load_sfnt(&mut buffer)?
+            }
+        };
+        font.parse(&mut buffer)?;
+        Ok(font)
+    }
+
+    fn load_sfnt(buffer: &mut Buffer) -> Result<Self, FontError> {
         let signature: u32 = buffer.get();
         let num_tables: u16 = buffer.get();
         // Skip searchRange, entrySelector and rangeShift.
@@ -327,16 +662,16 @@ impl Font {
                 (tag, record)
             })
             .collect();
-        Self {
+        Ok(Self {
             format: Format::Sfnt,
-            flavor: Flavor::from(signature),
+            flavor: FontFlavor::from_signature(signature)?,
             table_records,
             ..Default::default()
-        }
+        })
     }
 
     #[allow(unused_variables)]
-    fn load_woff(buffer: &mut Buffer) -> Self {
+    fn load_woff(buffer: &mut Buffer) -> Result<Self, FontError> {
         let signature: u32 = buffer.get();
         let flavor: u32 = buffer.get();
         let length: u32 = buffer.get();
@@ -361,20 +696,22 @@ impl Font {
                     comp_length: buffer.get(),
                     length: buffer.get(),
                     checksum: buffer.get(),
+                    ..Default::default()
                 };
                 (tag, record)
             })
             .collect();
-        Self {
+        Ok(Self {
             format: Format::Woff,
-            flavor: Flavor::from(flavor),
+            flavor: FontFlavor::from_signature(flavor)?,
             table_records,
             ..Default::default()
-        }
+        })
     }
 
+    /// Load the fonts in a WOFF2 file, which is a collection if the flavor is `ttcf`.
     #[allow(unused_variables)]
-    fn load_woff2(buffer: &mut Buffer) -> Self {
+    fn load_woff2(buffer: &mut Buffer) -> Result<Vec<Self>, FontError> {
         let signature: u32 = buffer.get();
         let flavor: u32 = buffer.get();
         let length: u32 = buffer.get();
@@ -391,30 +728,72 @@ impl Font {
         let meta_orig_length: u32 = buffer.get();
         let priv_offset: u32 = buffer.get();
         let priv_length: u32 = buffer.get();
-        let table_entries: Vec<Woff2TableEntry> = buffer.get_vec(num_tables);
-        let table_records = table_entries
+        let table_entries = (0..num_tables)
+            .map(|_| Woff2TableEntry::read(buffer))
+            .collect::<Result<Vec<_>, _>>()?;
+        // The tables are stored in the decompressed stream in the order of the table
+        // directory, and the offsets are relative to the stream.
+        let mut offset = 0;
+        let table_records: Vec<(Tag, TableRecord)> = table_entries
             .iter()
             .map(|entry| {
-                let tag = entry.tag;
-                // TODO: checksum and offset in WOFF2
+                // TODO: checksum in WOFF2
                 let record = TableRecord {
                     checksum: 0,
-                    offset: 0,
+                    offset,
                     length: entry.orig_len,
                     comp_length: entry.transform_len,
+                    transform_version: entry.transform_version(),
                 };
-                (tag, record)
+                offset += entry.transform_len;
+                (entry.tag, record)
             })
             .collect();
-        Self {
-            format: Format::Woff2,
-            flavor: Flavor::from(flavor),
-            table_records,
-            ..Default::default()
-        }
+
+        // Flavor and indices of the tables of each font
+        let fonts: Vec<(u32, Vec<usize>)> = if flavor == FontContainer::SIGNATURE_TTC {
+            let version: u32 = buffer.get();
+            let num_fonts: u16_var = buffer.get();
+            (0..u16::from(num_fonts))
+                .map(|_| {
+                    let num_tables: u16_var = buffer.get();
+                    let flavor = buffer.get();
+                    let indices = (0..u16::from(num_tables))
+                        .map(|_| u16::from(buffer.get::<u16_var>()) as usize)
+                        .collect();
+                    (flavor, indices)
+                })
+                .collect()
+        } else {
+            vec![(flavor, (0..num_tables as usize).collect())]
+        };
+        let woff2_stream = (buffer.offset(), total_compressed_size as usize);
+
+        fonts
+            .into_iter()
+            .map(|(flavor, indices)| {
+                let table_records = indices
+                    .iter()
+                    .map(|&i| {
+                        table_records.get(i).cloned().ok_or_else(|| {
+                            FontError::Decompression(format!("invalid table index {}", i))
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Self {
+                    format: Format::Woff2,
+                    flavor: FontFlavor::from_signature(flavor)?,
+                    table_records,
+                    woff2_stream,
+                    ..Default::default()
+                })
+            })
+            .collect()
     }
 
-    pub fn parse(&mut self, buffer: &mut Buffer) {
+    /// Parse all the tables of the font. The required tables are always parsed, and an
+    /// error is returned if any of them is missing.
+    pub fn parse(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         match self.format {
             Format::Sfnt => self.parse_sfnt(buffer),
             Format::Woff => self.parse_woff(buffer),
@@ -422,94 +801,251 @@ impl Font {
         }
     }
 
-    pub fn parse_table(&mut self, tag: Tag, buffer: &mut Buffer) {
+    pub fn parse_table(&mut self, tag: Tag, buffer: &mut Buffer) -> Result<(), FontError> {
         match self.format {
             Format::Sfnt => self.parse_sfnt_table(tag, buffer),
-            Format::Woff => self.parse_woff_table(tag, buffer),
+            Format::Woff => self.parse_woff_table(tag, buffer, &mut Vec::new()),
             Format::Woff2 => self.parse_woff2_table(tag, buffer),
         }
     }
 
-    fn parse_sfnt(&mut self, buffer: &mut Buffer) {
+    /// Parse the tables that are required for parsing the table `tag`, recursively, skipping
+    /// the ones that have been parsed or are not in the font. Parsing `hmtx`, for example,
+    /// requires the number of glyphs in `maxp`.
+    fn parse_dependencies(&mut self, tag: Tag, buffer: &mut Buffer) -> Result<(), FontError> {
+        let dependencies: &[&[u8; 4]] = match tag.bytes() {
+            b"hmtx" => &[b"hhea", b"maxp"],
+            b"vmtx" => &[b"vhea", b"maxp"],
+            b"loca" => &[b"head", b"maxp"],
+            b"glyf" => &[b"loca"],
+            b"sbix" => &[b"maxp"],
+            b"EBDT" => &[b"EBLC"],
+            b"CBDT" => &[b"CBLC"],
+            b"cvar" => &[b"fvar"],
+            _ => &[],
+        };
+        for &dependency in dependencies {
+            let dependency = Tag::new(dependency);
+            if self.table_records.contains(&dependency)
+                && matches!(self.table(dependency), Some(None))
+            {
+                self.parse_dependencies(dependency, buffer)?;
+                self.parse_table(dependency, buffer)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_sfnt(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let required_tables = &[
             b"head", b"hhea", b"maxp", b"hmtx", b"cmap", b"name", b"OS/2", b"post",
         ];
         let tables = &[
             b"loca", b"glyf", b"cvt ", b"fpgm", b"prep", b"gasp", // TrueType
-            b"CFF ", b"VORG", // CFF
-            b"BASE", b"GSUB", b"JSTF", b"MATH", // OpenType layout
+            b"CFF ", b"CFF2", b"VORG", // CFF
+            b"BASE", b"GDEF", b"GPOS", b"GSUB", b"JSTF", b"MATH", // OpenType layout
+            b"avar", b"fvar", b"cvar", b"HVAR", b"MVAR", b"STAT", b"VVAR", // Variations
             b"EBLC", b"EBDT", b"EBSC", // Bitmap
             b"CBLC", b"CBDT", b"COLR", b"CPAL", b"sbix", b"SVG ", // Color
-            b"DSIG", b"LTSH", // Other
+            b"DSIG", b"LTSH", b"vhea", b"vmtx", // Other
         ];
 
         for tag_str in required_tables {
             let tag = Tag::new(tag_str);
-            self.parse_sfnt_table(tag, buffer);
+            self.parse_sfnt_table(tag, buffer)?;
         }
         for tag_str in tables {
             let tag = Tag::new(tag_str);
             if self.table_records.contains(&tag) {
-                self.parse_sfnt_table(tag, buffer);
+                self.parse_sfnt_table(tag, buffer)?;
             }
         }
+        Ok(())
     }
 
-    fn parse_sfnt_table(&mut self, tag: Tag, buffer: &mut Buffer) {
-        buffer.set_offset(self.get_table_offset(tag));
-        self.parse_table_internal(tag, buffer);
+    fn parse_sfnt_table(&mut self, tag: Tag, buffer: &mut Buffer) -> Result<(), FontError> {
+        buffer.set_offset(self.checked_table_offset(tag, buffer)?);
+        self.parse_table_internal(tag, buffer)
     }
 
-    fn parse_woff(&mut self, buffer: &mut Buffer) {
+    fn parse_woff(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let required_tables = &[
             b"head", b"hhea", b"maxp", b"hmtx", b"cmap", b"name", b"OS/2", b"post",
         ];
         let tables = &[
             b"loca", b"glyf", b"cvt ", b"fpgm", b"prep", b"gasp", // TrueType
-            b"CFF ", b"VORG", // CFF
-            b"BASE", b"GSUB", b"JSTF", b"MATH", // OpenType layout
+            b"CFF ", b"CFF2", b"VORG", // CFF
+            b"BASE", b"GDEF", b"GPOS", b"GSUB", b"JSTF", b"MATH", // OpenType layout
+            b"avar", b"fvar", b"cvar", b"HVAR", b"MVAR", b"STAT", b"VVAR", // Variations
             b"EBLC", b"EBDT", b"EBSC", // Bitmap
             b"CBLC", b"CBDT", b"COLR", b"CPAL", b"sbix", b"SVG ", // Color
-            b"DSIG", b"LTSH", // Other
+            b"DSIG", b"LTSH", b"vhea", b"vmtx", // Other
         ];
 
+        // Scratch space for the decompressed tables, shared to avoid reallocating per table
+        let mut scratch = Vec::new();
         for tag_str in required_tables {
             let tag = Tag::new(tag_str);
-            self.parse_woff_table(tag, buffer);
+            self.parse_woff_table(tag, buffer, &mut scratch)?;
         }
         for tag_str in tables {
             let tag = Tag::new(tag_str);
             if self.table_records.contains(&tag) {
-                self.parse_woff_table(tag, buffer);
+                self.parse_woff_table(tag, buffer, &mut scratch)?;
             }
         }
+        Ok(())
     }
 
-    fn parse_woff_table(&mut self, tag: Tag, buffer: &mut Buffer) {
-        buffer.set_offset(self.get_table_offset(tag));
-        let len = self.get_table_len(tag);
-        let comp_len = self.get_table_comp_len(tag);
-        if comp_len < len {
-            match &mut buffer.zlib_decompress(comp_len) {
-                Ok(orig_buffer) => self.parse_table_internal(tag, orig_buffer),
-                Err(_) => panic!(),
-            }
+    fn parse_woff_table(
+        &mut self,
+        tag: Tag,
+        buffer: &mut Buffer,
+        scratch: &mut Vec<u8>,
+    ) -> Result<(), FontError> {
+        buffer.set_offset(self.checked_table_offset(tag, buffer)?);
+        if self.is_table_compressed(tag) {
+            let comp_len = self.get_table_comp_len(tag);
+            buffer
+                .zlib_decompress_into(comp_len, scratch)
+                .map_err(|err| FontError::Decompression(err.to_string()))?;
+            let mut orig_buffer = Buffer::new(mem::take(scratch));
+            let result = self.parse_table_internal(tag, &mut orig_buffer);
+            *scratch = orig_buffer.into_bytes();
+            result
         } else {
-            self.parse_table_internal(tag, buffer);
+            self.parse_table_internal(tag, buffer)
         }
     }
 
-    #[allow(unused_variables)]
-    fn parse_woff2(&mut self, buffer: &mut Buffer) {
-        unimplemented!()
+    fn parse_woff2(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let required_tables = &[
+            b"head", b"hhea", b"maxp", b"hmtx", b"cmap", b"name", b"OS/2", b"post",
+        ];
+        let tables = &[
+            b"loca", b"glyf", b"cvt ", b"fpgm", b"prep", b"gasp", // TrueType
+            b"CFF ", b"CFF2", b"VORG", // CFF
+            b"BASE", b"GDEF", b"GPOS", b"GSUB", b"JSTF", b"MATH", // OpenType layout
+            b"avar", b"fvar", b"cvar", b"HVAR", b"MVAR", b"STAT", b"VVAR", // Variations
+            b"EBLC", b"EBDT", b"EBSC", // Bitmap
+            b"CBLC", b"CBDT", b"COLR", b"CPAL", b"sbix", b"SVG ", // Color
+            b"DSIG", b"LTSH", b"vhea", b"vmtx", // Other
+        ];
+
+        // All the tables are parsed from a single decompression, which is not kept.
+        let mut woff2_tables = match self.woff2_tables.take() {
+            Some(woff2_tables) => woff2_tables,
+            None => self.woff2_decompress(buffer)?,
+        };
+        for tag_str in required_tables {
+            let tag = Tag::new(tag_str);
+            self.parse_woff2_table_internal(tag, &mut woff2_tables)?;
+        }
+        for tag_str in tables {
+            let tag = Tag::new(tag_str);
+            if self.table_records.contains(&tag) {
+                self.parse_woff2_table_internal(tag, &mut woff2_tables)?;
+            }
+        }
+        Ok(())
     }
 
-    #[allow(unused_variables)]
-    fn parse_woff2_table(&mut self, tag: Tag, buffer: &mut Buffer) {
-        unimplemented!()
+    fn parse_woff2_table(&mut self, tag: Tag, buffer: &mut Buffer) -> Result<(), FontError> {
+        let mut woff2_tables = match self.woff2_tables.take() {
+            Some(woff2_tables) => woff2_tables,
+            None => self.woff2_decompress(buffer)?,
+        };
+        let result = self.parse_woff2_table_internal(tag, &mut woff2_tables);
+        self.woff2_tables = Some(woff2_tables);
+        result
+    }
+
+    /// Decompress the table data of a WOFF2 font.
+    fn woff2_decompress(&self, buffer: &mut Buffer) -> Result<woff2::Woff2Tables, FontError> {
+        let (offset, comp_len) = self.woff2_stream;
+        buffer.set_offset(offset);
+        let table_data = buffer
+            .brotli_decompress(comp_len)
+            .map_err(|err| FontError::Decompression(err.to_string()))?;
+        Ok(woff2::Woff2Tables {
+            table_data,
+            glyf: None,
+        })
+    }
+
+    /// Parse the table `tag` in the decompressed table data of a WOFF2 font.
+    fn parse_woff2_table_internal(
+        &mut self,
+        tag: Tag,
+        woff2_tables: &mut woff2::Woff2Tables,
+    ) -> Result<(), FontError> {
+        let transform_version = match self.get(tag).transform_version {
+            Some(transform_version) => transform_version,
+            None => {
+                let table_data = &mut woff2_tables.table_data;
+                table_data.set_offset(self.checked_table_offset(tag, table_data)?);
+                return self.parse_table_internal(tag, table_data);
+            }
+        };
+        let mut table_buffer = match tag.bytes() {
+            // Both tables are reconstructed from the transformed `glyf` table.
+            b"glyf" => Buffer::new(self.reconstruct_glyf(woff2_tables)?.glyf.clone()),
+            b"loca" => Buffer::new(self.reconstruct_glyf(woff2_tables)?.loca.clone()),
+            b"hmtx" => {
+                let num_hor_metrics = self
+                    .hhea
+                    .as_ref()
+                    .ok_or(FontError::TableNotFound(Tag::new(b"hhea")))?
+                    .num_hor_metrics;
+                // The omitted left side bearings are the `xMin` of the reconstructed glyphs.
+                let glyf_tag = Tag::new(b"glyf");
+                if !self.table_records.contains(&glyf_tag)
+                    || self.get(glyf_tag).transform_version.is_none()
+                {
+                    return Err(FontError::Decompression(String::from(
+                        "transformed 'hmtx' requires a transformed 'glyf' table",
+                    )));
+                }
+                let x_mins = self.reconstruct_glyf(woff2_tables)?.x_mins.clone();
+                let table_data = &mut woff2_tables.table_data;
+                table_data.set_offset(self.checked_table_offset(tag, table_data)?);
+                let transformed_hmtx = table_data.slice(0, self.get_table_comp_len(tag));
+                Buffer::new(woff2::reconstruct_hmtx(
+                    transformed_hmtx,
+                    num_hor_metrics,
+                    &x_mins,
+                )?)
+            }
+            _ => {
+                return Err(FontError::UnknownTableFormat {
+                    tag,
+                    format: transform_version as u16,
+                })
+            }
+        };
+        self.parse_table_internal(tag, &mut table_buffer)
+    }
+
+    /// Reconstruct the `glyf` and `loca` tables from the transformed `glyf` table of a WOFF2
+    /// font, unless they have been reconstructed.
+    fn reconstruct_glyf<'a>(
+        &self,
+        woff2_tables: &'a mut woff2::Woff2Tables,
+    ) -> Result<&'a woff2::ReconstructedGlyf, FontError> {
+        if woff2_tables.glyf.is_none() {
+            let glyf_tag = Tag::new(b"glyf");
+            let table_data = &mut woff2_tables.table_data;
+            table_data.set_offset(self.checked_table_offset(glyf_tag, table_data)?);
+            let transformed_glyf = table_data.slice(0, self.get_table_comp_len(glyf_tag));
+            woff2_tables.glyf = Some(woff2::reconstruct_glyf_loca(transformed_glyf)?);
+        }
+        Ok(woff2_tables.glyf.as_ref().unwrap())
     }
 
-    fn parse_table_internal(&mut self, tag: Tag, buffer: &mut Buffer) {
+    /// Parse the table `tag` from the current offset of `buffer`. An undefined format in any
+    /// of the subtables, or a read beyond the end of `buffer`, is reported after the whole
+    /// table has been read.
+    fn parse_table_internal(&mut self, tag: Tag, buffer: &mut Buffer) -> Result<(), FontError> {
         match tag.bytes() {
             b"head" => self.parse_head(buffer),
             b"hhea" => self.parse_hhea(buffer),
@@ -526,23 +1062,25 @@ impl Font {
             b"prep" => self.parse_prep(buffer),
             b"gasp" => self.parse_gasp(buffer),
             b"CFF " => self.parse_CFF_(buffer),
-            // b"CFF2" => self.parse_CFF2(buffer),
+            b"CFF2" => self.parse_CFF2(buffer),
             b"VORG" => self.parse_VORG(buffer),
             b"EBDT" => self.parse_EBDT(buffer),
             b"EBLC" => self.parse_EBLC(buffer),
             b"EBSC" => self.parse_EBSC(buffer),
             b"BASE" => self.parse_BASE(buffer),
+            b"GDEF" => self.parse_GDEF(buffer),
+            b"GPOS" => self.parse_GPOS(buffer),
             b"GSUB" => self.parse_GSUB(buffer),
             b"JSTF" => self.parse_JSTF(buffer),
             b"MATH" => self.parse_MATH(buffer),
             b"avar" => self.parse_avar(buffer),
-            // b"cvar" => self.parse_cvar(buffer),
+            b"cvar" => self.parse_cvar(buffer),
             b"fvar" => self.parse_fvar(buffer),
             // b"gvar" => self.parse_gvar(buffer),
             b"HVAR" => self.parse_HVAR(buffer),
             b"MVAR" => self.parse_MVAR(buffer),
-            // b"STAT" => self.parse_STAT(buffer),
-            // b"VVAR" => self.parse_VVAR(buffer),
+            b"STAT" => self.parse_STAT(buffer),
+            b"VVAR" => self.parse_VVAR(buffer),
             b"COLR" => self.parse_COLR(buffer),
             b"CPAL" => self.parse_CPAL(buffer),
             b"CBDT" => self.parse_CBDT(buffer),
@@ -551,8 +1089,19 @@ impl Font {
             b"SVG " => self.parse_SVG_(buffer),
             b"DSIG" => self.parse_DSIG(buffer),
             b"LTSH" => self.parse_LTSH(buffer),
-            _ => eprintln!("Table `{}` is not supported", tag),
-        };
+            b"vhea" => self.parse_vhea(buffer),
+            b"vmtx" => self.parse_vmtx(buffer),
+            // Unsupported tables are skipped, and `Font::table` returns `None` for them
+            _ => Ok(()),
+        }?;
+        let unknown_format = buffer.take_unknown_format();
+        if let Some(err) = buffer.take_error() {
+            return Err(err.context(&format!("parsing '{}'", tag)));
+        }
+        match unknown_format {
+            Some(format) => Err(FontError::UnknownTableFormat { tag, format }),
+            None => Ok(()),
+        }
     }
 
     // TODO: consider Option<>
@@ -561,6 +1110,28 @@ impl Font {
         self.table_records.get(tag).unwrap()
     }
 
+    /// Return the offset of the table `tag`, checking that the table is in the font and its
+    /// (stored) data are within `buffer`.
+    fn checked_table_offset(&self, tag: Tag, buffer: &Buffer) -> Result<usize, FontError> {
+        let record = self
+            .table_records
+            .get(tag)
+            .ok_or(FontError::TableNotFound(tag))?;
+        let offset = record.offset as usize;
+        let stored_len = match record.comp_length {
+            0 => record.length,
+            comp_length => comp_length,
+        };
+        if offset + stored_len as usize > buffer.len() {
+            return Err(FontError::InvalidOffset {
+                table: tag,
+                offset,
+                hex_dump: buffer.error_hex_dump(offset),
+            });
+        }
+        Ok(offset)
+    }
+
     pub fn get_table_len(&self, tag: Tag) -> usize {
         self.get(tag).length as usize
     }
@@ -573,26 +1144,211 @@ impl Font {
         self.get(tag).comp_length as usize
     }
 
+    /// Return whether the table `tag` is stored compressed (in WOFF fonts). Tables that are
+    /// not in the font are not compressed.
+    pub fn is_table_compressed(&self, tag: Tag) -> bool {
+        self.table_records
+            .get(tag)
+            .is_some_and(TableRecord::is_compressed)
+    }
+
+    /// Return the tables whose checksums don't match the ones in the table directory. Tables
+    /// are padded to 4 bytes, and `checkSumAdjustment` is taken as 0 for the `head` table.
+    /// Compressed tables in WOFF fonts are checked after decompression, and WOFF2 fonts
+    /// have no checksums to verify.
+    pub fn verify_checksums(&self, buffer: &Buffer) -> Vec<Tag> {
+        const CHECKSUM_ADJUSTMENT_OFFSET: usize = 8;
+
+        if matches!(self.format, Format::Woff2) {
+            return Vec::new();
+        }
+        self.table_records
+            .into_iter()
+            .filter(|&(&tag, rec)| {
+                let decompressed;
+                let (data, offset) = if rec.is_compressed() {
+                    decompressed =
+                        buffer.zlib_decompress_at(rec.offset as usize, rec.comp_length as usize);
+                    match &decompressed {
+                        Ok(data) => (data, 0),
+                        Err(_) => return true,
+                    }
+                } else {
+                    (buffer, rec.offset as usize)
+                };
+                let mut checksum = data.calc_checksum(offset, rec.length as usize);
+                if tag == b"head" {
+                    let adjustment = data.calc_checksum(offset + CHECKSUM_ADJUSTMENT_OFFSET, 4);
+                    checksum = checksum.wrapping_sub(adjustment);
+                }
+                checksum != rec.checksum
+            })
+            .map(|(&tag, _)| tag)
+            .collect()
+    }
+
     pub fn contains(&self, s: &str) -> bool {
         self.table_records.contains(&Tag::from(s))
     }
 
+    /// Return the outline flavor of the font. Both CFF and CFF2 fonts use the `OTTO`
+    /// signature, so they are distinguished by the presence of the `CFF2` table.
+    pub fn flavor(&self) -> FontFlavor {
+        match self.flavor {
+            FontFlavor::Cff if self.contains("CFF2") => FontFlavor::Cff2,
+            flavor => flavor,
+        }
+    }
+
+    /// Return the type of the glyph outlines, according to the tables in the font. Unlike
+    /// [`Font::flavor`], it does not depend on the signature of the font file.
+    pub fn outline_type(&self) -> OutlineType {
+        if self.contains("CFF ") {
+            OutlineType::Cff1
+        } else if self.contains("CFF2") {
+            OutlineType::Cff2
+        } else {
+            OutlineType::TrueType
+        }
+    }
+
+    /// Return `true` if the font has glyph outlines, i.e. it is not a bitmap-only font.
+    pub fn has_outlines(&self) -> bool {
+        self.contains("glyf") || self.contains("CFF ") || self.contains("CFF2")
+    }
+
+    /// Check the consistency between the parsed tables, and return a message for each
+    /// mismatch found. Currently the following are checked:
+    ///
+    /// - the Unicode ranges in `OS/2` against `cmap`;
+    /// - the magic number in `head`;
+    /// - the size of `loca` against `indexToLocFormat` in `head` and the number of glyphs
+    ///   in `maxp`;
+    /// - the version of `maxp`, which should be 1.0 for fonts with TrueType outlines.
+    pub fn cross_validate(&self) -> Vec<String> {
+        // Bits 123-127 are reserved
+        const RESERVED_MASKS: [u32; 4] = [0, 0, 0, 0xF800_0000];
+
+        let mut messages = Vec::new();
+        if let (Some(os_2), Some(_)) = (&self.OS_2, self.char_map()) {
+            let computed = self.computed_unicode_ranges();
+            os_2.unicode_ranges()
+                .iter()
+                .zip(computed.iter())
+                .zip(RESERVED_MASKS.iter())
+                .enumerate()
+                .filter(|(_, ((&actual, &expected), &mask))| actual & !mask != expected)
+                .for_each(|(i, ((actual, expected), _))| {
+                    messages.push(format!(
+                        "OS/2 ulUnicodeRange{} is 0x{:08X}, but cmap covers 0x{:08X}",
+                        i + 1,
+                        actual,
+                        expected
+                    ))
+                });
+        }
+        if let Some(head) = &self.head {
+            if head.magic_number != required::head::Table_head::MAGIC_NUMBER {
+                messages.push(format!(
+                    "head magicNumber is 0x{:08X}, but should be 0x{:08X}",
+                    head.magic_number,
+                    required::head::Table_head::MAGIC_NUMBER
+                ));
+            }
+        }
+        if let (Some(head), Some(maxp), Some(_)) = (&self.head, &self.maxp, &self.loca) {
+            let loca_len = self.get_table_len(Tag::new(b"loca"));
+            let expected_len = |offset_size| (maxp.num_glyphs() as usize + 1) * offset_size;
+            let (offset_size, other_size) = if head.is_short_loca() { (2, 4) } else { (4, 2) };
+            if loca_len != expected_len(offset_size) {
+                messages.push(if loca_len == expected_len(other_size) {
+                    // A wrong `indexToLocFormat` makes all the `glyf` offsets meaningless.
+                    format!(
+                        "head indexToLocFormat is {}, but the size of loca ({} bytes) matches the other format",
+                        head.index_to_loc_format, loca_len
+                    )
+                } else {
+                    format!(
+                        "loca is {} bytes, but maxp numGlyphs is {}",
+                        loca_len,
+                        maxp.num_glyphs()
+                    )
+                });
+            }
+        }
+        if let Some(maxp) = &self.maxp {
+            // TrueType outlines need the additional fields of version 1.0.
+            if self.contains("glyf") && !maxp.is_version_10() {
+                messages.push(String::from(
+                    "maxp is not version 1.0, but the font has TrueType outlines",
+                ));
+            }
+        }
+        messages
+    }
+
+    /// Return a summary of the font, from the tables that have been parsed.
+    pub fn info(&self) -> FontInfo {
+        let name = |name_id| self.name.as_ref()?.best_string(name_id);
+        let script_tags: HashSet<Tag> = self
+            .GSUB
+            .iter()
+            .flat_map(|gsub| gsub.script_list.iter().map(|rec| rec.script_tag))
+            .chain(
+                self.GPOS
+                    .iter()
+                    .flat_map(|gpos| gpos.script_list.iter().map(|rec| rec.script_tag)),
+            )
+            .collect();
+        FontInfo {
+            // Prefer the typographic family name
+            family: name(16).or_else(|| name(1)),
+            full_name: name(4),
+            postscript_name: name(6),
+            version: name(5),
+            glyph_count: self
+                .maxp
+                .as_ref()
+                .map_or(0, |maxp| maxp.num_glyphs as usize),
+            units_per_em: self.head.as_ref().map(|head| head.units_per_em),
+            outline_type: self.outline_type(),
+            is_variable: self.contains("fvar"),
+            is_color: ["COLR", "sbix", "CBDT", "SVG "]
+                .iter()
+                .any(|&tag| self.contains(tag)),
+            axis_count: self.fvar.as_ref().map_or(0, |fvar| fvar.axes().len()),
+            script_count: script_tags.len(),
+        }
+    }
+
     pub fn fmt_font_info(&self, indent: &str) -> String {
         #[rustfmt::skip]
         let header = format!(
             concat!(
-                "{0}", "Tag     Checksum      Length      Offset", "\n",
-                "{0}", "----  ----------  ----------  ----------", "\n",
+                "{0}", "Outlines: {1}", "\n\n",
+                "{0}", "Tag     Checksum      Length      Offset  Compressed", "\n",
+                "{0}", "----  ----------  ----------  ----------  ----------", "\n",
             ),
-            indent
+            indent,
+            match (self.has_outlines(), self.outline_type()) {
+                (false, _) => "None",
+                (true, OutlineType::TrueType) => "TrueType",
+                (true, OutlineType::Cff1) => "CFF",
+                (true, OutlineType::Cff2) => "CFF2",
+            },
         );
         let body = self
             .table_records
             .into_iter()
             .map(|(tag, rec)| {
                 format!(
-                    "{}{}  0x{:08X}  {:10}  {:10}",
-                    indent, tag, rec.checksum, rec.length, rec.offset
+                    "{}{}  0x{:08X}  {:10}  {:10}  {:>10}",
+                    indent,
+                    tag,
+                    rec.checksum,
+                    rec.length,
+                    rec.offset,
+                    if rec.is_compressed() { "Yes" } else { "No" },
                 )
             })
             .collect::<Vec<_>>()
@@ -601,26 +1357,136 @@ impl Font {
     }
 
     pub fn fmt_tables(&self, tables: &[&str]) -> String {
+        let tags: Vec<Tag> = match tables.len() {
+            0 => self.table_records.tags.clone(),
+            _ => tables.iter().map(|&s| Tag::from(s)).collect(),
+        };
+        tags.into_iter()
+            .filter(|&tag| self.table(tag).is_some())
+            .map(|tag| self.fmt_table(tag))
+            .collect()
+    }
+
+    /// Write the tables `tables` (or all the tables in the font, if `tables` is empty) to
+    /// `writer`, in the same format as [`Font::fmt_tables`]. Unsupported tables are skipped
+    /// if `tables` is empty, and are errors otherwise.
+    pub fn dump_tables_to_writer<W: io::Write>(
+        &self,
+        tables: &[&str],
+        writer: &mut W,
+    ) -> io::Result<()> {
         match tables.len() {
             0 => self
                 .table_records
                 .into_iter()
-                .map(|(&tag, _)| self.fmt_table(tag))
-                .collect(),
+                .filter(|(&tag, _)| self.table(tag).is_some())
+                .try_for_each(|(&tag, _)| self.dump_table_to_writer(tag, writer)),
             _ => tables
                 .iter()
-                .map(|&s| self.fmt_table(Tag::from(s)))
-                .collect(),
+                .try_for_each(|&s| self.dump_table_to_writer(Tag::from(s), writer)),
+        }
+    }
+
+    /// Write the debug output of the table `tag` to `writer`, except that the `name` table
+    /// is written with its `Display` output. Nothing is written if the table is not parsed,
+    /// and an error of kind [`io::ErrorKind::InvalidInput`] is returned if it is not supported.
+    pub fn dump_table_to_writer<W: io::Write>(&self, tag: Tag, writer: &mut W) -> io::Result<()> {
+        match self.table(tag) {
+            Some(Some(t)) => match t.as_any().downcast_ref::<required::name::Table_name>() {
+                Some(name) => writeln!(writer, "{:#}", name),
+                None => writeln!(writer, "{:#?}", t),
+            },
+            Some(None) => Ok(()),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("table '{}' is not supported", tag),
+            )),
+        }
+    }
+
+    /// Return the tables `tables` (or all the tables in the font, if `tables` is empty) as a
+    /// JSON object keyed by their tags. Tables that are not parsed are skipped, and so are
+    /// those without serde support, unless they are explicitly requested, which is an error.
+    #[cfg(feature = "serde")]
+    pub fn tables_to_json(&self, tables: &[&str]) -> Result<serde_json::Value, FontError> {
+        let tags: Vec<Tag> = match tables.len() {
+            0 => self.table_records.tags.clone(),
+            _ => tables.iter().map(|&s| Tag::from(s)).collect(),
+        };
+        let mut map = serde_json::Map::new();
+        for tag in tags {
+            match self.table_to_json(tag) {
+                Some(Some(value)) => {
+                    map.insert(tag.to_string(), value.map_err(io::Error::from)?);
+                }
+                Some(None) => {}
+                None if tables.is_empty() => {}
+                None => {
+                    return Err(FontError::Io(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("table '{}' can't be serialized as JSON", tag),
+                    )))
+                }
+            }
+        }
+        Ok(serde_json::Value::Object(map))
+    }
+
+    /// Serialize the table `tag`. The outer `Option` is `None` if the table has no serde
+    /// support, and the inner one is `None` if it is not parsed.
+    #[cfg(feature = "serde")]
+    fn table_to_json(&self, tag: Tag) -> Option<Option<serde_json::Result<serde_json::Value>>> {
+        macro_rules! json {
+            ($table:ident) => {{
+                Some(self.$table.as_ref().map(serde_json::to_value))
+            }};
+        }
+        match tag.bytes() {
+            b"head" => json!(head),
+            b"hhea" => json!(hhea),
+            b"maxp" => json!(maxp),
+            b"hmtx" => json!(hmtx),
+            b"cmap" => json!(cmap),
+            b"name" => json!(name),
+            b"OS/2" => json!(OS_2),
+            b"post" => json!(post),
+            b"glyf" => json!(glyf),
+            b"CFF " => json!(CFF_),
+            b"GSUB" => json!(GSUB),
+            b"GPOS" => json!(GPOS),
+            b"MATH" => json!(MATH),
+            _ => None,
         }
     }
 
     fn fmt_table(&self, tag: Tag) -> String {
+        let mut bytes = Vec::new();
+        self.dump_table_to_writer(tag, &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    /// Return the parsed table with `tag` as type `T`, or `None` if the table is not parsed
+    /// or `T` is not the type of the table.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rustotf::{FontContainer, Tag, Table_head};
+    /// let mut font_container = FontContainer::read("font.otf").unwrap();
+    /// font_container.parse().unwrap();
+    /// let font = font_container.get(0).unwrap();
+    /// let head = font.get_table::<Table_head>(Tag::new(b"head")).unwrap();
+    /// ```
+    pub fn get_table<T: Any>(&self, tag: Tag) -> Option<&T> {
+        self.table(tag).flatten()?.as_any().downcast_ref()
+    }
+
+    /// Return the table with `tag`. The outer `Option` is `None` if the table is not
+    /// supported, and the inner one is `None` if it is not parsed.
+    fn table(&self, tag: Tag) -> Option<Option<&dyn AnyTable>> {
         macro_rules! fmt {
             ($table:ident) => {{
-                match &self.$table {
-                    Some(t) => format!("{:#?}\n", t),
-                    _ => "".to_string(),
-                }
+                Some(self.$table.as_ref().map(|t| t as &dyn AnyTable))
             }};
         }
         match tag.bytes() {
@@ -639,23 +1505,25 @@ impl Font {
             b"prep" => fmt!(prep),
             b"gasp" => fmt!(gasp),
             b"CFF " => fmt!(CFF_),
-            // b"CFF2" => fmt!(CFF2),
+            b"CFF2" => fmt!(CFF2),
             b"VORG" => fmt!(VORG),
             b"EBDT" => fmt!(EBDT),
             b"EBLC" => fmt!(EBLC),
             b"EBSC" => fmt!(EBSC),
             b"BASE" => fmt!(BASE),
+            b"GDEF" => fmt!(GDEF),
+            b"GPOS" => fmt!(GPOS),
             b"GSUB" => fmt!(GSUB),
             b"JSTF" => fmt!(JSTF),
             b"MATH" => fmt!(MATH),
             b"avar" => fmt!(avar),
-            // b"cvar" => fmt!(cvar),
+            b"cvar" => fmt!(cvar),
             b"fvar" => fmt!(fvar),
             // b"gvar" => fmt!(gvar),
             b"HVAR" => fmt!(HVAR),
             b"MVAR" => fmt!(MVAR),
-            // b"STAT" => fmt!(STAT),
-            // b"VVAR" => fmt!(VVAR),
+            b"STAT" => fmt!(STAT),
+            b"VVAR" => fmt!(VVAR),
             b"COLR" => fmt!(COLR),
             b"CPAL" => fmt!(CPAL),
             b"CBDT" => fmt!(CBDT),
@@ -664,14 +1532,24 @@ impl Font {
             b"SVG " => fmt!(SVG_),
             b"DSIG" => fmt!(DSIG),
             b"LTSH" => fmt!(LTSH),
-            _ => {
-                eprintln!("Table `{}` is not supported", tag);
-                "".to_string()
-            }
+            b"vhea" => fmt!(vhea),
+            b"vmtx" => fmt!(vmtx),
+            _ => None,
         }
     }
 }
 
+/// A parsed table, which can be printed or downcast to its concrete type.
+trait AnyTable: Any + fmt::Debug {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + fmt::Debug> AnyTable for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 #[derive(Debug, Default)]
 struct TableRecords {
     tags: Vec<Tag>,
@@ -730,13 +1608,86 @@ impl Default for Format {
     }
 }
 
-#[derive(Debug)]
-enum Flavor {
-    Ttf,
+/// The format of a [`FontContainer`], determined by the signature of the file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContainerFormat {
+    /// A single OpenType font (`.ttf` or `.otf`).
+    Sfnt,
+    /// A WOFF 1.0 font.
+    Woff,
+    /// A WOFF 2.0 font or font collection.
+    Woff2,
+    /// A TrueType Collection (`.ttc`).
+    Ttc,
+    /// An OpenType Collection with CFF outlines (`.otc`).
+    Otc,
+}
+
+/// The outline flavor of a [`Font`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FontFlavor {
+    /// TrueType outlines.
+    TrueType,
+    /// CFF (version 1) outlines.
     Cff,
+    /// CFF2 outlines.
+    Cff2,
+}
+
+/// A summary of the key properties of a [`Font`]. See [`Font::info`].
+#[derive(Clone, Debug)]
+pub struct FontInfo {
+    pub family: Option<String>,
+    pub full_name: Option<String>,
+    pub postscript_name: Option<String>,
+    pub version: Option<String>,
+    pub glyph_count: usize,
+    pub units_per_em: Option<u16>,
+    pub outline_type: OutlineType,
+    pub is_variable: bool,
+    pub is_color: bool,
+    pub axis_count: usize,
+    pub script_count: usize,
+}
+
+impl fmt::Display for FontInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let or_unknown = |s: &Option<String>| s.clone().unwrap_or_else(|| String::from("Unknown"));
+        writeln!(f, "Family:          {}", or_unknown(&self.family))?;
+        writeln!(f, "Full name:       {}", or_unknown(&self.full_name))?;
+        writeln!(f, "PostScript name: {}", or_unknown(&self.postscript_name))?;
+        writeln!(f, "Version:         {}", or_unknown(&self.version))?;
+        writeln!(f, "Glyphs:          {}", self.glyph_count)?;
+        match self.units_per_em {
+            Some(units_per_em) => writeln!(f, "Units per em:    {}", units_per_em)?,
+            None => writeln!(f, "Units per em:    Unknown")?,
+        }
+        writeln!(f, "Outlines:        {:?}", self.outline_type)?;
+        match self.is_variable {
+            true => writeln!(f, "Variable:        yes ({} axes)", self.axis_count)?,
+            false => writeln!(f, "Variable:        no")?,
+        }
+        writeln!(
+            f,
+            "Color:           {}",
+            if self.is_color { "yes" } else { "no" }
+        )?;
+        write!(f, "Scripts:         {}", self.script_count)
+    }
 }
 
-impl Flavor {
+/// The type of the glyph outlines of a [`Font`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutlineType {
+    /// TrueType outlines in the `glyf` table.
+    TrueType,
+    /// CFF outlines in the `CFF ` table.
+    Cff1,
+    /// CFF2 outlines in the `CFF2` table.
+    Cff2,
+}
+
+impl FontFlavor {
     /// For OpenType fonts containing CFF data (version 1 or 2), which is `OTTO`.
     const SIGNATURE_OTF: u32 = 0x4F54_544F;
     /// For OpenType fonts that contain TrueType outlines.
@@ -746,31 +1697,41 @@ impl Flavor {
     /// The Apple specification for TrueType fonts allows for `typ1`. Should NOT be used.
     const SIGNATURE_TTF_TYP1: u32 = 0x7479_7031;
 
-    fn from(flavor: u32) -> Self {
+    fn from_signature(flavor: u32) -> Result<Self, FontError> {
         match flavor {
-            Self::SIGNATURE_OTF => Self::Cff,
-            Self::SIGNATURE_TTF | Self::SIGNATURE_TTF_TRUE | Self::SIGNATURE_TTF_TYP1 => Self::Ttf,
-            _ => unreachable!(),
+            Self::SIGNATURE_OTF => Ok(Self::Cff),
+            Self::SIGNATURE_TTF | Self::SIGNATURE_TTF_TRUE | Self::SIGNATURE_TTF_TYP1 => {
+                Ok(Self::TrueType)
+            }
+            _ => Err(FontError::InvalidMagic(flavor)),
         }
     }
 }
 
-impl Default for Flavor {
+impl Default for FontFlavor {
     fn default() -> Self {
-        Self::Ttf
+        Self::TrueType
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 struct TableRecord {
     checksum: u32,
     offset: u32,
     length: u32,
     comp_length: u32,
+    /// The transform version of the table in a WOFF2 font, or `None` if not transformed.
+    transform_version: Option<u8>,
+}
+
+impl TableRecord {
+    /// Whether the table is zlib-compressed in a WOFF font. A `comp_length` of 0 (as in
+    /// SFNT fonts) means that the table is stored at its natural size.
+    fn is_compressed(&self) -> bool {
+        self.comp_length != 0 && self.comp_length < self.length
+    }
 }
 
-// TODO:
-#[allow(dead_code)]
 struct Woff2TableEntry {
     tag: Tag,
     flags: u8,
@@ -779,6 +1740,18 @@ struct Woff2TableEntry {
 }
 
 impl Woff2TableEntry {
+    /// The `glyf` and `loca` tables are transformed by default (version 0), where version 3
+    /// is the null transform. Other tables are only transformed with a non-zero version.
+    fn transform_version(&self) -> Option<u8> {
+        let transform_version = self.flags >> 6;
+        let null_transform = if self.tag == b"glyf" || self.tag == b"loca" {
+            3
+        } else {
+            0
+        };
+        Some(transform_version).filter(|&version| version != null_transform)
+    }
+
     fn to_tag(flag: u8) -> Tag {
         match flag {
             0 => Tag::new(b"cmap"),
@@ -849,8 +1822,8 @@ impl Woff2TableEntry {
     }
 }
 
-impl ReadBuffer for Woff2TableEntry {
-    fn read(buffer: &mut Buffer) -> Self {
+impl Woff2TableEntry {
+    fn read(buffer: &mut Buffer) -> Result<Self, FontError> {
         let flags = buffer.get();
         let table_flag = flags & 0x3F;
         let trans_version: u8 = flags >> 6;
@@ -861,25 +1834,25 @@ impl ReadBuffer for Woff2TableEntry {
                 Tag::from(raw_tag)
             }
         };
-        let orig_len: u32_var = buffer.get();
-        let transform_len: u32_var = if tag == b"glyf" || tag == b"loca" {
+        let orig_len = u32_var::read(buffer)?;
+        let transform_len = if tag == b"glyf" || tag == b"loca" {
             if trans_version == 3 {
                 orig_len
             } else {
-                buffer.get()
+                u32_var::read(buffer)?
             }
         } else {
             if trans_version == 0 {
                 orig_len
             } else {
-                buffer.get()
+                u32_var::read(buffer)?
             }
         };
-        Self {
+        Ok(Self {
             tag,
             flags,
             orig_len: orig_len.into(),
             transform_len: transform_len.into(),
-        }
+        })
     }
 }
@@ -0,0 +1,1078 @@
+//! Constants for registered OpenType Layout tags, which are used to look up scripts,
+//! language systems and features in the `GSUB`, `GPOS`, `BASE` and `JSTF` tables.
+//!
+//! Tags are grouped into the [`feature`], [`script`] and [`language`] modules, since the
+//! same name can appear in more than one group (e.g. `lao ` as a script and `LAO ` as a
+//! language system).
+//!
+//! # Examples
+//!
+//! ```
+//! use rustotf::layout_tags::{feature, script};
+//! use rustotf::Tag;
+//!
+//! assert_eq!(feature::LIGA, Tag::new(b"liga"));
+//! assert_eq!(script::LATN, Tag::new(b"latn"));
+//! ```
+
+use crate::types::Tag;
+
+/// Feature tags.
+///
+/// See: <https://docs.microsoft.com/en-us/typography/opentype/spec/featuretags>.
+pub mod feature {
+    use super::Tag;
+
+    /// Access All Alternates
+    pub const AALT: Tag = Tag::new(b"aalt");
+    /// Above-base Forms
+    pub const ABVF: Tag = Tag::new(b"abvf");
+    /// Above-base Mark Positioning
+    pub const ABVM: Tag = Tag::new(b"abvm");
+    /// Above-base Substitutions
+    pub const ABVS: Tag = Tag::new(b"abvs");
+    /// Alternative Fractions
+    pub const AFRC: Tag = Tag::new(b"afrc");
+    /// Akhand
+    pub const AKHN: Tag = Tag::new(b"akhn");
+    /// Kerning for Alternate Proportional Widths
+    pub const APKN: Tag = Tag::new(b"apkn");
+    /// Below-base Forms
+    pub const BLWF: Tag = Tag::new(b"blwf");
+    /// Below-base Mark Positioning
+    pub const BLWM: Tag = Tag::new(b"blwm");
+    /// Below-base Substitutions
+    pub const BLWS: Tag = Tag::new(b"blws");
+    /// Petite Capitals From Capitals
+    pub const C2PC: Tag = Tag::new(b"c2pc");
+    /// Small Capitals From Capitals
+    pub const C2SC: Tag = Tag::new(b"c2sc");
+    /// Contextual Alternates
+    pub const CALT: Tag = Tag::new(b"calt");
+    /// Case-sensitive Forms
+    pub const CASE: Tag = Tag::new(b"case");
+    /// Glyph Composition / Decomposition
+    pub const CCMP: Tag = Tag::new(b"ccmp");
+    /// Conjunct Form After Ro
+    pub const CFAR: Tag = Tag::new(b"cfar");
+    /// Contextual Half-width Spacing
+    pub const CHWS: Tag = Tag::new(b"chws");
+    /// Conjunct Forms
+    pub const CJCT: Tag = Tag::new(b"cjct");
+    /// Contextual Ligatures
+    pub const CLIG: Tag = Tag::new(b"clig");
+    /// Centered CJK Punctuation
+    pub const CPCT: Tag = Tag::new(b"cpct");
+    /// Capital Spacing
+    pub const CPSP: Tag = Tag::new(b"cpsp");
+    /// Contextual Swash
+    pub const CSWH: Tag = Tag::new(b"cswh");
+    /// Cursive Positioning
+    pub const CURS: Tag = Tag::new(b"curs");
+    /// haracter Variant 1
+    pub const CV01: Tag = Tag::new(b"cv01");
+    /// haracter Variant 2
+    pub const CV02: Tag = Tag::new(b"cv02");
+    /// haracter Variant 3
+    pub const CV03: Tag = Tag::new(b"cv03");
+    /// haracter Variant 4
+    pub const CV04: Tag = Tag::new(b"cv04");
+    /// haracter Variant 5
+    pub const CV05: Tag = Tag::new(b"cv05");
+    /// haracter Variant 6
+    pub const CV06: Tag = Tag::new(b"cv06");
+    /// haracter Variant 7
+    pub const CV07: Tag = Tag::new(b"cv07");
+    /// haracter Variant 8
+    pub const CV08: Tag = Tag::new(b"cv08");
+    /// haracter Variant 9
+    pub const CV09: Tag = Tag::new(b"cv09");
+    /// haracter Variant 10
+    pub const CV10: Tag = Tag::new(b"cv10");
+    /// haracter Variant 11
+    pub const CV11: Tag = Tag::new(b"cv11");
+    /// haracter Variant 12
+    pub const CV12: Tag = Tag::new(b"cv12");
+    /// haracter Variant 13
+    pub const CV13: Tag = Tag::new(b"cv13");
+    /// haracter Variant 14
+    pub const CV14: Tag = Tag::new(b"cv14");
+    /// haracter Variant 15
+    pub const CV15: Tag = Tag::new(b"cv15");
+    /// haracter Variant 16
+    pub const CV16: Tag = Tag::new(b"cv16");
+    /// haracter Variant 17
+    pub const CV17: Tag = Tag::new(b"cv17");
+    /// haracter Variant 18
+    pub const CV18: Tag = Tag::new(b"cv18");
+    /// haracter Variant 19
+    pub const CV19: Tag = Tag::new(b"cv19");
+    /// haracter Variant 20
+    pub const CV20: Tag = Tag::new(b"cv20");
+    /// haracter Variant 21
+    pub const CV21: Tag = Tag::new(b"cv21");
+    /// haracter Variant 22
+    pub const CV22: Tag = Tag::new(b"cv22");
+    /// haracter Variant 23
+    pub const CV23: Tag = Tag::new(b"cv23");
+    /// haracter Variant 24
+    pub const CV24: Tag = Tag::new(b"cv24");
+    /// haracter Variant 25
+    pub const CV25: Tag = Tag::new(b"cv25");
+    /// haracter Variant 26
+    pub const CV26: Tag = Tag::new(b"cv26");
+    /// haracter Variant 27
+    pub const CV27: Tag = Tag::new(b"cv27");
+    /// haracter Variant 28
+    pub const CV28: Tag = Tag::new(b"cv28");
+    /// haracter Variant 29
+    pub const CV29: Tag = Tag::new(b"cv29");
+    /// haracter Variant 30
+    pub const CV30: Tag = Tag::new(b"cv30");
+    /// haracter Variant 31
+    pub const CV31: Tag = Tag::new(b"cv31");
+    /// haracter Variant 32
+    pub const CV32: Tag = Tag::new(b"cv32");
+    /// haracter Variant 33
+    pub const CV33: Tag = Tag::new(b"cv33");
+    /// haracter Variant 34
+    pub const CV34: Tag = Tag::new(b"cv34");
+    /// haracter Variant 35
+    pub const CV35: Tag = Tag::new(b"cv35");
+    /// haracter Variant 36
+    pub const CV36: Tag = Tag::new(b"cv36");
+    /// haracter Variant 37
+    pub const CV37: Tag = Tag::new(b"cv37");
+    /// haracter Variant 38
+    pub const CV38: Tag = Tag::new(b"cv38");
+    /// haracter Variant 39
+    pub const CV39: Tag = Tag::new(b"cv39");
+    /// haracter Variant 40
+    pub const CV40: Tag = Tag::new(b"cv40");
+    /// haracter Variant 41
+    pub const CV41: Tag = Tag::new(b"cv41");
+    /// haracter Variant 42
+    pub const CV42: Tag = Tag::new(b"cv42");
+    /// haracter Variant 43
+    pub const CV43: Tag = Tag::new(b"cv43");
+    /// haracter Variant 44
+    pub const CV44: Tag = Tag::new(b"cv44");
+    /// haracter Variant 45
+    pub const CV45: Tag = Tag::new(b"cv45");
+    /// haracter Variant 46
+    pub const CV46: Tag = Tag::new(b"cv46");
+    /// haracter Variant 47
+    pub const CV47: Tag = Tag::new(b"cv47");
+    /// haracter Variant 48
+    pub const CV48: Tag = Tag::new(b"cv48");
+    /// haracter Variant 49
+    pub const CV49: Tag = Tag::new(b"cv49");
+    /// haracter Variant 50
+    pub const CV50: Tag = Tag::new(b"cv50");
+    /// haracter Variant 51
+    pub const CV51: Tag = Tag::new(b"cv51");
+    /// haracter Variant 52
+    pub const CV52: Tag = Tag::new(b"cv52");
+    /// haracter Variant 53
+    pub const CV53: Tag = Tag::new(b"cv53");
+    /// haracter Variant 54
+    pub const CV54: Tag = Tag::new(b"cv54");
+    /// haracter Variant 55
+    pub const CV55: Tag = Tag::new(b"cv55");
+    /// haracter Variant 56
+    pub const CV56: Tag = Tag::new(b"cv56");
+    /// haracter Variant 57
+    pub const CV57: Tag = Tag::new(b"cv57");
+    /// haracter Variant 58
+    pub const CV58: Tag = Tag::new(b"cv58");
+    /// haracter Variant 59
+    pub const CV59: Tag = Tag::new(b"cv59");
+    /// haracter Variant 60
+    pub const CV60: Tag = Tag::new(b"cv60");
+    /// haracter Variant 61
+    pub const CV61: Tag = Tag::new(b"cv61");
+    /// haracter Variant 62
+    pub const CV62: Tag = Tag::new(b"cv62");
+    /// haracter Variant 63
+    pub const CV63: Tag = Tag::new(b"cv63");
+    /// haracter Variant 64
+    pub const CV64: Tag = Tag::new(b"cv64");
+    /// haracter Variant 65
+    pub const CV65: Tag = Tag::new(b"cv65");
+    /// haracter Variant 66
+    pub const CV66: Tag = Tag::new(b"cv66");
+    /// haracter Variant 67
+    pub const CV67: Tag = Tag::new(b"cv67");
+    /// haracter Variant 68
+    pub const CV68: Tag = Tag::new(b"cv68");
+    /// haracter Variant 69
+    pub const CV69: Tag = Tag::new(b"cv69");
+    /// haracter Variant 70
+    pub const CV70: Tag = Tag::new(b"cv70");
+    /// haracter Variant 71
+    pub const CV71: Tag = Tag::new(b"cv71");
+    /// haracter Variant 72
+    pub const CV72: Tag = Tag::new(b"cv72");
+    /// haracter Variant 73
+    pub const CV73: Tag = Tag::new(b"cv73");
+    /// haracter Variant 74
+    pub const CV74: Tag = Tag::new(b"cv74");
+    /// haracter Variant 75
+    pub const CV75: Tag = Tag::new(b"cv75");
+    /// haracter Variant 76
+    pub const CV76: Tag = Tag::new(b"cv76");
+    /// haracter Variant 77
+    pub const CV77: Tag = Tag::new(b"cv77");
+    /// haracter Variant 78
+    pub const CV78: Tag = Tag::new(b"cv78");
+    /// haracter Variant 79
+    pub const CV79: Tag = Tag::new(b"cv79");
+    /// haracter Variant 80
+    pub const CV80: Tag = Tag::new(b"cv80");
+    /// haracter Variant 81
+    pub const CV81: Tag = Tag::new(b"cv81");
+    /// haracter Variant 82
+    pub const CV82: Tag = Tag::new(b"cv82");
+    /// haracter Variant 83
+    pub const CV83: Tag = Tag::new(b"cv83");
+    /// haracter Variant 84
+    pub const CV84: Tag = Tag::new(b"cv84");
+    /// haracter Variant 85
+    pub const CV85: Tag = Tag::new(b"cv85");
+    /// haracter Variant 86
+    pub const CV86: Tag = Tag::new(b"cv86");
+    /// haracter Variant 87
+    pub const CV87: Tag = Tag::new(b"cv87");
+    /// haracter Variant 88
+    pub const CV88: Tag = Tag::new(b"cv88");
+    /// haracter Variant 89
+    pub const CV89: Tag = Tag::new(b"cv89");
+    /// haracter Variant 90
+    pub const CV90: Tag = Tag::new(b"cv90");
+    /// haracter Variant 91
+    pub const CV91: Tag = Tag::new(b"cv91");
+    /// haracter Variant 92
+    pub const CV92: Tag = Tag::new(b"cv92");
+    /// haracter Variant 93
+    pub const CV93: Tag = Tag::new(b"cv93");
+    /// haracter Variant 94
+    pub const CV94: Tag = Tag::new(b"cv94");
+    /// haracter Variant 95
+    pub const CV95: Tag = Tag::new(b"cv95");
+    /// haracter Variant 96
+    pub const CV96: Tag = Tag::new(b"cv96");
+    /// haracter Variant 97
+    pub const CV97: Tag = Tag::new(b"cv97");
+    /// haracter Variant 98
+    pub const CV98: Tag = Tag::new(b"cv98");
+    /// haracter Variant 99
+    pub const CV99: Tag = Tag::new(b"cv99");
+    /// Distances
+    pub const DIST: Tag = Tag::new(b"dist");
+    /// Discretionary Ligatures
+    pub const DLIG: Tag = Tag::new(b"dlig");
+    /// Denominators
+    pub const DNOM: Tag = Tag::new(b"dnom");
+    /// Dotless Forms
+    pub const DTLS: Tag = Tag::new(b"dtls");
+    /// Expert Forms
+    pub const EXPT: Tag = Tag::new(b"expt");
+    /// Final Glyph on Line Alternates
+    pub const FALT: Tag = Tag::new(b"falt");
+    /// Terminal Forms #2
+    pub const FIN2: Tag = Tag::new(b"fin2");
+    /// Terminal Forms #3
+    pub const FIN3: Tag = Tag::new(b"fin3");
+    /// Terminal Forms
+    pub const FINA: Tag = Tag::new(b"fina");
+    /// Flattened Accent Forms
+    pub const FLAC: Tag = Tag::new(b"flac");
+    /// Fractions
+    pub const FRAC: Tag = Tag::new(b"frac");
+    /// Full Widths
+    pub const FWID: Tag = Tag::new(b"fwid");
+    /// Half Forms
+    pub const HALF: Tag = Tag::new(b"half");
+    /// Halant Forms
+    pub const HALN: Tag = Tag::new(b"haln");
+    /// Alternate Half Widths
+    pub const HALT: Tag = Tag::new(b"halt");
+    /// Historical Forms
+    pub const HIST: Tag = Tag::new(b"hist");
+    /// Horizontal Kana Alternates
+    pub const HKNA: Tag = Tag::new(b"hkna");
+    /// Historical Ligatures
+    pub const HLIG: Tag = Tag::new(b"hlig");
+    /// Hangul
+    pub const HNGL: Tag = Tag::new(b"hngl");
+    /// Hojo Kanji Forms (JIS X 0212-1990 Kanji Forms)
+    pub const HOJO: Tag = Tag::new(b"hojo");
+    /// Half Widths
+    pub const HWID: Tag = Tag::new(b"hwid");
+    /// Initial Forms
+    pub const INIT: Tag = Tag::new(b"init");
+    /// Isolated Forms
+    pub const ISOL: Tag = Tag::new(b"isol");
+    /// Italics
+    pub const ITAL: Tag = Tag::new(b"ital");
+    /// Justification Alternates
+    pub const JALT: Tag = Tag::new(b"jalt");
+    /// JIS78 Forms
+    pub const JP78: Tag = Tag::new(b"jp78");
+    /// JIS83 Forms
+    pub const JP83: Tag = Tag::new(b"jp83");
+    /// JIS90 Forms
+    pub const JP90: Tag = Tag::new(b"jp90");
+    /// JIS2004 Forms
+    pub const JP04: Tag = Tag::new(b"jp04");
+    /// Kerning
+    pub const KERN: Tag = Tag::new(b"kern");
+    /// Left Bounds
+    pub const LFBD: Tag = Tag::new(b"lfbd");
+    /// Standard Ligatures
+    pub const LIGA: Tag = Tag::new(b"liga");
+    /// Leading Jamo Forms
+    pub const LJMO: Tag = Tag::new(b"ljmo");
+    /// Lining Figures
+    pub const LNUM: Tag = Tag::new(b"lnum");
+    /// Localized Forms
+    pub const LOCL: Tag = Tag::new(b"locl");
+    /// Left-to-right Alternates
+    pub const LTRA: Tag = Tag::new(b"ltra");
+    /// Left-to-right Mirrored Forms
+    pub const LTRM: Tag = Tag::new(b"ltrm");
+    /// Mark Positioning
+    pub const MARK: Tag = Tag::new(b"mark");
+    /// Medial Forms #2
+    pub const MED2: Tag = Tag::new(b"med2");
+    /// Medial Forms
+    pub const MEDI: Tag = Tag::new(b"medi");
+    /// Mathematical Greek
+    pub const MGRK: Tag = Tag::new(b"mgrk");
+    /// Mark to Mark Positioning
+    pub const MKMK: Tag = Tag::new(b"mkmk");
+    /// Mark Positioning via Substitution
+    pub const MSET: Tag = Tag::new(b"mset");
+    /// Alternate Annotation Forms
+    pub const NALT: Tag = Tag::new(b"nalt");
+    /// NLC Kanji Forms
+    pub const NLCK: Tag = Tag::new(b"nlck");
+    /// Nukta Forms
+    pub const NUKT: Tag = Tag::new(b"nukt");
+    /// Numerators
+    pub const NUMR: Tag = Tag::new(b"numr");
+    /// Oldstyle Figures
+    pub const ONUM: Tag = Tag::new(b"onum");
+    /// Optical Bounds
+    pub const OPBD: Tag = Tag::new(b"opbd");
+    /// Ordinals
+    pub const ORDN: Tag = Tag::new(b"ordn");
+    /// Ornaments
+    pub const ORNM: Tag = Tag::new(b"ornm");
+    /// Proportional Alternate Widths
+    pub const PALT: Tag = Tag::new(b"palt");
+    /// Petite Capitals
+    pub const PCAP: Tag = Tag::new(b"pcap");
+    /// Proportional Kana
+    pub const PKNA: Tag = Tag::new(b"pkna");
+    /// Proportional Figures
+    pub const PNUM: Tag = Tag::new(b"pnum");
+    /// Pre-base Forms
+    pub const PREF: Tag = Tag::new(b"pref");
+    /// Pre-base Substitutions
+    pub const PRES: Tag = Tag::new(b"pres");
+    /// Post-base Forms
+    pub const PSTF: Tag = Tag::new(b"pstf");
+    /// Post-base Substitutions
+    pub const PSTS: Tag = Tag::new(b"psts");
+    /// Proportional Widths
+    pub const PWID: Tag = Tag::new(b"pwid");
+    /// Quarter Widths
+    pub const QWID: Tag = Tag::new(b"qwid");
+    /// Randomize
+    pub const RAND: Tag = Tag::new(b"rand");
+    /// Required Contextual Alternates
+    pub const RCLT: Tag = Tag::new(b"rclt");
+    /// Rakar Forms
+    pub const RKRF: Tag = Tag::new(b"rkrf");
+    /// Required Ligatures
+    pub const RLIG: Tag = Tag::new(b"rlig");
+    /// Reph Form
+    pub const RPHF: Tag = Tag::new(b"rphf");
+    /// Right Bounds
+    pub const RTBD: Tag = Tag::new(b"rtbd");
+    /// Right-to-left Alternates
+    pub const RTLA: Tag = Tag::new(b"rtla");
+    /// Right-to-left Mirrored Forms
+    pub const RTLM: Tag = Tag::new(b"rtlm");
+    /// Ruby Notation Forms
+    pub const RUBY: Tag = Tag::new(b"ruby");
+    /// Required Variation Alternates
+    pub const RVRN: Tag = Tag::new(b"rvrn");
+    /// Stylistic Alternates
+    pub const SALT: Tag = Tag::new(b"salt");
+    /// Scientific Inferiors
+    pub const SINF: Tag = Tag::new(b"sinf");
+    /// Optical size
+    pub const SIZE: Tag = Tag::new(b"size");
+    /// Small Capitals
+    pub const SMCP: Tag = Tag::new(b"smcp");
+    /// Simplified Forms
+    pub const SMPL: Tag = Tag::new(b"smpl");
+    /// tylistic Set 1
+    pub const SS01: Tag = Tag::new(b"ss01");
+    /// tylistic Set 2
+    pub const SS02: Tag = Tag::new(b"ss02");
+    /// tylistic Set 3
+    pub const SS03: Tag = Tag::new(b"ss03");
+    /// tylistic Set 4
+    pub const SS04: Tag = Tag::new(b"ss04");
+    /// tylistic Set 5
+    pub const SS05: Tag = Tag::new(b"ss05");
+    /// tylistic Set 6
+    pub const SS06: Tag = Tag::new(b"ss06");
+    /// tylistic Set 7
+    pub const SS07: Tag = Tag::new(b"ss07");
+    /// tylistic Set 8
+    pub const SS08: Tag = Tag::new(b"ss08");
+    /// tylistic Set 9
+    pub const SS09: Tag = Tag::new(b"ss09");
+    /// tylistic Set 10
+    pub const SS10: Tag = Tag::new(b"ss10");
+    /// tylistic Set 11
+    pub const SS11: Tag = Tag::new(b"ss11");
+    /// tylistic Set 12
+    pub const SS12: Tag = Tag::new(b"ss12");
+    /// tylistic Set 13
+    pub const SS13: Tag = Tag::new(b"ss13");
+    /// tylistic Set 14
+    pub const SS14: Tag = Tag::new(b"ss14");
+    /// tylistic Set 15
+    pub const SS15: Tag = Tag::new(b"ss15");
+    /// tylistic Set 16
+    pub const SS16: Tag = Tag::new(b"ss16");
+    /// tylistic Set 17
+    pub const SS17: Tag = Tag::new(b"ss17");
+    /// tylistic Set 18
+    pub const SS18: Tag = Tag::new(b"ss18");
+    /// tylistic Set 19
+    pub const SS19: Tag = Tag::new(b"ss19");
+    /// tylistic Set 20
+    pub const SS20: Tag = Tag::new(b"ss20");
+    /// Math Script-style Alternates
+    pub const SSTY: Tag = Tag::new(b"ssty");
+    /// Stretching Glyph Decomposition
+    pub const STCH: Tag = Tag::new(b"stch");
+    /// Subscript
+    pub const SUBS: Tag = Tag::new(b"subs");
+    /// Superscript
+    pub const SUPS: Tag = Tag::new(b"sups");
+    /// Swash
+    pub const SWSH: Tag = Tag::new(b"swsh");
+    /// Titling
+    pub const TITL: Tag = Tag::new(b"titl");
+    /// Trailing Jamo Forms
+    pub const TJMO: Tag = Tag::new(b"tjmo");
+    /// Traditional Name Forms
+    pub const TNAM: Tag = Tag::new(b"tnam");
+    /// Tabular Figures
+    pub const TNUM: Tag = Tag::new(b"tnum");
+    /// Traditional Forms
+    pub const TRAD: Tag = Tag::new(b"trad");
+    /// Third Widths
+    pub const TWID: Tag = Tag::new(b"twid");
+    /// Unicase
+    pub const UNIC: Tag = Tag::new(b"unic");
+    /// Alternate Vertical Metrics
+    pub const VALT: Tag = Tag::new(b"valt");
+    /// Vattu Variants
+    pub const VATU: Tag = Tag::new(b"vatu");
+    /// Vertical Contextual Half-width Spacing
+    pub const VCHW: Tag = Tag::new(b"vchw");
+    /// Vertical Alternates
+    pub const VERT: Tag = Tag::new(b"vert");
+    /// Alternate Vertical Half Metrics
+    pub const VHAL: Tag = Tag::new(b"vhal");
+    /// Vowel Jamo Forms
+    pub const VJMO: Tag = Tag::new(b"vjmo");
+    /// Vertical Kana Alternates
+    pub const VKNA: Tag = Tag::new(b"vkna");
+    /// Vertical Kerning
+    pub const VKRN: Tag = Tag::new(b"vkrn");
+    /// Proportional Alternate Vertical Metrics
+    pub const VPAL: Tag = Tag::new(b"vpal");
+    /// Vertical Alternates and Rotation
+    pub const VRT2: Tag = Tag::new(b"vrt2");
+    /// Vertical Alternates for Rotation
+    pub const VRTR: Tag = Tag::new(b"vrtr");
+    /// Slashed Zero
+    pub const ZERO: Tag = Tag::new(b"zero");
+}
+
+/// Script tags.
+///
+/// See: <https://docs.microsoft.com/en-us/typography/opentype/spec/scripttags>.
+pub mod script {
+    use super::Tag;
+
+    /// Adlam
+    pub const ADLM: Tag = Tag::new(b"adlm");
+    /// Ahom
+    pub const AHOM: Tag = Tag::new(b"ahom");
+    /// Anatolian Hieroglyphs
+    pub const HLUW: Tag = Tag::new(b"hluw");
+    /// Arabic
+    pub const ARAB: Tag = Tag::new(b"arab");
+    /// Armenian
+    pub const ARMN: Tag = Tag::new(b"armn");
+    /// Avestan
+    pub const AVST: Tag = Tag::new(b"avst");
+    /// Balinese
+    pub const BALI: Tag = Tag::new(b"bali");
+    /// Bamum
+    pub const BAMU: Tag = Tag::new(b"bamu");
+    /// Bassa Vah
+    pub const BASS: Tag = Tag::new(b"bass");
+    /// Batak
+    pub const BATK: Tag = Tag::new(b"batk");
+    /// Bengali
+    pub const BENG: Tag = Tag::new(b"beng");
+    /// Bengali v.2
+    pub const BNG2: Tag = Tag::new(b"bng2");
+    /// Bhaiksuki
+    pub const BHKS: Tag = Tag::new(b"bhks");
+    /// Bopomofo
+    pub const BOPO: Tag = Tag::new(b"bopo");
+    /// Brahmi
+    pub const BRAH: Tag = Tag::new(b"brah");
+    /// Braille
+    pub const BRAI: Tag = Tag::new(b"brai");
+    /// Buginese
+    pub const BUGI: Tag = Tag::new(b"bugi");
+    /// Buhid
+    pub const BUHD: Tag = Tag::new(b"buhd");
+    /// Byzantine Music
+    pub const BYZM: Tag = Tag::new(b"byzm");
+    /// Canadian Syllabics
+    pub const CANS: Tag = Tag::new(b"cans");
+    /// Carian
+    pub const CARI: Tag = Tag::new(b"cari");
+    /// Caucasian Albanian
+    pub const AGHB: Tag = Tag::new(b"aghb");
+    /// Chakma
+    pub const CAKM: Tag = Tag::new(b"cakm");
+    /// Cham
+    pub const CHAM: Tag = Tag::new(b"cham");
+    /// Cherokee
+    pub const CHER: Tag = Tag::new(b"cher");
+    /// Chorasmian
+    pub const CHRS: Tag = Tag::new(b"chrs");
+    /// CJK Ideographic
+    pub const HANI: Tag = Tag::new(b"hani");
+    /// Coptic
+    pub const COPT: Tag = Tag::new(b"copt");
+    /// Cypriot Syllabary
+    pub const CPRT: Tag = Tag::new(b"cprt");
+    /// Cypro-Minoan
+    pub const CPMN: Tag = Tag::new(b"cpmn");
+    /// Cyrillic
+    pub const CYRL: Tag = Tag::new(b"cyrl");
+    /// Default
+    pub const DFLT: Tag = Tag::new(b"DFLT");
+    /// Deseret
+    pub const DSRT: Tag = Tag::new(b"dsrt");
+    /// Devanagari
+    pub const DEVA: Tag = Tag::new(b"deva");
+    /// Devanagari v.2
+    pub const DEV2: Tag = Tag::new(b"dev2");
+    /// Dives Akuru
+    pub const DIAK: Tag = Tag::new(b"diak");
+    /// Dogra
+    pub const DOGR: Tag = Tag::new(b"dogr");
+    /// Duployan
+    pub const DUPL: Tag = Tag::new(b"dupl");
+    /// Egyptian Hieroglyphs
+    pub const EGYP: Tag = Tag::new(b"egyp");
+    /// Elbasan
+    pub const ELBA: Tag = Tag::new(b"elba");
+    /// Elymaic
+    pub const ELYM: Tag = Tag::new(b"elym");
+    /// Ethiopic
+    pub const ETHI: Tag = Tag::new(b"ethi");
+    /// Georgian
+    pub const GEOR: Tag = Tag::new(b"geor");
+    /// Glagolitic
+    pub const GLAG: Tag = Tag::new(b"glag");
+    /// Gothic
+    pub const GOTH: Tag = Tag::new(b"goth");
+    /// Grantha
+    pub const GRAN: Tag = Tag::new(b"gran");
+    /// Greek
+    pub const GREK: Tag = Tag::new(b"grek");
+    /// Gujarati
+    pub const GUJR: Tag = Tag::new(b"gujr");
+    /// Gujarati v.2
+    pub const GJR2: Tag = Tag::new(b"gjr2");
+    /// Gunjala Gondi
+    pub const GONG: Tag = Tag::new(b"gong");
+    /// Gurmukhi
+    pub const GURU: Tag = Tag::new(b"guru");
+    /// Gurmukhi v.2
+    pub const GUR2: Tag = Tag::new(b"gur2");
+    /// Hangul
+    pub const HANG: Tag = Tag::new(b"hang");
+    /// Hangul Jamo
+    pub const JAMO: Tag = Tag::new(b"jamo");
+    /// Hanifi Rohingya
+    pub const ROHG: Tag = Tag::new(b"rohg");
+    /// Hanunoo
+    pub const HANO: Tag = Tag::new(b"hano");
+    /// Hatran
+    pub const HATR: Tag = Tag::new(b"hatr");
+    /// Hebrew
+    pub const HEBR: Tag = Tag::new(b"hebr");
+    /// Hiragana and Katakana
+    pub const KANA: Tag = Tag::new(b"kana");
+    /// Imperial Aramaic
+    pub const ARMI: Tag = Tag::new(b"armi");
+    /// Inscriptional Pahlavi
+    pub const PHLI: Tag = Tag::new(b"phli");
+    /// Inscriptional Parthian
+    pub const PRTI: Tag = Tag::new(b"prti");
+    /// Javanese
+    pub const JAVA: Tag = Tag::new(b"java");
+    /// Kaithi
+    pub const KTHI: Tag = Tag::new(b"kthi");
+    /// Kannada
+    pub const KNDA: Tag = Tag::new(b"knda");
+    /// Kannada v.2
+    pub const KND2: Tag = Tag::new(b"knd2");
+    /// Kayah Li
+    pub const KALI: Tag = Tag::new(b"kali");
+    /// Kharosthi
+    pub const KHAR: Tag = Tag::new(b"khar");
+    /// Khitan Small Script
+    pub const KITS: Tag = Tag::new(b"kits");
+    /// Khmer
+    pub const KHMR: Tag = Tag::new(b"khmr");
+    /// Khojki
+    pub const KHOJ: Tag = Tag::new(b"khoj");
+    /// Khudawadi
+    pub const SIND: Tag = Tag::new(b"sind");
+    /// Lao
+    pub const LAO: Tag = Tag::new(b"lao ");
+    /// Latin
+    pub const LATN: Tag = Tag::new(b"latn");
+    /// Lepcha
+    pub const LEPC: Tag = Tag::new(b"lepc");
+    /// Limbu
+    pub const LIMB: Tag = Tag::new(b"limb");
+    /// Linear A
+    pub const LINA: Tag = Tag::new(b"lina");
+    /// Linear B
+    pub const LINB: Tag = Tag::new(b"linb");
+    /// Lisu (Fraser)
+    pub const LISU: Tag = Tag::new(b"lisu");
+    /// Lycian
+    pub const LYCI: Tag = Tag::new(b"lyci");
+    /// Lydian
+    pub const LYDI: Tag = Tag::new(b"lydi");
+    /// Mahajani
+    pub const MAHJ: Tag = Tag::new(b"mahj");
+    /// Makasar
+    pub const MAKA: Tag = Tag::new(b"maka");
+    /// Malayalam
+    pub const MLYM: Tag = Tag::new(b"mlym");
+    /// Malayalam v.2
+    pub const MLM2: Tag = Tag::new(b"mlm2");
+    /// Mandaic, Mandaean
+    pub const MAND: Tag = Tag::new(b"mand");
+    /// Manichaean
+    pub const MANI: Tag = Tag::new(b"mani");
+    /// Marchen
+    pub const MARC: Tag = Tag::new(b"marc");
+    /// Masaram Gondi
+    pub const GONM: Tag = Tag::new(b"gonm");
+    /// Mathematical Alphanumeric Symbols
+    pub const MATH: Tag = Tag::new(b"math");
+    /// Medefaidrin
+    pub const MEDF: Tag = Tag::new(b"medf");
+    /// Meitei Mayek (Meithei, Meetei)
+    pub const MTEI: Tag = Tag::new(b"mtei");
+    /// Mende Kikakui
+    pub const MEND: Tag = Tag::new(b"mend");
+    /// Meroitic Cursive
+    pub const MERC: Tag = Tag::new(b"merc");
+    /// Meroitic Hieroglyphs
+    pub const MERO: Tag = Tag::new(b"mero");
+    /// Miao
+    pub const PLRD: Tag = Tag::new(b"plrd");
+    /// Modi
+    pub const MODI: Tag = Tag::new(b"modi");
+    /// Mongolian
+    pub const MONG: Tag = Tag::new(b"mong");
+    /// Mro
+    pub const MROO: Tag = Tag::new(b"mroo");
+    /// Multani
+    pub const MULT: Tag = Tag::new(b"mult");
+    /// Musical Symbols
+    pub const MUSC: Tag = Tag::new(b"musc");
+    /// Myanmar
+    pub const MYMR: Tag = Tag::new(b"mymr");
+    /// Myanmar v.2
+    pub const MYM2: Tag = Tag::new(b"mym2");
+    /// Nabataean
+    pub const NBAT: Tag = Tag::new(b"nbat");
+    /// Nandinagari
+    pub const NAND: Tag = Tag::new(b"nand");
+    /// Newa
+    pub const NEWA: Tag = Tag::new(b"newa");
+    /// New Tai Lue
+    pub const TALU: Tag = Tag::new(b"talu");
+    /// N'Ko
+    pub const NKO: Tag = Tag::new(b"nko ");
+    /// Nushu
+    pub const NSHU: Tag = Tag::new(b"nshu");
+    /// Nyiakeng Puachue Hmong
+    pub const HMNP: Tag = Tag::new(b"hmnp");
+    /// Odia (formerly Oriya)
+    pub const ORYA: Tag = Tag::new(b"orya");
+    /// Odia v.2 (formerly Oriya v.2)
+    pub const ORY2: Tag = Tag::new(b"ory2");
+    /// Ogham
+    pub const OGAM: Tag = Tag::new(b"ogam");
+    /// Ol Chiki
+    pub const OLCK: Tag = Tag::new(b"olck");
+    /// Old Italic
+    pub const ITAL: Tag = Tag::new(b"ital");
+    /// Old Hungarian
+    pub const HUNG: Tag = Tag::new(b"hung");
+    /// Old North Arabian
+    pub const NARB: Tag = Tag::new(b"narb");
+    /// Old Permic
+    pub const PERM: Tag = Tag::new(b"perm");
+    /// Old Persian Cuneiform
+    pub const XPEO: Tag = Tag::new(b"xpeo");
+    /// Old Sogdian
+    pub const SOGO: Tag = Tag::new(b"sogo");
+    /// Old South Arabian
+    pub const SARB: Tag = Tag::new(b"sarb");
+    /// Old Turkic, Orkhon Runic
+    pub const ORKH: Tag = Tag::new(b"orkh");
+    /// Old Uyghur
+    pub const OUGR: Tag = Tag::new(b"ougr");
+    /// Osage
+    pub const OSGE: Tag = Tag::new(b"osge");
+    /// Osmanya
+    pub const OSMA: Tag = Tag::new(b"osma");
+    /// Pahawh Hmong
+    pub const HMNG: Tag = Tag::new(b"hmng");
+    /// Palmyrene
+    pub const PALM: Tag = Tag::new(b"palm");
+    /// Pau Cin Hau
+    pub const PAUC: Tag = Tag::new(b"pauc");
+    /// Phags-pa
+    pub const PHAG: Tag = Tag::new(b"phag");
+    /// Phoenician
+    pub const PHNX: Tag = Tag::new(b"phnx");
+    /// Psalter Pahlavi
+    pub const PHLP: Tag = Tag::new(b"phlp");
+    /// Rejang
+    pub const RJNG: Tag = Tag::new(b"rjng");
+    /// Runic
+    pub const RUNR: Tag = Tag::new(b"runr");
+    /// Samaritan
+    pub const SAMR: Tag = Tag::new(b"samr");
+    /// Saurashtra
+    pub const SAUR: Tag = Tag::new(b"saur");
+    /// Sharada
+    pub const SHRD: Tag = Tag::new(b"shrd");
+    /// Shavian
+    pub const SHAW: Tag = Tag::new(b"shaw");
+    /// Siddham
+    pub const SIDD: Tag = Tag::new(b"sidd");
+    /// Sign Writing
+    pub const SGNW: Tag = Tag::new(b"sgnw");
+    /// Sinhala
+    pub const SINH: Tag = Tag::new(b"sinh");
+    /// Sogdian
+    pub const SOGD: Tag = Tag::new(b"sogd");
+    /// Sora Sompeng
+    pub const SORA: Tag = Tag::new(b"sora");
+    /// Soyombo
+    pub const SOYO: Tag = Tag::new(b"soyo");
+    /// Sumero-Akkadian Cuneiform
+    pub const XSUX: Tag = Tag::new(b"xsux");
+    /// Sundanese
+    pub const SUND: Tag = Tag::new(b"sund");
+    /// Syloti Nagri
+    pub const SYLO: Tag = Tag::new(b"sylo");
+    /// Syriac
+    pub const SYRC: Tag = Tag::new(b"syrc");
+    /// Tagalog
+    pub const TGLG: Tag = Tag::new(b"tglg");
+    /// Tagbanwa
+    pub const TAGB: Tag = Tag::new(b"tagb");
+    /// Tai Le
+    pub const TALE: Tag = Tag::new(b"tale");
+    /// Tai Tham (Lanna)
+    pub const LANA: Tag = Tag::new(b"lana");
+    /// Tai Viet
+    pub const TAVT: Tag = Tag::new(b"tavt");
+    /// Takri
+    pub const TAKR: Tag = Tag::new(b"takr");
+    /// Tamil
+    pub const TAML: Tag = Tag::new(b"taml");
+    /// Tamil v.2
+    pub const TML2: Tag = Tag::new(b"tml2");
+    /// Tangsa
+    pub const TNSA: Tag = Tag::new(b"tnsa");
+    /// Tangut
+    pub const TANG: Tag = Tag::new(b"tang");
+    /// Telugu
+    pub const TELU: Tag = Tag::new(b"telu");
+    /// Telugu v.2
+    pub const TEL2: Tag = Tag::new(b"tel2");
+    /// Thaana
+    pub const THAA: Tag = Tag::new(b"thaa");
+    /// Thai
+    pub const THAI: Tag = Tag::new(b"thai");
+    /// Tibetan
+    pub const TIBT: Tag = Tag::new(b"tibt");
+    /// Tifinagh
+    pub const TFNG: Tag = Tag::new(b"tfng");
+    /// Tirhuta
+    pub const TIRH: Tag = Tag::new(b"tirh");
+    /// Toto
+    pub const TOTO: Tag = Tag::new(b"toto");
+    /// Ugaritic Cuneiform
+    pub const UGAR: Tag = Tag::new(b"ugar");
+    /// Vai
+    pub const VAII: Tag = Tag::new(b"vaii");
+    /// Vithkuqi
+    pub const VITH: Tag = Tag::new(b"vith");
+    /// Wancho
+    pub const WCHO: Tag = Tag::new(b"wcho");
+    /// Warang Citi
+    pub const WARA: Tag = Tag::new(b"wara");
+    /// Yezidi
+    pub const YEZI: Tag = Tag::new(b"yezi");
+    /// Yi
+    pub const YI: Tag = Tag::new(b"yi  ");
+    /// Zanabazar Square
+    pub const ZANB: Tag = Tag::new(b"zanb");
+}
+
+/// Language system tags. Only commonly used tags are listed here.
+///
+/// See: <https://docs.microsoft.com/en-us/typography/opentype/spec/languagetags>.
+pub mod language {
+    use super::Tag;
+
+    /// Abkhazian
+    pub const ABK: Tag = Tag::new(b"ABK ");
+    /// Afrikaans
+    pub const AFK: Tag = Tag::new(b"AFK ");
+    /// Afar
+    pub const AFR: Tag = Tag::new(b"AFR ");
+    /// Amharic
+    pub const AMH: Tag = Tag::new(b"AMH ");
+    /// Arabic
+    pub const ARA: Tag = Tag::new(b"ARA ");
+    /// Assamese
+    pub const ASM: Tag = Tag::new(b"ASM ");
+    /// Azerbaijani
+    pub const AZE: Tag = Tag::new(b"AZE ");
+    /// Belarussian
+    pub const BEL: Tag = Tag::new(b"BEL ");
+    /// Bengali
+    pub const BEN: Tag = Tag::new(b"BEN ");
+    /// Bulgarian
+    pub const BGR: Tag = Tag::new(b"BGR ");
+    /// Bosnian
+    pub const BOS: Tag = Tag::new(b"BOS ");
+    /// Breton
+    pub const BRE: Tag = Tag::new(b"BRE ");
+    /// Burmese
+    pub const BRM: Tag = Tag::new(b"BRM ");
+    /// Catalan
+    pub const CAT: Tag = Tag::new(b"CAT ");
+    /// Chechen
+    pub const CHE: Tag = Tag::new(b"CHE ");
+    /// Cherokee
+    pub const CHR: Tag = Tag::new(b"CHR ");
+    /// Czech
+    pub const CSY: Tag = Tag::new(b"CSY ");
+    /// Danish
+    pub const DAN: Tag = Tag::new(b"DAN ");
+    /// German
+    pub const DEU: Tag = Tag::new(b"DEU ");
+    /// Dzongkha
+    pub const DZN: Tag = Tag::new(b"DZN ");
+    /// Greek
+    pub const ELL: Tag = Tag::new(b"ELL ");
+    /// English
+    pub const ENG: Tag = Tag::new(b"ENG ");
+    /// Spanish
+    pub const ESP: Tag = Tag::new(b"ESP ");
+    /// Estonian
+    pub const ETI: Tag = Tag::new(b"ETI ");
+    /// Basque
+    pub const EUQ: Tag = Tag::new(b"EUQ ");
+    /// Persian
+    pub const FAR: Tag = Tag::new(b"FAR ");
+    /// Finnish
+    pub const FIN: Tag = Tag::new(b"FIN ");
+    /// Faroese
+    pub const FOS: Tag = Tag::new(b"FOS ");
+    /// French
+    pub const FRA: Tag = Tag::new(b"FRA ");
+    /// Scottish Gaelic
+    pub const GAE: Tag = Tag::new(b"GAE ");
+    /// Galician
+    pub const GAL: Tag = Tag::new(b"GAL ");
+    /// Gujarati
+    pub const GUJ: Tag = Tag::new(b"GUJ ");
+    /// Hausa
+    pub const HAU: Tag = Tag::new(b"HAU ");
+    /// Hindi
+    pub const HIN: Tag = Tag::new(b"HIN ");
+    /// Croatian
+    pub const HRV: Tag = Tag::new(b"HRV ");
+    /// Hungarian
+    pub const HUN: Tag = Tag::new(b"HUN ");
+    /// Armenian
+    pub const HYE: Tag = Tag::new(b"HYE ");
+    /// Igbo
+    pub const IBO: Tag = Tag::new(b"IBO ");
+    /// Indonesian
+    pub const IND: Tag = Tag::new(b"IND ");
+    /// Phonetic transcription—IPA conventions
+    pub const IPPH: Tag = Tag::new(b"IPPH");
+    /// Irish
+    pub const IRI: Tag = Tag::new(b"IRI ");
+    /// Icelandic
+    pub const ISL: Tag = Tag::new(b"ISL ");
+    /// Italian
+    pub const ITA: Tag = Tag::new(b"ITA ");
+    /// Hebrew
+    pub const IWR: Tag = Tag::new(b"IWR ");
+    /// Japanese
+    pub const JAN: Tag = Tag::new(b"JAN ");
+    /// Kannada
+    pub const KAN: Tag = Tag::new(b"KAN ");
+    /// Georgian
+    pub const KAT: Tag = Tag::new(b"KAT ");
+    /// Kazakh
+    pub const KAZ: Tag = Tag::new(b"KAZ ");
+    /// Khmer
+    pub const KHM: Tag = Tag::new(b"KHM ");
+    /// Kirghiz (Kyrgyz)
+    pub const KIR: Tag = Tag::new(b"KIR ");
+    /// Korean
+    pub const KOR: Tag = Tag::new(b"KOR ");
+    /// Kurdish
+    pub const KUR: Tag = Tag::new(b"KUR ");
+    /// Lao
+    pub const LAO: Tag = Tag::new(b"LAO ");
+    /// Latin
+    pub const LAT: Tag = Tag::new(b"LAT ");
+    /// Lithuanian
+    pub const LTH: Tag = Tag::new(b"LTH ");
+    /// Latvian
+    pub const LVI: Tag = Tag::new(b"LVI ");
+    /// Malayalam
+    pub const MAL: Tag = Tag::new(b"MAL ");
+    /// Marathi
+    pub const MAR: Tag = Tag::new(b"MAR ");
+    /// Macedonian
+    pub const MKD: Tag = Tag::new(b"MKD ");
+    /// Malay
+    pub const MLY: Tag = Tag::new(b"MLY ");
+    /// Mongolian
+    pub const MNG: Tag = Tag::new(b"MNG ");
+    /// Moldavian
+    pub const MOL: Tag = Tag::new(b"MOL ");
+    /// Maltese
+    pub const MTS: Tag = Tag::new(b"MTS ");
+    /// Nepali
+    pub const NEP: Tag = Tag::new(b"NEP ");
+    /// Dutch
+    pub const NLD: Tag = Tag::new(b"NLD ");
+    /// Norwegian
+    pub const NOR: Tag = Tag::new(b"NOR ");
+    /// Odia (formerly Oriya)
+    pub const ORI: Tag = Tag::new(b"ORI ");
+    /// Punjabi
+    pub const PAN: Tag = Tag::new(b"PAN ");
+    /// Polish
+    pub const PLK: Tag = Tag::new(b"PLK ");
+    /// Portuguese
+    pub const PTG: Tag = Tag::new(b"PTG ");
+    /// Pashto
+    pub const PUS: Tag = Tag::new(b"PUS ");
+    /// Romanian
+    pub const ROM: Tag = Tag::new(b"ROM ");
+    /// Russian
+    pub const RUS: Tag = Tag::new(b"RUS ");
+    /// Sanskrit
+    pub const SAN: Tag = Tag::new(b"SAN ");
+    /// Slovak
+    pub const SKY: Tag = Tag::new(b"SKY ");
+    /// Slovenian
+    pub const SLV: Tag = Tag::new(b"SLV ");
+    /// Somali
+    pub const SML: Tag = Tag::new(b"SML ");
+    /// Sinhala (Sinhalese)
+    pub const SNH: Tag = Tag::new(b"SNH ");
+    /// Albanian
+    pub const SQI: Tag = Tag::new(b"SQI ");
+    /// Serbian
+    pub const SRB: Tag = Tag::new(b"SRB ");
+    /// Swedish
+    pub const SVE: Tag = Tag::new(b"SVE ");
+    /// Swahili
+    pub const SWK: Tag = Tag::new(b"SWK ");
+    /// Syriac
+    pub const SYR: Tag = Tag::new(b"SYR ");
+    /// Tamil
+    pub const TAM: Tag = Tag::new(b"TAM ");
+    /// Tatar
+    pub const TAT: Tag = Tag::new(b"TAT ");
+    /// Telugu
+    pub const TEL: Tag = Tag::new(b"TEL ");
+    /// Tajiki
+    pub const TGK: Tag = Tag::new(b"TGK ");
+    /// Tagalog
+    pub const TGL: Tag = Tag::new(b"TGL ");
+    /// Thai
+    pub const THA: Tag = Tag::new(b"THA ");
+    /// Tibetan
+    pub const TIB: Tag = Tag::new(b"TIB ");
+    /// Turkish
+    pub const TRK: Tag = Tag::new(b"TRK ");
+    /// Ukrainian
+    pub const UKR: Tag = Tag::new(b"UKR ");
+    /// Urdu
+    pub const URD: Tag = Tag::new(b"URD ");
+    /// Uzbek
+    pub const UZB: Tag = Tag::new(b"UZB ");
+    /// Vietnamese
+    pub const VIT: Tag = Tag::new(b"VIT ");
+    /// Welsh
+    pub const WEL: Tag = Tag::new(b"WEL ");
+    /// Yiddish
+    pub const YID: Tag = Tag::new(b"YID ");
+    /// Yoruba
+    pub const YOR: Tag = Tag::new(b"YOR ");
+    /// Chinese, Traditional, Hong Kong SAR
+    pub const ZHH: Tag = Tag::new(b"ZHH ");
+    /// Chinese, Phonetic
+    pub const ZHP: Tag = Tag::new(b"ZHP ");
+    /// Chinese, Simplified
+    pub const ZHS: Tag = Tag::new(b"ZHS ");
+    /// Chinese, Traditional
+    pub const ZHT: Tag = Tag::new(b"ZHT ");
+    /// Zulu
+    pub const ZUL: Tag = Tag::new(b"ZUL ");
+}
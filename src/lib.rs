@@ -1,7 +1,11 @@
 pub mod cli;
+mod error;
 mod font;
+pub mod layout_tags;
 mod types;
+mod unicode;
 mod util;
+mod woff2;
 mod tables {
     pub mod required {
         pub mod cmap;
@@ -22,10 +26,10 @@ mod tables {
         pub mod prep;
     }
     pub mod cff {
+        pub mod cff2;
         pub mod cff_;
-        // pub mod cff2;
-        mod cff_char_string;
-        mod cff_data;
+        pub mod cff_char_string;
+        pub mod cff_data;
         pub mod vorg;
     }
     pub mod bitmap {
@@ -35,15 +39,22 @@ mod tables {
     }
     pub mod layout {
         pub mod base;
+        pub mod gdef;
+        pub mod gpos;
         pub mod gsub;
         pub mod jstf;
         pub mod math;
     }
     pub mod otvar {
         pub mod avar;
+        pub mod cvar;
         pub mod fvar;
         pub mod hvar;
+        pub mod item_variation_store;
         pub mod mvar;
+        pub mod stat;
+        pub mod tuple_variation_store;
+        pub mod vvar;
     }
     pub mod color {
         pub mod cbdt;
@@ -56,11 +67,14 @@ mod tables {
     pub mod other {
         pub mod dsig;
         pub mod ltsh;
+        pub mod vhea;
+        pub mod vmtx;
     }
 }
 
-pub use font::{Font, FontContainer};
-pub use types::Tag;
+pub use error::FontError;
+pub use font::{ContainerFormat, Font, FontContainer, FontFlavor, FontInfo, OutlineType};
+pub use types::{Fixed, Tag};
 
 #[rustfmt::skip]
 pub use tables::{
@@ -80,11 +94,13 @@ pub use tables::{
         cvt_::Table_cvt_,
         fpgm::Table_fpgm,
         prep::Table_prep,
-        gasp::Table_gasp,
+        gasp::{Table_gasp, GaspBehavior},
     },
     cff::{
-        cff_::Table_CFF_,
-        // cff2::Table_CFF2,
+        cff_::{Table_CFF_, CffFont},
+        cff_char_string::{CharString, CharStringCommand, CharStringOperator},
+        cff2::Table_CFF2,
+        cff_data::cff_subr_bias,
         vorg::Table_VORG,
     },
     bitmap::{
@@ -93,19 +109,26 @@ pub use tables::{
         ebsc::Table_EBSC,
     },
     layout::{
-        base::Table_BASE,
-        gsub::Table_GSUB,
+        base::{Table_BASE, BaseAxis},
+        gdef::{Table_GDEF, AttachList, CaretValue, ClassDef, GlyphClass, LigCaretList},
+        gpos::{Table_GPOS, Anchor, ValueRecord},
+        gsub::{Table_GSUB, LookupFlags, SubstLookupRecord},
         jstf::Table_JSTF,
-        math::Table_MATH,
+        math::{Table_MATH, KernCorner},
     },
     otvar::{
         avar::Table_avar,
+        cvar::Table_cvar,
         fvar::Table_fvar,
         hvar::Table_HVAR,
+        item_variation_store::{ItemVariationStore, DeltaSetIndexMap},
         mvar::Table_MVAR,
+        stat::{Table_STAT, AxisRecord, AxisValue},
+        tuple_variation_store::TupleVariation,
+        vvar::Table_VVAR,
     },
     color::{
-        colr::Table_COLR,
+        colr::{Table_COLR, Paint},
         cpal::Table_CPAL,
         cbdt::Table_CBDT,
         cblc::Table_CBLC,
@@ -115,5 +138,7 @@ pub use tables::{
     other::{
         dsig::Table_DSIG,
         ltsh::Table_LTSH,
+        vhea::Table_vhea,
+        vmtx::Table_vmtx,
     },
 };
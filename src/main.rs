@@ -1,16 +1,42 @@
-use std::io;
 use clap::{self, App, Arg, ArgMatches};
-use rustotf::cli;
+use rustotf::cli::{self, OutputFormat};
+use std::error::Error;
+use std::path::Path;
+use std::process;
 
-fn main() -> io::Result<()> {
+fn main() {
+    // Print the errors with `Display`, which includes the hex dumps of the offending bytes
+    if let Err(err) = run() {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
     let matches = app().get_matches();
-    if let Some(input_path) = matches.value_of("input") {
-        let ttc_indices = parse_arg_ttc_indices(&matches);
-        if matches.is_present("list") {
-            cli::print_font_info(input_path, &ttc_indices)?;
+    if let Some(input_path) = matches.value_of_os("input").map(Path::new) {
+        let ttc_indices = parse_arg_ttc_indices(&matches)?;
+        let output_path = matches.value_of_os("output").map(Path::new);
+        if let Some(offset) = matches.value_of("debug_offset") {
+            cli::print_hex_dump(input_path, parse_arg_offset(offset)?, output_path)?;
+        } else if matches.is_present("validate") {
+            // Exit with an error, so that the validation can be used in scripts
+            match cli::print_checksum_mismatches(input_path, &ttc_indices, output_path)? {
+                0 => {}
+                1 => return Err("1 table has a checksum mismatch".into()),
+                n => return Err(format!("{} tables have checksum mismatches", n).into()),
+            }
+        } else if matches.is_present("list") {
+            cli::print_font_info(input_path, &ttc_indices, output_path)?;
+        } else if matches.is_present("glyph_metrics") {
+            let glyph_ids = parse_arg_glyph_ids(&matches)?;
+            cli::print_glyph_metrics(input_path, &ttc_indices, &glyph_ids, output_path)?;
+        } else if matches.is_present("coverage") {
+            cli::print_char_coverage(input_path, &ttc_indices, output_path)?;
         } else {
             let tables = parse_arg_tables(&matches);
-            cli::print_tables(input_path, &ttc_indices, &tables)?;
+            let format = parse_arg_format(&matches)?;
+            cli::print_tables(input_path, &ttc_indices, &tables, format, output_path)?;
         }
     }
     Ok(())
@@ -32,12 +58,42 @@ fn app() -> App<'static> {
         .short('l')
         .takes_value(false)
         .about("Print some basic information about each table.");
+    let arg_coverage = Arg::new("coverage")
+        .long("coverage")
+        .short('c')
+        .takes_value(false)
+        .about("Print the coverage of each Unicode block.");
+    let arg_validate = Arg::new("validate")
+        .long("validate")
+        .takes_value(false)
+        .about("Verify the table checksums and report the mismatched tables.");
+    let arg_glyph_metrics = Arg::new("glyph_metrics")
+        .long("glyph-metrics")
+        .short('g')
+        .takes_value(true)
+        .min_values(0)
+        .max_values(1)
+        .require_equals(true)
+        .value_name("GLYPH_IDS")
+        .about("Print the metrics of the glyphs (e.g. --glyph-metrics=0-100,200) as tab-separated values. If not specified, then all glyphs will be printed.");
     let arg_tables = Arg::new("tables")
         .long("tables")
         .short('t')
         .takes_value(true)
         .value_name("TABLE")
         .about("Specify a table to dump. If not specified, then all tables will be dumpled.");
+    let arg_format = Arg::new("format")
+        .long("format")
+        .short('f')
+        .takes_value(true)
+        .value_name("FORMAT")
+        .possible_values(&["text", "json"])
+        .about("Set the format of the dumped tables. JSON output requires the `serde` feature.");
+    let arg_debug_offset = Arg::new("debug_offset")
+        .long("debug-offset")
+        .takes_value(true)
+        .value_name("N")
+        .about("Print a hex dump of the bytes around file offset N (decimal or 0x-prefixed hex).");
     let arg_output = Arg::new("output")
         .long("output")
         .short('o')
@@ -52,7 +108,7 @@ fn app() -> App<'static> {
         .about("Select font number(s) for OpenType Collection, starting from 0. If not specified, then all subfonts will be dumpled.");
     let arg_input = Arg::new("input")
         .value_name("INPUT")
-        .about("Specify the input font file.")
+        .about("Specify the input font file, or `-` to read from the standard input.")
         .required_unless_present_all(&["help", "version"]);
     App::new(clap::crate_name!())
         .author(clap::crate_authors!())
@@ -60,22 +116,57 @@ fn app() -> App<'static> {
         .arg(arg_help)
         .arg(arg_version)
         .arg(arg_list)
+        .arg(arg_coverage)
+        .arg(arg_validate)
+        .arg(arg_glyph_metrics)
         .arg(arg_tables)
+        .arg(arg_format)
+        .arg(arg_debug_offset)
         .arg(arg_output)
         .arg(arg_ttc_indices)
         .arg(arg_input)
 }
 
-fn parse_arg_ttc_indices(matches: &ArgMatches) -> Vec<usize> {
+fn parse_arg_ttc_indices(matches: &ArgMatches) -> Result<Vec<usize>, String> {
     match matches.value_of("ttc_indices") {
         Some(value) => value
             .split(',')
-            .map(|s| match s.parse() {
-                Ok(n) => n,
-                Err(_) => panic!("Invalid ttc index {:?}.", s),
-            })
+            .map(|s| s.parse().map_err(|_| format!("invalid ttc index {:?}", s)))
             .collect(),
-        None => Vec::new(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn parse_arg_glyph_ids(matches: &ArgMatches) -> Result<Vec<u16>, String> {
+    let parse = |s: &str| {
+        s.parse::<u16>()
+            .map_err(|_| format!("invalid glyph ID {:?}", s))
+    };
+    let mut glyph_ids = Vec::new();
+    if let Some(value) = matches.value_of("glyph_metrics") {
+        for s in value.split(',') {
+            let (first, last) = s.split_once('-').unwrap_or((s, s));
+            glyph_ids.extend(parse(first)?..=parse(last)?);
+        }
+    }
+    Ok(glyph_ids)
+}
+
+fn parse_arg_offset(value: &str) -> Result<usize, String> {
+    let result = match value.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    result.map_err(|_| format!("invalid offset {:?}", value))
+}
+
+fn parse_arg_format(matches: &ArgMatches) -> Result<OutputFormat, String> {
+    match matches.value_of("format") {
+        Some("json") if cfg!(not(feature = "serde")) => {
+            Err("JSON output requires the `serde` feature".to_string())
+        }
+        Some("json") => Ok(OutputFormat::Json),
+        _ => Ok(OutputFormat::Text),
     }
 }
 
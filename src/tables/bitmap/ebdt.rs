@@ -1,7 +1,9 @@
 use std::mem::size_of;
 
+use crate::error::FontError;
 use crate::font::Font;
 use crate::tables::bitmap::eblc::{BigGlyphMetrics, SmallGlyphMetrics};
+use crate::types::Tag;
 use crate::util::Buffer;
 
 /// ## `EBDT` &mdash; Embedded Bitmap Data Table
@@ -22,10 +24,18 @@ pub struct Table_EBDT {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_EBDT(&mut self, buffer: &mut Buffer) {
+    pub fn parse_EBDT(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let ebdt_start = buffer.offset();
         let version = buffer.get_version::<u16>();
-        let strikes = &self.EBLC.as_ref().unwrap().strikes;
+        let strikes = &self
+            .EBLC
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"EBLC")))?
+            .strikes;
+        let unknown_format = |format: u16| FontError::UnknownTableFormat {
+            tag: Tag::new(b"EBDT"),
+            format,
+        };
         let bitmap_data = strikes
             .iter()
             .map(|strike| {
@@ -35,10 +45,14 @@ impl Font {
                     match index_sub_table.image_format {
                         1 | 2 => {
                             // TODO: only for index format 1 or 3
-                            let sbit = index_sub_table.sbit_offsets.as_ref().unwrap();
+                            let sbit = index_sub_table
+                                .sbit_offsets
+                                .as_ref()
+                                .ok_or_else(|| unknown_format(index_sub_table.image_format))?;
                             (0..sbit.len() - 1).for_each(|i| {
-                                let image_data_size = (sbit[i + 1] - sbit[i]) as usize
-                                    - size_of::<SmallGlyphMetrics>();
+                                let image_data_size = (sbit[i + 1].saturating_sub(sbit[i])
+                                    as usize)
+                                    .saturating_sub(size_of::<SmallGlyphMetrics>());
                                 strike_bitmap_data.push(BitmapData {
                                     small_metrics: Some(buffer.get()),
                                     image_data: Some(buffer.get_vec(image_data_size)),
@@ -48,7 +62,9 @@ impl Font {
                         }
                         5 => {
                             // TODO: only for index format 2
-                            let image_size = index_sub_table.image_size.unwrap();
+                            let image_size = index_sub_table
+                                .image_size
+                                .ok_or_else(|| unknown_format(index_sub_table.image_format))?;
                             let len = index_sub_table.last_glyph_index
                                 - index_sub_table.first_glyph_index
                                 + 1;
@@ -59,17 +75,18 @@ impl Font {
                                 });
                             })
                         }
-                        6 | 7 | 8 | 9 => unimplemented!(),
-                        _ => unreachable!(),
+                        // Formats 6 to 9 (big metrics and components) are not supported yet
+                        format => return Err(unknown_format(format)),
                     }
                 }
-                strike_bitmap_data
+                Ok(strike_bitmap_data)
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         self.EBDT = Some(Table_EBDT {
             version,
             bitmap_data,
         });
+        Ok(())
     }
 }
 
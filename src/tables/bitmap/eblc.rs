@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
@@ -21,7 +22,7 @@ pub struct Table_EBLC {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_EBLC(&mut self, buffer: &mut Buffer) {
+    pub fn parse_EBLC(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let eblc_start = buffer.offset();
         let version = buffer.get_version::<u16>();
         let num_strikes = buffer.get();
@@ -30,6 +31,18 @@ impl Font {
             version,
             num_strikes,
             strikes,
+        });
+        Ok(())
+    }
+}
+
+impl Table_EBLC {
+    /// Return the strike whose vertical ppem is closest to `ppem`, preferring the larger one
+    /// if there are two such strikes.
+    pub fn best_strike_for_ppem(&self, ppem: u8) -> Option<&Strike> {
+        self.strikes.iter().min_by_key(|strike| {
+            let strike_ppem = strike.bitmap_size.ppem_y;
+            ((strike_ppem as i16 - ppem as i16).abs(), strike_ppem < ppem)
         })
     }
 }
@@ -41,12 +54,18 @@ pub struct Strike {
 }
 
 impl Strike {
+    /// Return the index subtable that contains `glyph_id`.
+    pub fn sub_table_for_glyph(&self, glyph_id: u16) -> Option<&IndexSubTable> {
+        self.index_sub_tables.iter().find(|sub_table| {
+            (sub_table.first_glyph_index..=sub_table.last_glyph_index).contains(&glyph_id)
+        })
+    }
+
     pub fn read_vec(buffer: &mut Buffer, num: usize, start: usize) -> Vec<Self> {
         let bitmap_size_vec: Vec<BitmapSize> = buffer.get_vec(num);
         let mut strikes = Vec::new();
         for bitmap_size in bitmap_size_vec {
-            let index_sub_table_start =
-                start + bitmap_size._index_sub_table_offset as usize;
+            let index_sub_table_start = start + bitmap_size._index_sub_table_offset as usize;
             buffer.set_offset(index_sub_table_start);
             let index_sub_table_arrays: Vec<IndexSubTableArray> =
                 buffer.get_vec(bitmap_size._num_index_sub_tables);
@@ -119,6 +138,46 @@ pub struct IndexSubTable {
 }
 
 impl IndexSubTable {
+    /// Return the offset of the bitmap data of `glyph_id` from the beginning of the `EBDT`
+    /// table, or `None` if the glyph has no bitmap in this subtable.
+    pub fn glyph_data_offset(&self, glyph_id: u16) -> Option<u32> {
+        let index = glyph_id.checked_sub(self.first_glyph_index)? as usize;
+        if glyph_id > self.last_glyph_index {
+            return None;
+        }
+        let offset = match self.index_format {
+            1 | 3 => {
+                let sbit_offsets = self.sbit_offsets.as_ref()?;
+                let (offset, next_offset) =
+                    (*sbit_offsets.get(index)?, *sbit_offsets.get(index + 1)?);
+                // Glyphs with the same offset as their successors have no data.
+                if offset == next_offset {
+                    return None;
+                }
+                offset
+            }
+            2 => self.image_size? * index as u32,
+            4 => {
+                let glyph_array = self.glyph_array.as_ref()?;
+                let num_glyphs = self.num_glyphs? as usize;
+                let i = glyph_array[..num_glyphs.min(glyph_array.len())]
+                    .binary_search_by_key(&glyph_id, |pair| pair.glyph_id)
+                    .ok()?;
+                glyph_array[i].sbit_offset as u32
+            }
+            5 => {
+                let i = self
+                    .glyph_id_array
+                    .as_ref()?
+                    .binary_search(&glyph_id)
+                    .ok()?;
+                self.image_size? * i as u32
+            }
+            _ => return None,
+        };
+        Some(self.image_data_offset + offset)
+    }
+
     fn read(buffer: &mut Buffer, array: &IndexSubTableArray) -> Self {
         let index_format = buffer.get();
         let image_format = buffer.get();
@@ -155,7 +214,7 @@ impl IndexSubTable {
                 num_glyphs = Some(buffer.get());
                 glyph_id_array = Some(buffer.get_vec(num_glyphs.unwrap()));
             }
-            _ => unreachable!(),
+            format => buffer.set_unknown_format(format),
         }
         Self {
             first_glyph_index: array.first_glyph_index,
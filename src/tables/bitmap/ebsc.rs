@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::Buffer;
 
@@ -19,5 +20,7 @@ pub struct Table_EBSC {}
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_EBSC(&mut self, _buffer: &mut Buffer) {}
+    pub fn parse_EBSC(&mut self, _buffer: &mut Buffer) -> Result<(), FontError> {
+        Ok(())
+    }
 }
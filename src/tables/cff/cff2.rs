@@ -0,0 +1,220 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::tables::cff::cff_::{_parse_dict, FDSelect, Index, Number, Private};
+use crate::tables::cff::cff_char_string::CharString;
+use crate::tables::otvar::item_variation_store::ItemVariationStore;
+use crate::util::Buffer;
+
+/// ## `CFF2` &mdash; Compact Font Format (CFF) Version 2
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/cff2>.
+///
+/// This table contains a CFF2 font, which is derived from CFF (version 1) with support
+/// of font variations. Compared with CFF, there is no Name INDEX, String INDEX, Encoding
+/// or Charset: the glyph names come from the `post` table and the mapping from characters
+/// from the `cmap` table. The variation data are kept in an item variation store.
+///
+/// The `blend` and `vsindex` operators in charstrings are not supported by the charstring
+/// interpreter yet, so the charstrings are kept uninterpreted.
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+pub struct Table_CFF2 {
+    version: String,
+    header_size: u8,
+    top_dict_length: u16,
+    // Top DICT
+    font_matrix: Vec<Number>,
+    _char_strings_offset: usize,
+    _fd_array_offset: usize,
+    _fd_select_offset: Option<usize>,
+    _vstore_offset: Option<usize>,
+    global_subrs: Vec<CharString>,
+    char_strings: Vec<CharString>,
+    /// The Private DICTs of the Font DICTs in `FDArray`. A CFF2 Font DICT contains nothing
+    /// but its Private DICT.
+    private_dicts: Vec<Private>,
+    fd_select: Option<FDSelect>,
+    pub item_variation_store: Option<ItemVariationStore>,
+}
+
+impl Font {
+    #[allow(non_snake_case)]
+    pub fn parse_CFF2(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let cff2_start = buffer.offset();
+        let version = buffer.get_version::<u8>();
+        let header_size = buffer.get();
+        let top_dict_length = buffer.get();
+        buffer.set_offset_from(cff2_start, header_size);
+
+        let mut cff2 = Table_CFF2 {
+            version,
+            header_size,
+            top_dict_length,
+            font_matrix: Vec::new(),
+            _char_strings_offset: 0,
+            _fd_array_offset: 0,
+            _fd_select_offset: None,
+            _vstore_offset: None,
+            global_subrs: Vec::new(),
+            char_strings: Vec::new(),
+            private_dicts: Vec::new(),
+            fd_select: None,
+            item_variation_store: None,
+        };
+        let top_dict = buffer.get_bytes(top_dict_length as usize);
+        cff2.parse_top_dict(&top_dict);
+        cff2.global_subrs = read_char_strings(buffer);
+
+        // Item variation store, which is preceded by its length
+        if let Some(vstore_offset) = cff2._vstore_offset {
+            buffer.set_offset_from(cff2_start, vstore_offset);
+            buffer.skip::<u16>(1);
+            cff2.item_variation_store = Some(buffer.get());
+        }
+        // Char strings
+        buffer.set_offset_from(cff2_start, cff2._char_strings_offset);
+        cff2.char_strings = read_char_strings(buffer);
+        // FD Array
+        let region_counts: Vec<usize> = match &cff2.item_variation_store {
+            Some(store) => store
+                .item_variation_data
+                .iter()
+                .map(|data| data.region_indexes.len())
+                .collect(),
+            None => Vec::new(),
+        };
+        buffer.set_offset_from(cff2_start, cff2._fd_array_offset);
+        cff2.private_dicts = Index::read_cff2(buffer)
+            .data
+            .iter()
+            .map(|font_dict| {
+                let (private_size, private_offset) = parse_font_dict(font_dict);
+                buffer.set_offset_from(cff2_start, private_offset);
+                Private::read(buffer, private_size, Some(&region_counts))
+            })
+            .collect();
+        // FD Select, which is only required when there are more than one Font DICT
+        if let Some(fd_select_offset) = cff2._fd_select_offset {
+            buffer.set_offset_from(cff2_start, fd_select_offset);
+            cff2.fd_select = Some(FDSelect::read(buffer, cff2.char_strings.len()));
+        }
+
+        self.CFF2 = Some(cff2);
+        Ok(())
+    }
+}
+
+impl Table_CFF2 {
+    fn parse_top_dict(&mut self, top_dict: &[u8]) {
+        // There is no String INDEX in CFF2, so no operator takes a string operand.
+        _parse_dict!(top_dict; Vec::new(); [
+                        {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {},
+                        {}, {}, {}, {}, {}, {}, {},
+            /* 12 07 */ self.font_matrix = _array!(),
+                        {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {},
+                        {}, {}, {}, {}, {}, {},
+            /* 12 36 */ self._fd_array_offset = _integer!() as usize,
+            /* 12 37 */ self._fd_select_offset = Some(_integer!() as usize),
+                        {}, {}, {}, {}, {},
+            /* 17    */ self._char_strings_offset = _integer!() as usize,
+                        {}, {}, {}, {}, {}, {},
+            /* 24    */ self._vstore_offset = Some(_integer!() as usize),
+        ]);
+    }
+
+    /// Return the number of glyphs, i.e. the number of charstrings.
+    pub fn glyph_count(&self) -> usize {
+        self.char_strings.len()
+    }
+
+    /// Return the font matrix. The default is `[0.001, 0, 0, 0.001, 0, 0]`.
+    pub fn font_matrix(&self) -> Vec<f64> {
+        match self.font_matrix.as_slice() {
+            [] => vec![0.001, 0.0, 0.0, 0.001, 0.0, 0.0],
+            font_matrix => font_matrix.iter().map(Number::real).collect(),
+        }
+    }
+
+    /// Return the (uninterpreted) charstring of `glyph_id`.
+    pub fn char_string(&self, glyph_id: usize) -> Option<&CharString> {
+        self.char_strings.get(glyph_id)
+    }
+
+    /// Return the number of global subroutines.
+    pub fn global_subrs_count(&self) -> usize {
+        self.global_subrs.len()
+    }
+
+    /// Return the number of Font DICTs in `FDArray`.
+    pub fn fd_count(&self) -> usize {
+        self.private_dicts.len()
+    }
+
+    /// Return the index of the Font DICT used by the glyph. Without `FDSelect`, all the
+    /// glyphs use the first Font DICT.
+    pub fn fd_index(&self, glyph_id: usize) -> Option<usize> {
+        if glyph_id >= self.char_strings.len() {
+            return None;
+        }
+        match &self.fd_select {
+            Some(fd_select) => fd_select.fd_index(glyph_id),
+            None => Some(0),
+        }
+    }
+
+    /// Return the Private DICT used by the glyph, selected by `FDSelect`.
+    pub fn private_for_glyph(&self, glyph_id: usize) -> Option<&Private> {
+        self.private_dicts.get(self.fd_index(glyph_id)?)
+    }
+}
+
+/// Return the size and offset of the Private DICT of a Font DICT.
+fn parse_font_dict(font_dict: &[u8]) -> (usize, usize) {
+    let mut private = (0, 0);
+    _parse_dict!(font_dict; Vec::new(); [
+                    {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {},
+                    {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {},
+                    {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {},
+                    {}, {}, {}, {}, {},
+        /* 18    */ private = _private!(),
+                    {}, {}, {}, {}, {}, {},
+    ]);
+    private
+}
+
+fn read_char_strings(buffer: &mut Buffer) -> Vec<CharString> {
+    Index::read_cff2(buffer)
+        .data
+        .into_iter()
+        .map(CharString::from)
+        .collect()
+}
+
+#[test]
+fn test_cff2() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        2, 0, 5, 0, 19, // header
+        29, 0, 0, 0, 60, 17, 29, 0, 0, 0, 70, 12, 36, 29, 0, 0, 0, 28, 24, // top dict
+        0, 0, 0, 0, // global subrs
+        0, 30, 0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22, // vstore: 1 item variation data
+        0, 1, 0, 1, 0, 0, 64, 0, 64, 0, // 1 axis, 1 region
+        0, 0, 0, 0, 0, 1, 0, 0, // no items, 1 region
+        0, 0, 0, 2, 1, 1, 2, 3, 139, 139, // char strings
+        0, 0, 0, 1, 1, 1, 12, 29, 0, 0, 0, 5, 29, 0, 0, 0, 88, 18, // FD array
+        144, 239, 140, 23, 19, // private dict: `5 100 1 blend subrs`
+        0, 0, 0, 1, 1, 1, 2, 11, // subrs
+    ]);
+    let mut font = Font::default();
+    font.parse_CFF2(&mut buffer).unwrap();
+    let cff2 = font.CFF2.unwrap();
+    assert_eq!(cff2.glyph_count(), 2);
+    assert_eq!(cff2.global_subrs_count(), 0);
+    assert_eq!(cff2.fd_count(), 1);
+    assert_eq!(cff2.fd_index(1), Some(0));
+    assert_eq!(cff2.fd_index(2), None);
+    assert_eq!(cff2.private_for_glyph(0).unwrap().subrs_count(), 1);
+    assert_eq!(cff2.font_matrix()[0], 0.001);
+    let store = cff2.item_variation_store.unwrap();
+    assert_eq!(store.variation_regions.len(), 1);
+}
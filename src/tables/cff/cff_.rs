@@ -1,9 +1,10 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::tables::cff::{cff_char_string::*, cff_data::*};
-use crate::types::u24;
+use crate::types::{u24, Tag};
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
-use std::fmt;
+use std::{collections::HashMap, fmt, str};
 
 /// ## `CFF` &mdash; Compact Font Format table
 ///
@@ -16,6 +17,7 @@ use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_CFF_ {
     version: String,
     header_size: u8,
@@ -26,17 +28,17 @@ pub struct Table_CFF_ {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_CFF_(&mut self, buffer: &mut Buffer) {
+    pub fn parse_CFF_(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let cff_start = buffer.offset();
         let version = buffer.get_version::<u8>();
         let header_size = buffer.get();
         let offset_size = buffer.get();
         buffer.set_offset_from(cff_start, header_size);
 
-        let names = buffer.get::<Index>().to_string_vec();
+        let names = buffer.get::<Index>().to_string_vec()?;
         let top_dicts = buffer.get::<Index>().data;
-        let strings = buffer.get::<Index>().to_string_vec();
-        let mut global_subrs = buffer
+        let strings = buffer.get::<Index>().to_string_vec()?;
+        let global_subrs: Vec<CharString> = buffer
             .get::<Index>()
             .data
             .into_iter()
@@ -47,11 +49,11 @@ impl Font {
             .zip(top_dicts.iter())
             .map(|(name, top_dict)| {
                 let mut cff = CffFont::new(name);
-                cff.parse(buffer, cff_start, top_dict, &strings);
-                cff.parse_char_strings(&mut global_subrs);
-                cff
+                cff.parse(buffer, cff_start, top_dict, &strings)?;
+                cff.parse_char_strings(&global_subrs)?;
+                Ok(cff)
             })
-            .collect();
+            .collect::<Result<_, FontError>>()?;
 
         self.CFF_ = Some(Table_CFF_ {
             version,
@@ -60,6 +62,43 @@ impl Font {
             cff_fonts,
             global_subrs,
         });
+        Ok(())
+    }
+}
+
+impl Table_CFF_ {
+    /// Return the fonts in the CFF table. An OpenType font contains exactly one of them.
+    pub fn cff_fonts(&self) -> &[CffFont] {
+        &self.cff_fonts
+    }
+
+    /// Return `true` if the font at `font_index` is a CID-keyed font.
+    pub fn is_cid_font(&self, font_index: usize) -> bool {
+        self.cff_fonts
+            .get(font_index)
+            .is_some_and(CffFont::is_cid_font)
+    }
+
+    /// Return the number of global subroutines.
+    pub fn global_subrs_count(&self) -> usize {
+        self.global_subrs.len()
+    }
+
+    /// Return the code-to-GID mapping of the encoding of the font at `index`.
+    /// See [`CffFont::encoding_map`].
+    pub fn encoding_for_font(&self, index: usize) -> Option<HashMap<u8, u16>> {
+        self.cff_fonts.get(index).map(CffFont::encoding_map)
+    }
+
+    /// Return the bounding box of the (first) font, rounded to integers.
+    pub fn font_bbox(&self) -> Option<(i32, i32, i32, i32)> {
+        let (x_min, y_min, x_max, y_max) = self.cff_fonts.first()?.font_bbox();
+        Some((
+            x_min.round() as i32,
+            y_min.round() as i32,
+            x_max.round() as i32,
+            y_max.round() as i32,
+        ))
     }
 }
 
@@ -120,6 +159,9 @@ macro_rules! _parse_dict {
             $e19:expr,
             $e20:expr,
             $e21:expr,
+            $e22:expr,
+            $e23:expr,
+            $e24:expr,
         ]
     ) => {{
         let mut i = 0;
@@ -127,7 +169,7 @@ macro_rules! _parse_dict {
 
         macro_rules! _num {
             () => {
-                temp.pop().unwrap()
+                temp.pop().unwrap_or(Number::Int(0))
             };
         }
         macro_rules! _integer {
@@ -159,16 +201,25 @@ macro_rules! _parse_dict {
         }
         macro_rules! _private {
             () => {{
-                let num2 = temp.pop().unwrap().int() as usize;
-                let num1 = temp.pop().unwrap().int() as usize;
+                let num2 = _integer!() as usize;
+                let num1 = _integer!() as usize;
                 (num1, num2)
             }};
         }
+        // Keep the default values only, i.e. the values at the default instance of a variable
+        // font, and drop the deltas of the `region_count` regions.
+        macro_rules! _blend {
+            ($region_count:expr) => {{
+                let n = _integer!() as usize;
+                let deltas = n * $region_count;
+                temp.truncate(temp.len().saturating_sub(deltas));
+            }};
+        }
         macro_rules! _ros {
             () => {{
-                let supplement = temp.pop().unwrap().int();
-                let index_o = temp.pop().unwrap().int() as usize;
-                let index_r = temp.pop().unwrap().int() as usize;
+                let supplement = _integer!();
+                let index_o = _integer!() as usize;
+                let index_r = _integer!() as usize;
                 Ros::new(index_r, index_o, supplement, $strings)
             }};
         }
@@ -198,6 +249,9 @@ macro_rules! _parse_dict {
                 19 => $e19,
                 20 => $e20,
                 21 => $e21,
+                22 => $e22,
+                23 => $e23,
+                24 => $e24,
                 // Operators: two bytes
                 12 => {
                     let b1 = $data[i + 1];
@@ -233,7 +287,8 @@ macro_rules! _parse_dict {
                         36 => $e12_36,
                         37 => $e12_37,
                         38 => $e12_38,
-                        _ => unreachable!(),
+                        // Reserved operators are ignored, together with their operands
+                        _ => temp.clear(),
                     }
                     i += 1;
                 }
@@ -285,7 +340,8 @@ macro_rules! _parse_dict {
                                         i += 1;
                                         break;
                                     }
-                                    _ => unreachable!(),
+                                    // Reserved nibble
+                                    _ => {}
                                 }
                             };
                         }
@@ -295,14 +351,17 @@ macro_rules! _parse_dict {
                         i += 1;
                     }
                 }
-                _ => unreachable!(),
+                _ => temp.clear(),
             }
             i += 1;
         }
     }};
 }
 
+pub(super) use _parse_dict;
+
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CffFont {
     // Name
     name: String,
@@ -334,6 +393,7 @@ pub struct CffFont {
     // Charsets
     _charset_offset: usize,
     charset: Vec<String>,
+    cids: Vec<u16>,
     // Char strings
     _char_strings_offset: usize,
     char_strings: Vec<CharString>,
@@ -390,7 +450,7 @@ impl CffFont {
         cff_start: usize,
         top_dict: &[u8],
         strings: &[String],
-    ) {
+    ) -> Result<(), FontError> {
         self.parse_top_dict(top_dict, strings);
         // Encoding
         self.encoding = match self._encoding_offset {
@@ -411,52 +471,54 @@ impl CffFont {
             .map(CharString::from)
             .collect();
         // Charset
-        macro_rules! _get_charsets {
-            ($t:ty) => {{
-                // ".notdef" is omitted in the array.
-                let mut count = 1;
-                let mut result = vec![CFF_STANDARD_STRINGS[0].to_string()];
-                while count < num_glyphs {
-                    let sid = buffer.get::<u16>() as usize;
-                    let num_left = buffer.get::<$t>() as usize;
-                    (0..=num_left).for_each(|i| result.push(from_sid(sid + i, &strings)));
-                    count += num_left as usize + 1;
-                }
-                result
-            }};
-        }
         if !self.is_cid_font() {
+            // The predefined charsets omit ".notdef" (GID 0).
+            let predefined_charset = |charset: &[&str]| {
+                std::iter::once(".notdef")
+                    .chain(charset.iter().copied())
+                    .take(num_glyphs)
+                    .map(String::from)
+                    .collect()
+            };
             self.charset = match self._charset_offset {
-                0 => CFF_ISO_ADOBE_CHARSET
-                    .iter()
-                    .map(|&i| i.to_string())
-                    .collect(),
-                1 => CFF_EXPERT_CHARSET.iter().map(|&i| i.to_string()).collect(),
-                2 => CFF_EXPERT_SUBSET_CHARSET
-                    .iter()
-                    .map(|&i| i.to_string())
-                    .collect(),
+                0 => predefined_charset(CFF_ISO_ADOBE_CHARSET),
+                1 => predefined_charset(CFF_EXPERT_CHARSET),
+                2 => predefined_charset(CFF_EXPERT_SUBSET_CHARSET),
                 offset => {
                     buffer.set_offset_from(cff_start, offset);
-                    let format: u8 = buffer.get();
-                    match format {
-                        0 => (0..num_glyphs)
-                            .map(|_| from_sid(buffer.get::<u16>() as usize, strings))
-                            .collect(),
-                        1 => _get_charsets!(u8),
-                        2 => _get_charsets!(u16),
-                        _ => unreachable!(),
-                    }
+                    read_charset(buffer, num_glyphs)?
+                        .into_iter()
+                        .map(|sid| from_sid(sid as usize, strings))
+                        .collect()
                 }
             };
             // Private dict
             if self._private_size != 0 {
                 buffer.set_offset_from(cff_start, self._private_offset);
-                self.private = Some(Private::read(buffer, self._private_size));
+                self.private = Some(Private::read(buffer, self._private_size, None));
             }
         } else {
-            // FD Array
-            buffer.set_offset_from(cff_start, self._fd_array_offset.unwrap());
+            // In CID fonts, the charset maps glyph IDs to CIDs rather than SIDs, and the glyph
+            // names are made from the ROS and the CIDs.
+            buffer.set_offset_from(cff_start, self._charset_offset);
+            self.cids = read_charset(buffer, num_glyphs)?;
+            let prefix = self
+                .ros
+                .as_ref()
+                .map_or_else(String::new, Ros::glyph_name_prefix);
+            self.charset = self
+                .cids
+                .iter()
+                .map(|cid| format!("{}-{}", prefix, cid))
+                .collect();
+            // FD Array and FD Select, which are required in CID fonts
+            let missing_offset = || FontError::InvalidOffset {
+                table: Tag::new(b"CFF "),
+                offset: 0,
+                hex_dump: None,
+            };
+            let fd_array_offset = self._fd_array_offset.ok_or_else(missing_offset)?;
+            buffer.set_offset_from(cff_start, fd_array_offset);
             buffer
                 .get::<Index>()
                 .data
@@ -464,12 +526,14 @@ impl CffFont {
                 .for_each(|font_dict| self.init_fd_array(font_dict, strings));
             self.fd_array.iter_mut().for_each(|fd| {
                 buffer.set_offset_from(cff_start, fd._private_offset);
-                fd.private = Private::read(buffer, fd._private_size);
+                fd.private = Private::read(buffer, fd._private_size, None);
             });
             // FD Select
-            buffer.set_offset_from(cff_start, self._fd_select_offset.unwrap());
+            let fd_select_offset = self._fd_select_offset.ok_or_else(missing_offset)?;
+            buffer.set_offset_from(cff_start, fd_select_offset);
             self.fd_select = Some(FDSelect::read(buffer, num_glyphs));
         }
+        Ok(())
     }
 
     fn parse_top_dict(&mut self, top_dict: &[u8], strings: &[String]) {
@@ -514,7 +578,7 @@ impl CffFont {
                            self._private_size = private.0;
                            self._private_offset = private.1;
                         },
-                        {}, {}, {},
+                        {}, {}, {}, {}, {}, {},
         ]);
         self.init_cid();
     }
@@ -534,7 +598,7 @@ impl CffFont {
                            _private_size = private.0;
                            _private_offset = private.1;
                         },
-                        {}, {}, {},
+                        {}, {}, {}, {}, {}, {},
         ]);
         self.fd_array.push(FDArray {
             font_name,
@@ -560,22 +624,207 @@ impl CffFont {
         }
     }
 
-    fn parse_char_strings(&mut self, global_subrs: &mut Vec<CharString>) {
-        let mut subrs = &mut Vec::new();
-        if let Some(private) = self.private.as_mut() {
-            subrs = &mut private.subrs;
-        };
-        self.char_strings
+    fn parse_char_strings(&mut self, global_subrs: &[CharString]) -> Result<(), FontError> {
+        let mut char_strings = std::mem::take(&mut self.char_strings);
+        let result = char_strings
             .iter_mut()
-            .for_each(|i| i.parse(global_subrs, subrs));
+            .enumerate()
+            .try_for_each(|(glyph_id, char_string)| {
+                let subrs = self
+                    .private_for_glyph(glyph_id)
+                    .map_or(&[][..], |private| &private.subrs);
+                char_string
+                    .parse(global_subrs, subrs)
+                    .map_err(|err| err.context(&format!("charstring of glyph {}", glyph_id)))
+            });
+        self.char_strings = char_strings;
+        result
+    }
+
+    /// Return the name of the font in the Name INDEX.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn full_name(&self) -> &str {
+        &self.full_name
     }
 
-    const fn is_cid_font(&self) -> bool {
+    pub fn family_name(&self) -> &str {
+        &self.family_name
+    }
+
+    pub fn weight(&self) -> &str {
+        &self.weight
+    }
+
+    pub const fn is_fixed_pitch(&self) -> bool {
+        self.is_fixed_pitch
+    }
+
+    /// Return the italic angle in degrees counterclockwise from the vertical.
+    pub fn italic_angle(&self) -> f64 {
+        self.italic_angle.real()
+    }
+
+    /// Return the font bounding box as `(x_min, y_min, x_max, y_max)`.
+    pub fn font_bbox(&self) -> (f64, f64, f64, f64) {
+        match self.font_bbox.as_slice() {
+            [x_min, y_min, x_max, y_max] => {
+                (x_min.real(), y_min.real(), x_max.real(), y_max.real())
+            }
+            _ => (0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Return `true` if it is a CID-keyed font, i.e. the Top DICT begins with `ROS`.
+    pub const fn is_cid_font(&self) -> bool {
         self.ros.is_some()
     }
+
+    /// Return the number of CIDs of a CID-keyed font (8720 if not specified).
+    pub fn cid_count(&self) -> Option<u32> {
+        self.cid_count.map(|n| n as u32)
+    }
+
+    /// Return the number of Font DICTs in `FDArray`, which is 0 for non-CID fonts.
+    pub fn fd_count(&self) -> usize {
+        self.fd_array.len()
+    }
+
+    /// Return the CID of the glyph, or `None` if it is not a CID-keyed font or `glyph_id`
+    /// is out of range.
+    pub fn cid_for_glyph(&self, glyph_id: usize) -> Option<u16> {
+        self.cids.get(glyph_id).copied()
+    }
+
+    /// Return the `(Registry, Ordering, Supplement)` of a CID-keyed font, or `None` if it is
+    /// not a CID-keyed font.
+    pub fn ros(&self) -> Option<(&str, &str, i32)> {
+        self.ros
+            .as_ref()
+            .map(|ros| (ros.registry.as_str(), ros.ordering.as_str(), ros.supplement))
+    }
+
+    /// Alias of [`CffFont::ros`].
+    pub fn cid_font_ros(&self) -> Option<(&str, &str, i32)> {
+        self.ros()
+    }
+
+    /// Return the index of the Font DICT in `FDArray` used by the glyph, or `None` if it is
+    /// not a CID-keyed font.
+    pub fn fd_index(&self, glyph_id: usize) -> Option<usize> {
+        self.fd_select.as_ref()?.fd_index(glyph_id)
+    }
+
+    /// Return the mapping from character codes to glyph IDs defined by the encoding.
+    /// For the predefined encodings, codes are mapped to glyphs by the names in the charset,
+    /// and codes whose glyphs are missing in the font are omitted. CID-keyed fonts have no
+    /// encoding, so the result is empty.
+    pub fn encoding_map(&self) -> HashMap<u8, u16> {
+        match &self.encoding {
+            Encoding::Standard => self.predefined_encoding_map(CFF_STANDARD_ENCODING),
+            Encoding::Expert => self.predefined_encoding_map(CFF_EXPERT_ENCODING),
+            // Glyphs are encoded in order, starting from GID 1 (GID 0 is ".notdef").
+            Encoding::Custom { code, range, .. } => match (code, range) {
+                (Some(code), _) => code.iter().copied().zip(1..).collect(),
+                (_, Some(range)) => range
+                    .iter()
+                    .flat_map(|r| (0..=r.num_left).map(move |i| r.first.wrapping_add(i)))
+                    .zip(1..)
+                    .collect(),
+                _ => HashMap::new(),
+            },
+        }
+    }
+
+    fn predefined_encoding_map(&self, encoding: &[u16]) -> HashMap<u8, u16> {
+        encoding
+            .iter()
+            .enumerate()
+            .filter(|(_, &sid)| sid != 0)
+            .filter_map(|(code, &sid)| {
+                let name = CFF_STANDARD_STRINGS[sid as usize];
+                let glyph_id = self.charset.iter().position(|n| n == name)?;
+                Some((code as u8, glyph_id as u16))
+            })
+            .collect()
+    }
+
+    /// Return the interpreted charstring of `glyph_id`.
+    pub fn char_string(&self, glyph_id: usize) -> Option<&CharString> {
+        self.char_strings.get(glyph_id)
+    }
+
+    /// Return the length of the charstring of `glyph_id` in bytes, or 0 if there is no such
+    /// glyph.
+    pub fn char_string_len(&self, glyph_id: usize) -> usize {
+        self.char_strings.get(glyph_id).map_or(0, CharString::len)
+    }
+
+    /// Return the name of `glyph_id` in the charset. In CID-keyed fonts, the name is made
+    /// from the ROS and the CID of the glyph, e.g. `aj1-6304` for CID 6304 of Adobe-Japan1.
+    pub fn glyph_name(&self, glyph_id: usize) -> Option<&str> {
+        self.charset.get(glyph_id).map(String::as_str)
+    }
+
+    /// Return the number of local subroutines in the top-level Private DICT. For CID-keyed
+    /// fonts, which have a Private DICT for each Font DICT, use
+    /// [`CffFont::private_for_glyph`] instead.
+    pub fn local_subrs_count(&self) -> usize {
+        self.private.as_ref().map_or(0, Private::subrs_count)
+    }
+
+    /// Return the Private DICT used by the glyph. For CID-keyed fonts, it is the one in the
+    /// Font DICT selected by `FDSelect`; otherwise it is the top-level Private DICT.
+    pub fn private_for_glyph(&self, glyph_id: usize) -> Option<&Private> {
+        if self.is_cid_font() {
+            let fd_index = self.fd_index(glyph_id)?;
+            self.fd_array.get(fd_index).map(|fd| &fd.private)
+        } else {
+            self.private.as_ref()
+        }
+    }
+}
+
+/// Read the glyph names (SIDs) or CIDs of the glyphs, where ".notdef" (GID 0) is
+/// omitted in the font but included in the result.
+fn read_charset(buffer: &mut Buffer, num_glyphs: usize) -> Result<Vec<u16>, FontError> {
+    macro_rules! _get_ranges {
+        ($t:ty) => {{
+            let mut result = vec![0];
+            while result.len() < num_glyphs {
+                let first = buffer.get::<u16>();
+                let num_left = buffer.get::<$t>() as u16;
+                let last = first
+                    .checked_add(num_left)
+                    .ok_or_else(|| FontError::Malformed {
+                        table: Tag::new(b"CFF "),
+                        msg: format!("charset range {}+{} exceeds 65535", first, num_left),
+                    })?;
+                result.extend(first..=last);
+            }
+            result
+        }};
+    }
+    let format: u8 = buffer.get();
+    Ok(match format {
+        0 => {
+            let mut result = vec![0];
+            result.extend(buffer.get_vec::<u16, _>(num_glyphs.saturating_sub(1)));
+            result
+        }
+        1 => _get_ranges!(u8),
+        2 => _get_ranges!(u16),
+        _ => {
+            buffer.set_unknown_format(format as u16);
+            Vec::new()
+        }
+    })
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum Encoding {
     Standard,
     Expert,
@@ -612,7 +861,7 @@ impl ReadBuffer for Encoding {
                 num_ranges = Some(buffer.get());
                 range = Some(buffer.get_vec(num_ranges.unwrap()));
             }
-            _ => unreachable!(),
+            _ => buffer.set_unknown_format(format as u16),
         }
         Self::Custom {
             format,
@@ -625,13 +874,15 @@ impl ReadBuffer for Encoding {
 }
 
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct EncodingRange {
     first: u8,
     num_left: u8,
 }
 
 #[derive(Debug, Default)]
-struct Private {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Private {
     _size: usize,
     _offset: usize,
     blue_values: Option<Delta>,
@@ -653,9 +904,25 @@ struct Private {
     subrs: Vec<CharString>,
     default_width_x: Number,
     nominal_width_x: Number,
+    vsindex: usize,
 }
 
 impl Private {
+    /// Return the number of local subroutines.
+    pub fn subrs_count(&self) -> usize {
+        self.subrs.len()
+    }
+
+    /// Return the local subroutines.
+    pub fn subrs(&self) -> &[CharString] {
+        &self.subrs
+    }
+
+    /// Return the index of the item variation data used by `blend` (CFF2 only).
+    pub fn vsindex(&self) -> usize {
+        self.vsindex
+    }
+
     fn new() -> Self {
         Self {
             blue_scale: Number::Real((0.039625).to_string()),
@@ -671,9 +938,16 @@ impl Private {
         }
     }
 
-    fn read(buffer: &mut Buffer, private_size: usize) -> Self {
+    /// Read a Private DICT of `private_size` bytes. `region_counts` is `None` for CFF, and for
+    /// CFF2 it is the number of regions of each item variation data, which is used by the
+    /// `blend` operator.
+    pub(super) fn read(
+        buffer: &mut Buffer,
+        private_size: usize,
+        region_counts: Option<&[usize]>,
+    ) -> Self {
         let start = buffer.offset();
-        let private_dict: Vec<u8> = buffer.get_vec(private_size);
+        let private_dict = buffer.get_bytes(private_size);
         let mut private = Self::new();
         // We use an empty vector as a placeholder of strings to make the macro work.
         _parse_dict!(private_dict; Vec::new(); [
@@ -698,21 +972,28 @@ impl Private {
             /* 19    */ private._subrs_offset = Some(_integer!() as usize),
             /* 20    */ private.default_width_x = _num!(),
             /* 21    */ private.nominal_width_x = _num!(),
+            /* 22    */ private.vsindex = _integer!() as usize,
+            /* 23    */ {
+                           let region_count = region_counts
+                               .and_then(|counts| counts.get(private.vsindex).copied());
+                           _blend!(region_count.unwrap_or(0))
+                        },
+                        {},
         ]);
         if let Some(subrs_offset) = private._subrs_offset {
             buffer.set_offset_from(start, subrs_offset);
-            private.subrs = buffer
-                .get::<Index>()
-                .data
-                .into_iter()
-                .map(CharString::from)
-                .collect();
+            let subrs_index = match region_counts {
+                Some(_) => Index::read_cff2(buffer),
+                None => buffer.get(),
+            };
+            private.subrs = subrs_index.data.into_iter().map(CharString::from).collect();
         }
         private
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Ros {
     registry: String,
     ordering: String,
@@ -727,9 +1008,22 @@ impl Ros {
             supplement,
         }
     }
+
+    /// Return the prefix of the glyph names of CID-keyed fonts: the initials of the registry
+    /// and the ordering, followed by the digits of the ordering, in lowercase (e.g. `aj1` for
+    /// Adobe-Japan1 and `ai` for Adobe-Identity).
+    fn glyph_name_prefix(&self) -> String {
+        let initial = |s: &str| s.chars().next().map(|c| c.to_ascii_lowercase());
+        initial(&self.registry)
+            .into_iter()
+            .chain(initial(&self.ordering))
+            .chain(self.ordering.chars().filter(char::is_ascii_digit))
+            .collect()
+    }
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct FDArray {
     font_name: String,
     _private_size: usize,
@@ -738,18 +1032,19 @@ struct FDArray {
 }
 
 #[derive(Debug, Default)]
-struct FDSelect {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct FDSelect {
     format: u8,
     // Format 0
     fd_selector_array: Vec<u8>,
-    // Format 3
-    num_ranges: Option<u16>,
+    // Format 3 and 4 (CFF2 only)
+    num_ranges: Option<u32>,
     range: Vec<FDSelectRange>,
-    sentinel: Option<u16>,
+    sentinel: Option<u32>,
 }
 
 impl FDSelect {
-    fn read(buffer: &mut Buffer, num_glyphs: usize) -> Self {
+    pub(super) fn read(buffer: &mut Buffer, num_glyphs: usize) -> Self {
         let format = buffer.get();
         let mut fd_select = Self {
             format,
@@ -760,34 +1055,77 @@ impl FDSelect {
                 fd_select.fd_selector_array = buffer.get_vec(num_glyphs);
             }
             3 => {
-                fd_select.num_ranges = Some(buffer.get());
-                fd_select.range = buffer.get_vec(fd_select.num_ranges.unwrap());
+                let num_ranges: u16 = buffer.get();
+                fd_select.num_ranges = Some(num_ranges as u32);
+                fd_select.range = (0..num_ranges)
+                    .map(|_| FDSelectRange {
+                        first: buffer.get::<u16>() as u32,
+                        fd: buffer.get::<u8>() as u16,
+                    })
+                    .collect();
+                fd_select.sentinel = Some(buffer.get::<u16>() as u32);
+            }
+            4 => {
+                let num_ranges: u32 = buffer.get();
+                fd_select.num_ranges = Some(num_ranges);
+                fd_select.range = buffer.get_vec(num_ranges);
                 fd_select.sentinel = Some(buffer.get());
             }
-            _ => unreachable!(),
+            format => buffer.set_unknown_format(format as u16),
         }
         fd_select
     }
 }
 
+impl FDSelect {
+    pub(super) fn fd_index(&self, glyph_id: usize) -> Option<usize> {
+        match self.format {
+            0 => self.fd_selector_array.get(glyph_id).map(|&fd| fd as usize),
+            3 | 4 => {
+                if glyph_id >= self.sentinel? as usize {
+                    return None;
+                }
+                self.range
+                    .iter()
+                    .rev()
+                    .find(|range| range.first as usize <= glyph_id)
+                    .map(|range| range.fd as usize)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Range of `FDSelect`, which is stored as `(u16, u8)` in format 3 and `(u32, u16)` in
+/// format 4.
 #[derive(Debug, Default, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct FDSelectRange {
-    first: u16,
-    fd: u8,
+    first: u32,
+    fd: u16,
 }
 
 #[derive(Clone)]
-enum Number {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) enum Number {
     Int(i32),
     Real(String),
 }
 
 impl Number {
-    fn int(self) -> i32 {
-        if let Self::Int(n) = self {
-            n
-        } else {
-            panic!()
+    /// Return the integer value. A real number (which is not expected) is truncated.
+    pub(super) fn int(self) -> i32 {
+        match self {
+            Self::Int(n) => n,
+            real => real.real() as i32,
+        }
+    }
+
+    /// Return the real value. A malformed real number is treated as 0.
+    pub(super) fn real(&self) -> f64 {
+        match self {
+            Self::Int(n) => f64::from(*n),
+            Self::Real(n) => n.parse().unwrap_or(0.0),
         }
     }
 }
@@ -808,6 +1146,7 @@ impl Default for Number {
 }
 
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Delta(Vec<Number>);
 
 impl Delta {
@@ -831,25 +1170,31 @@ impl fmt::Debug for Delta {
 
 /// An array of variable-sized objects.
 #[derive(Debug, Default)]
-struct Index {
-    count: usize, // Actual type is `u16`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct Index {
+    count: usize, // Actual type is `u16` (`u32` in CFF2)
     offset_size: u8,
     offset: Vec<usize>, // Actual type is `Offset[]`
-    data: Vec<Vec<u8>>,
+    pub(super) data: Vec<Vec<u8>>,
 }
 
 impl Index {
-    fn to_string_vec(&self) -> Vec<String> {
+    fn to_string_vec(&self) -> Result<Vec<String>, str::Utf8Error> {
         self.data
             .iter()
-            .map(|i| String::from_utf8(i.to_vec()).unwrap())
+            .map(|i| str::from_utf8(i).map(String::from))
             .collect()
     }
 }
 
-impl ReadBuffer for Index {
-    fn read(buffer: &mut Buffer) -> Self {
-        let count = buffer.get::<u16>() as usize;
+impl Index {
+    /// Read a CFF2 INDEX, whose count is a `u32`.
+    pub(super) fn read_cff2(buffer: &mut Buffer) -> Self {
+        let count = buffer.get::<u32>() as usize;
+        Self::read_with_count(buffer, count)
+    }
+
+    fn read_with_count(buffer: &mut Buffer, count: usize) -> Self {
         match count {
             0 => Default::default(),
             _ => {
@@ -875,10 +1220,13 @@ impl ReadBuffer for Index {
                     2 => _get_offset!(u16),
                     3 => _get_offset!(u24),
                     4 => _get_offset!(u32),
-                    _ => unreachable!(),
+                    _ => {
+                        buffer.set_unknown_format(offset_size as u16);
+                        vec![1; count + 1]
+                    }
                 };
                 let data = (0..count)
-                    .map(|i| buffer.get_vec(offset[i + 1] - offset[i]))
+                    .map(|i| buffer.get_bytes(offset[i + 1].saturating_sub(offset[i])))
                     .collect();
                 Self {
                     count,
@@ -891,6 +1239,13 @@ impl ReadBuffer for Index {
     }
 }
 
+impl ReadBuffer for Index {
+    fn read(buffer: &mut Buffer) -> Self {
+        let count = buffer.get::<u16>() as usize;
+        Self::read_with_count(buffer, count)
+    }
+}
+
 fn from_sid(sid: usize, strings: &[String]) -> String {
     let len = CFF_STANDARD_STRINGS.len();
     if sid < len {
@@ -899,3 +1254,42 @@ fn from_sid(sid: usize, strings: &[String]) -> String {
         strings[sid - len].to_string()
     }
 }
+
+#[test]
+fn test_read_charset() {
+    // Format 1: GIDs 1-3 are CIDs 100-102, and GID 4 is CID 200
+    let mut buffer = Buffer::new(vec![1, 0, 100, 2, 0, 200, 0]);
+    assert_eq!(
+        read_charset(&mut buffer, 5).unwrap(),
+        vec![0, 100, 101, 102, 200]
+    );
+    // Format 2, where the range overflows u16
+    let mut buffer = Buffer::new(vec![2, 0xFF, 0xFE, 0, 2]);
+    assert!(matches!(
+        read_charset(&mut buffer, 4),
+        Err(FontError::Malformed { .. })
+    ));
+}
+
+#[test]
+fn test_cid_glyph_name_prefix() {
+    let ros = |registry: &str, ordering: &str| Ros {
+        registry: registry.to_string(),
+        ordering: ordering.to_string(),
+        supplement: 0,
+    };
+    assert_eq!(ros("Adobe", "Japan1").glyph_name_prefix(), "aj1");
+    assert_eq!(ros("Adobe", "Identity").glyph_name_prefix(), "ai");
+    assert_eq!(ros("", "").glyph_name_prefix(), "");
+}
+
+#[test]
+fn test_index_to_string_vec() {
+    // count = 2, offSize = 1, offsets = [1, 4, 6], data = "abc", [0xFF, 0x41]
+    let mut buffer = Buffer::new(vec![0, 2, 1, 1, 4, 6, b'a', b'b', b'c', 0xFF, 0x41]);
+    let index: Index = buffer.get();
+    assert!(index.to_string_vec().is_err());
+    let mut buffer = Buffer::new(vec![0, 2, 1, 1, 4, 6, b'a', b'b', b'c', b'd', b'e']);
+    let index: Index = buffer.get();
+    assert_eq!(index.to_string_vec().unwrap(), ["abc", "de"]);
+}
@@ -1,429 +1,529 @@
+use crate::error::FontError;
+use crate::tables::cff::cff_data::cff_subr_bias;
+use crate::types::Tag;
+use std::convert::TryFrom;
+
+/// A Type 2 charstring. After [`CharString::parse`], the subroutine calls and arithmetic
+/// operators are resolved, leaving the width and a flat list of hinting and drawing
+/// commands.
 #[derive(Debug)]
-pub struct CharString(Vec<u8>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharString {
+    data: Vec<u8>,
+    width: Option<f64>,
+    commands: Vec<CharStringCommand>,
+}
 
 impl CharString {
     pub fn from(data: Vec<u8>) -> Self {
-        Self(data)
+        Self {
+            data,
+            width: None,
+            commands: Vec::new(),
+        }
     }
 
-    #[allow(unused_variables)]
-    pub fn parse(&mut self, global_subrs: &mut Vec<CharString>, subrs: &mut Vec<CharString>) {}
-}
+    /// Return the length of the charstring in bytes.
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
 
-/*
-#[derive(Default)]
-struct Subrs {
-    data: Vec<CharString>,
-}
+    /// Interpret the charstring, resolving the subroutine calls with `global_subrs` and the
+    /// local `subrs`. Interpretation stops at an invalid operator, an invalid subroutine index
+    /// or a too deep subroutine nesting, which are returned as errors.
+    pub fn parse(
+        &mut self,
+        global_subrs: &[CharString],
+        subrs: &[CharString],
+    ) -> Result<(), FontError> {
+        let mut interpreter = Interpreter::new(global_subrs, subrs);
+        interpreter.run(&self.data, 0)?;
+        self.width = interpreter.width;
+        self.commands = interpreter.commands;
+        Ok(())
+    }
 
-impl fmt::Debug for Subrs {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.data)
+    /// Return the hinting and drawing commands of the charstring.
+    pub fn commands(&self) -> &[CharStringCommand] {
+        &self.commands
     }
-}
 
-impl Subrs {
-    fn from(data: Vec<CharString>) -> Self {
-        Self { data }
+    /// Return the width argument, i.e. the difference between the advance width and
+    /// `nominalWidthX`. `None` means the width is `defaultWidthX`.
+    pub fn width(&self) -> Option<f64> {
+        self.width
     }
+}
+
+/// A hinting or drawing operator with its arguments.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharStringCommand {
+    pub operator: CharStringOperator,
+    /// For `hintmask` and `cntrmask`, these are the implicit `vstem` hints.
+    pub args: Vec<f64>,
+    /// Mask bytes of `hintmask` and `cntrmask`, which are empty for other operators.
+    pub mask: Vec<u8>,
+}
 
-    fn get_mut(&mut self, index: i32) -> &CharString {
-        // TODO: we assume CharstringType == 2
-        let bias = if self.data.len() < 1240 {
-            107
-        } else if self.data.len() < 33900 {
-            1131
-        } else {
-            32768
-        };
-        &self.data[(index + bias) as usize]
+impl CharStringCommand {
+    fn new(operator: CharStringOperator, args: Vec<f64>) -> Self {
+        Self {
+            operator,
+            args,
+            mask: Vec::new(),
+        }
     }
 }
 
-// #[derive(Debug)]
-struct CharString {
-    data: Vec<u8>,
-    commands: Vec<CharStringCommand>,
+/// Type 2 charstring operators that are kept after interpretation. Subroutine and
+/// arithmetic operators are executed instead.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CharStringOperator {
+    Hstem,
+    Vstem,
+    Vmoveto,
+    Rlineto,
+    Hlineto,
+    Vlineto,
+    Rrcurveto,
+    /// With 4 arguments (`adx ady bchar achar`), it is the deprecated `seac` form.
+    Endchar,
+    Hstemhm,
+    Hintmask,
+    Cntrmask,
+    Rmoveto,
+    Hmoveto,
+    Vstemhm,
+    Rcurveline,
+    Rlinecurve,
+    Vvcurveto,
+    Hhcurveto,
+    Vhcurveto,
+    Hvcurveto,
+    Hflex,
+    Flex,
+    Hflex1,
+    Flex1,
 }
 
-impl fmt::Debug for CharString {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:#?}", self.commands)
+fn malformed(msg: &str) -> FontError {
+    FontError::Malformed {
+        table: Tag::new(b"CFF "),
+        msg: msg.to_string(),
     }
 }
 
-impl CharString {
-    fn new(data: Vec<u8>) -> Self {
+/// Maximum nesting depth of subroutine calls.
+const MAX_SUBR_NESTING: usize = 10;
+const TRANSIENT_ARRAY_SIZE: usize = 32;
+
+struct Interpreter<'a> {
+    global_subrs: &'a [CharString],
+    subrs: &'a [CharString],
+    stack: Vec<f64>,
+    transient_array: [f64; TRANSIENT_ARRAY_SIZE],
+    /// Number of stem hints, which determines the length of the masks. It is shared by
+    /// the charstring and all the subroutines it calls.
+    hint_count: usize,
+    width: Option<f64>,
+    width_parsed: bool,
+    random_seed: u32,
+    commands: Vec<CharStringCommand>,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(global_subrs: &'a [CharString], subrs: &'a [CharString]) -> Self {
         Self {
-            data,
+            global_subrs,
+            subrs,
+            stack: Vec::new(),
+            transient_array: [0.0; TRANSIENT_ARRAY_SIZE],
+            hint_count: 0,
+            width: None,
+            width_parsed: false,
+            random_seed: 1,
             commands: Vec::new(),
         }
     }
 
-    fn parse(&mut self, global_subrs: &mut Subrs, local_subrs: &mut Subrs) {
+    /// Run `data` until its end, `return` or `endchar`. Return `true` if `endchar` is
+    /// reached, which also terminates all the callers.
+    fn run(&mut self, data: &[u8], depth: usize) -> Result<bool, FontError> {
+        use CharStringOperator::*;
 
-        println!("{0}{0}{0}{0}{0}{0}{0}{0}", "==========");
-
-        // let mut seq = Vec::new();
         let mut i = 0;
-
-        let mut hint_num = 0;
-
-        let mut number_stack: Vec<CharStringValue> = Vec::new();
-        let mut commands: Vec<CharStringCommand> = Vec::new();
-
-        macro_rules! _push_str {
-            ($s:literal) => {
-                eprintln!($s);
-                // self.commands
-                //     .push(CharStringValue::Operator($s.to_string()))
-            };
-        }
-
-        macro_rules! _set_width {
-            () => {
-                commands.push(CharStringCommand::new(vec![number_stack[0]], CharStringOperator::op_width))
-            };
-        }
-
-        // TODO: width and hintmask bytes are not considered
-        while i < self.data.len() {
-            let b0 = self.data[i];
+        while i < data.len() {
+            let b0 = data[i];
+            i += 1;
             match b0 {
                 // Numbers
                 28 => {
-                    let b1 = self.data[i + 1] as i16;
-                    let b2 = self.data[i + 2] as i16;
+                    let bytes = match data.get(i..i + 2) {
+                        Some(bytes) => [bytes[0], bytes[1]],
+                        None => break,
+                    };
                     i += 2;
-                    number_stack.push(CharStringValue::Int((b1 << 8 | b2) as i32));
-                }
-                32..=246 => {
-                    let b0 = b0 as i32;
-                    number_stack.push(CharStringValue::Int(b0 - 139));
+                    self.stack.push(i16::from_be_bytes(bytes) as f64);
                 }
-                247..=250 => {
-                    let b0 = b0 as i32;
-                    let b1 = self.data[i + 1] as i32;
-                    i += 1;
-                    number_stack.push(CharStringValue::Int((b0 - 247) * 256 + b1 + 108));
-                }
-                251..=254 => {
-                    let b0 = b0 as i32;
-                    let b1 = self.data[i + 1] as i32;
+                32..=246 => self.stack.push(b0 as f64 - 139.0),
+                247..=254 => {
+                    let b1 = match data.get(i) {
+                        Some(&b1) => b1 as f64,
+                        None => break,
+                    };
                     i += 1;
-                    number_stack.push(CharStringValue::Int(-(b0 - 251) * 256 - b1 - 108));
+                    self.stack.push(if b0 <= 250 {
+                        (b0 as f64 - 247.0) * 256.0 + b1 + 108.0
+                    } else {
+                        -(b0 as f64 - 251.0) * 256.0 - b1 - 108.0
+                    });
                 }
                 255 => {
-                    let b1 = self.data[i + 1] as i16;
-                    let b2 = self.data[i + 2] as i16;
-                    let b3 = self.data[i + 3] as u16;
-                    let b4 = self.data[i + 4] as u16;
+                    let bytes = match data.get(i..i + 4) {
+                        Some(bytes) => [bytes[0], bytes[1], bytes[2], bytes[3]],
+                        None => break,
+                    };
                     i += 4;
-                    number_stack.push(CharStringValue::Fixed(b1 << 8 | b2, b3 << 8 | b4));
+                    self.stack.push(i32::from_be_bytes(bytes) as f64 / 65536.0);
                 }
 
-                // Operators
+                // Hinting operators
+                1 | 3 | 18 | 23 => {
+                    self.parse_width(self.stack.len() % 2 == 1);
+                    self.hint_count += self.stack.len() / 2;
+                    let operator = match b0 {
+                        1 => Hstem,
+                        3 => Vstem,
+                        18 => Hstemhm,
+                        _ => Vstemhm,
+                    };
+                    self.push_command(operator);
+                }
+                19 | 20 => {
+                    // The arguments, if any, are implicit `vstem` hints.
+                    self.parse_width(self.stack.len() % 2 == 1);
+                    self.hint_count += self.stack.len() / 2;
+                    let mask_len = self.hint_count.div_ceil(8);
+                    let mask = data.get(i..i + mask_len).unwrap_or(&data[i..]).to_vec();
+                    i += mask.len();
+                    self.commands.push(CharStringCommand {
+                        operator: if b0 == 19 { Hintmask } else { Cntrmask },
+                        args: self.stack.split_off(0),
+                        mask,
+                    });
+                }
 
+                // Path construction operators
                 21 => {
-                    if number_stack.len() == 3 {
-                        _set_width!();
-                        number_stack = number_stack.split_off(1);
-                    }
-                    let cmd = CharStringCommand::new(number_stack.clone(), CharStringOperator::op_rmoveto);
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
-                    number_stack.clear();
+                    self.parse_width(self.stack.len() > 2);
+                    self.push_command(Rmoveto);
                 }
-                22 => {
-                    if number_stack.len() == 2 {
-                        _set_width!();
-                        number_stack = number_stack.split_off(1);
-                    }
-                    let n = number_stack.pop().unwrap();
-                    let cmd = CharStringCommand::new(vec![n], CharStringOperator::op_hmoveto);
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
+                4 | 22 => {
+                    self.parse_width(self.stack.len() > 1);
+                    self.push_command(if b0 == 4 { Vmoveto } else { Hmoveto });
                 }
-                4 => {
-                    if number_stack.len() == 2 {
-                        _set_width!();
-                        number_stack = number_stack.split_off(1);
-                    }
-                    let n = number_stack.pop().unwrap();
-                    let cmd = CharStringCommand::new(vec![n], CharStringOperator::op_vmoveto);
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
+                5 => self.push_command(Rlineto),
+                6 => self.push_command(Hlineto),
+                7 => self.push_command(Vlineto),
+                8 => self.push_command(Rrcurveto),
+                24 => self.push_command(Rcurveline),
+                25 => self.push_command(Rlinecurve),
+                26 => self.push_command(Vvcurveto),
+                27 => self.push_command(Hhcurveto),
+                30 => self.push_command(Vhcurveto),
+                31 => self.push_command(Hvcurveto),
+                14 => {
+                    self.parse_width(self.stack.len() == 1 || self.stack.len() == 5);
+                    self.push_command(Endchar);
+                    return Ok(true);
                 }
 
-                1 | 3 | 18 | 23 => {
-                    if number_stack.len() % 2 == 1 {
-                        _set_width!();
-                        number_stack = number_stack.split_off(1);
+                // Subroutine operators
+                10 | 29 => {
+                    let subrs = if b0 == 10 {
+                        self.subrs
+                    } else {
+                        self.global_subrs
+                    };
+                    let index = self.pop() as i32 + cff_subr_bias(subrs.len());
+                    if depth >= MAX_SUBR_NESTING {
+                        return Err(malformed("subroutine nesting exceeds the limit"));
                     }
-                    hint_num += number_stack.len() / 2;
-                    let cmd = CharStringCommand::new(
-                        number_stack.clone(),
-                        match b0 {
-                            1 => CharStringOperator::op_hstem,
-                            3 => CharStringOperator::op_vstem,
-                            18 => CharStringOperator::op_hstemhm,
-                            23 => CharStringOperator::op_vstemhm,
-                            _ => unreachable!(),
+                    match usize::try_from(index).ok().and_then(|i| subrs.get(i)) {
+                        Some(subr) => {
+                            if self.run(&subr.data, depth + 1)? {
+                                return Ok(true);
+                            }
+                        }
+                        None => {
+                            return Err(malformed(&format!("invalid subroutine index {}", index)))
                         }
-                    );
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
-                    number_stack.clear();
-                }
-
-                14 => {
-                    if !number_stack.is_empty() {
-                        _set_width!();
-                        number_stack.clear();
                     }
-                    commands.push(CharStringCommand::new(vec![], CharStringOperator::op_endchar));
                 }
+                11 => return Ok(false),
 
-                19 => {
-                    let hint_bytes = (hint_num + number_stack.len() + 7) / 8;
-                    let cmd = CharStringCommand {
-                        args: number_stack.clone(),
-                        operator: CharStringOperator::op_hintmask,
-                        mask: (0..hint_bytes).map(|j| self.data[i + j + 1]).collect(),
-                    };
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
-                    number_stack.clear();
-                    i += hint_bytes;
-                    hint_num = 0;
-                }
-                20 => {
-                    let hint_bytes = (hint_num + number_stack.len() + 7) / 8;
-                    let cmd = CharStringCommand {
-                        args: number_stack.clone(),
-                        operator: CharStringOperator::op_cntrmask,
-                        mask: (0..hint_bytes).map(|j| self.data[i + j + 1]).collect(),
+                12 => {
+                    let b1 = match data.get(i) {
+                        Some(&b1) => b1,
+                        None => break,
                     };
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
-                    number_stack.clear();
-                    i += hint_bytes;
-                    hint_num = 0;
-                }
-
-
-                5 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_rlineto));
-                    number_stack.clear();
-                }
-                6 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_hlineto));
-                    number_stack.clear();
-                }
-                7 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_vlineto));
-                    number_stack.clear();
-                }
-                8 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_rrcurveto));
-                    number_stack.clear();
-                }
-                27 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_hhcurveto));
-                    number_stack.clear();
-                }
-                31 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_hvcurveto));
-                    number_stack.clear();
-                }
-                24 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_rcurveline));
-                    number_stack.clear();
-                }
-                25 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_rlinecurve));
-                    number_stack.clear();
-                }
-                30 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_vhcurveto));
-                    number_stack.clear();
-                }
-                26 => {
-                    commands.push(CharStringCommand::new(number_stack.clone(), CharStringOperator::op_vvcurveto));
-                    number_stack.clear();
+                    i += 1;
+                    self.run_escape(b1)?;
                 }
+                _ => return Err(malformed(&format!("invalid charstring operator {}", b0))),
+            }
+        }
+        Ok(false)
+    }
 
-                // 10 => _push_str!("callsubr"),
-                10 => {
-                    let cmd = CharStringCommand::new(number_stack.clone(), CharStringOperator::op_callsubr);
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
-                    let index = match number_stack.pop().unwrap() {
-                        CharStringValue::Int(n) => n,
-                        _ => unreachable!(),
-                    };
-                    let x = local_subrs.get_mut(index);
-                    println!("LOCAL_SUBRS: {:?}", x.data);
-                    // x.parse(global_subrs, local_subrs);
+    /// Run the two-byte operator `12 b1`.
+    fn run_escape(&mut self, b1: u8) -> Result<(), FontError> {
+        use CharStringOperator::*;
+
+        let bool_to_f64 = |b: bool| if b { 1.0 } else { 0.0 };
+        match b1 {
+            // dotsection (deprecated)
+            0 => self.stack.clear(),
+            // Arithmetic operators
+            3 => {
+                let (a, b) = self.pop2();
+                self.stack.push(bool_to_f64(a != 0.0 && b != 0.0));
+            }
+            4 => {
+                let (a, b) = self.pop2();
+                self.stack.push(bool_to_f64(a != 0.0 || b != 0.0));
+            }
+            5 => {
+                let a = self.pop();
+                self.stack.push(bool_to_f64(a == 0.0));
+            }
+            9 => {
+                let a = self.pop();
+                self.stack.push(a.abs());
+            }
+            10 => {
+                let (a, b) = self.pop2();
+                self.stack.push(a + b);
+            }
+            11 => {
+                let (a, b) = self.pop2();
+                self.stack.push(a - b);
+            }
+            12 => {
+                let (a, b) = self.pop2();
+                self.stack.push(if b == 0.0 { 0.0 } else { a / b });
+            }
+            14 => {
+                let a = self.pop();
+                self.stack.push(-a);
+            }
+            15 => {
+                let (a, b) = self.pop2();
+                self.stack.push(bool_to_f64(a == b));
+            }
+            18 => {
+                self.pop();
+            }
+            20 => {
+                let (value, index) = self.pop2();
+                if let Some(n) = self.transient_array.get_mut(index as usize) {
+                    *n = value;
                 }
-                // 29 => _push_str!("callgsubr"),
-                29 => {
-                    let cmd = CharStringCommand::new(number_stack.clone(), CharStringOperator::op_callgsubr);
-                    println!("{:?}", cmd);
-                    commands.push(cmd);
-                    let index = match number_stack.pop().unwrap() {
-                        CharStringValue::Int(n) => n,
-                        _ => unreachable!(),
-                    };
-                    let x = global_subrs.get_mut(index);
-                    println!("GLOBAL_SUBRS: {:?}", x.data);
+            }
+            21 => {
+                let index = self.pop();
+                let value = self.transient_array.get(index as usize).copied();
+                self.stack.push(value.unwrap_or(0.0));
+            }
+            22 => {
+                let (v1, v2) = self.pop2();
+                let (s1, s2) = self.pop2();
+                self.stack.push(if v1 <= v2 { s1 } else { s2 });
+            }
+            23 => {
+                let n = self.random();
+                self.stack.push(n);
+            }
+            24 => {
+                let (a, b) = self.pop2();
+                self.stack.push(a * b);
+            }
+            26 => {
+                let a = self.pop();
+                self.stack.push(a.abs().sqrt());
+            }
+            27 => {
+                let a = self.pop();
+                self.stack.extend([a, a]);
+            }
+            28 => {
+                let (a, b) = self.pop2();
+                self.stack.extend([b, a]);
+            }
+            29 => {
+                let index = self.pop().max(0.0) as usize;
+                let len = self.stack.len();
+                let value = len.checked_sub(index + 1).map(|i| self.stack[i]);
+                self.stack.push(value.unwrap_or(0.0));
+            }
+            30 => {
+                let (n, j) = self.pop2();
+                let n = (n.max(0.0) as usize).min(self.stack.len());
+                let start = self.stack.len() - n;
+                if n > 0 {
+                    let j = (j as i64).rem_euclid(n as i64) as usize;
+                    self.stack[start..].rotate_right(j);
                 }
+            }
+            // Flex operators
+            34 => self.push_command(Hflex),
+            35 => self.push_command(Flex),
+            36 => self.push_command(Hflex1),
+            37 => self.push_command(Flex1),
+            _ => return Err(malformed(&format!("invalid charstring operator 12 {}", b1))),
+        }
+        Ok(())
+    }
 
-
-                11 => _push_str!("return"),
-                12 => {
-                    // let b1 = self.data[i + 1];
-                    // let op_str = match b1 {
-                    //     3 => "and",
-                    //     4 => "or",
-                    //     5 => "not",
-                    //     9 => "abs",
-                    //     10 => "add",
-                    //     11 => "sub",
-                    //     12 => "div",
-                    //     14 => "neg",
-                    //     15 => "eq",
-                    //     18 => "drop",
-                    //     20 => "put",
-                    //     21 => "get",
-                    //     22 => "ifelse",
-                    //     23 => "random",
-                    //     24 => "mul",
-                    //     26 => "sqrt",
-                    //     27 => "dup",
-                    //     28 => "exch",
-                    //     29 => "index",
-                    //     30 => "roll",
-                    //     34 => "hflex",
-                    //     35 => "flex",
-                    //     36 => "hflex1",
-                    //     37 => "flex1",
-                    //     _ => "[TODO] hint_mask_bytes",
-                    // };
-                    i += 1;
-                    // self.commands
-                    //     .push(CharStringValue::Operator(op_str.to_string()));
-                }
-                _ => _push_str!("[TODO] hint_mask_bytes"),
+    /// The first stack-clearing operator may take an extra argument, the width, at the
+    /// bottom of the stack.
+    fn parse_width(&mut self, has_width: bool) {
+        if !self.width_parsed {
+            self.width_parsed = true;
+            if has_width && !self.stack.is_empty() {
+                self.width = Some(self.stack.remove(0));
             }
-            i += 1;
         }
+    }
 
-        self.commands = commands;
+    /// Push a command with all the arguments on the stack, and clear the stack.
+    fn push_command(&mut self, operator: CharStringOperator) {
+        let args = self.stack.split_off(0);
+        self.commands.push(CharStringCommand::new(operator, args));
     }
-}
 
-struct CharStringCommand {
-    args: Vec<CharStringValue>,
-    operator: CharStringOperator,
-    mask: Vec<u8>,
-}
+    fn pop(&mut self) -> f64 {
+        self.stack.pop().unwrap_or(0.0)
+    }
 
-impl fmt::Debug for CharStringCommand {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.mask.is_empty() {
-            write!(f, "{:?} \"{:?}\"", self.args, self.operator)
-        } else {
-            let hintmask_str = self.mask
-                .iter()
-                .map(|i| format!("{:08b}", i))
-                .collect::<Vec<String>>()
-                .join("_");
-            write!(f, "{:?} \"{:?}\" {}", self.args, self.operator, hintmask_str)
-        }
+    /// Pop two values `(a, b)`, where `b` is the top of the stack.
+    fn pop2(&mut self) -> (f64, f64) {
+        let b = self.pop();
+        let a = self.pop();
+        (a, b)
     }
-}
 
-impl CharStringCommand {
-    fn new(args: Vec<CharStringValue>, operator: CharStringOperator) -> Self {
-        Self {
-            args,
-            operator,
-            mask: Vec::new()
-        }
+    /// Return a pseudo-random number in (0, 1]. The sequence is deterministic, so that
+    /// parsing a font always gives the same result.
+    fn random(&mut self) -> f64 {
+        // xorshift32
+        let mut x = self.random_seed;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.random_seed = x;
+        (x % 65536 + 1) as f64 / 65536.0
     }
 }
 
-// FIXME:
-#[derive(Clone, Copy)]
-enum CharStringValue {
-    Int(i32),
-    Fixed(i16, u16),
+#[test]
+fn test_char_string_subrs() {
+    use CharStringOperator::*;
+
+    // 0 callsubr: "100 200 rlineto return"
+    let subrs = vec![CharString::from(vec![239, 247, 92, 5, 11])];
+    // 0 callgsubr: "hintmask 0b11000000 return", which needs the hint count of the caller
+    let global_subrs = vec![CharString::from(vec![19, 0xC0, 11])];
+
+    // "50 10 20 30 40 hstem -107 callgsubr 10 20 rmoveto -107 callsubr endchar"
+    let mut char_string = CharString::from(vec![
+        189, 149, 159, 169, 179, 1, 32, 29, 149, 159, 21, 32, 10, 14,
+    ]);
+    char_string.parse(&global_subrs, &subrs).unwrap();
+    assert_eq!(char_string.width(), Some(50.0));
+    assert_eq!(
+        char_string.commands(),
+        &[
+            CharStringCommand::new(Hstem, vec![10.0, 20.0, 30.0, 40.0]),
+            CharStringCommand {
+                operator: Hintmask,
+                args: vec![],
+                mask: vec![0xC0],
+            },
+            CharStringCommand::new(Rmoveto, vec![10.0, 20.0]),
+            CharStringCommand::new(Rlineto, vec![100.0, 200.0]),
+            CharStringCommand::new(Endchar, vec![]),
+        ]
+    );
+
+    // The same outline without subroutines and without width
+    let mut flattened = CharString::from(vec![
+        149, 159, 169, 179, 1, 19, 0xC0, 149, 159, 21, 239, 247, 92, 5, 14,
+    ]);
+    flattened.parse(&[], &[]).unwrap();
+    assert_eq!(flattened.width(), None);
+    assert_eq!(flattened.commands(), char_string.commands());
 }
 
-impl fmt::Debug for CharStringValue {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::Int(n) => write!(f, "{}", n),
-            Self::Fixed(i, u) => write!(f, "{}", *i as f64 + *u as f64 / 65536.0),
-        }
-    }
+#[test]
+fn test_char_string_arithmetic() {
+    use CharStringOperator::*;
+
+    // "3 4 mul 1 put 1 get 10 add 2 2 div 0 hlineto"
+    // "1 2 3 3 1 roll 12 -5 exch abs vlineto"
+    let mut char_string = CharString::from(vec![
+        142, 143, 12, 24, 140, 12, 20, 140, 12, 21, 149, 12, 10, 141, 141, 12, 12, 139, 6, //
+        140, 141, 142, 142, 140, 12, 30, 151, 134, 12, 28, 12, 9, 7,
+    ]);
+    char_string.parse(&[], &[]).unwrap();
+    assert_eq!(
+        char_string.commands(),
+        &[
+            CharStringCommand::new(Hlineto, vec![22.0, 1.0, 0.0]),
+            CharStringCommand::new(Vlineto, vec![3.0, 1.0, 2.0, -5.0, 12.0]),
+        ]
+    );
 }
 
-#[allow(non_camel_case_types, dead_code)]
-#[derive(Debug)]
-enum CharStringOperator {
-    // One-byte operators
-    op_hstem, // = 0x01,
-    op_vstem, // = 0x03,
-    op_vmoveto, // = 0x04,
-    op_rlineto, // = 0x05,
-    op_hlineto, // = 0x06,
-    op_vlineto, // = 0x07,
-    op_rrcurveto, // = 0x08,
-    op_callsubr, // = 0x0a,
-    op_return, // = 0x0b,
-    // escape = 0x0c
-    op_endchar, // = 0x0d,
-    op_hstemhm, // = 0x12,
-    op_hintmask, // = 0x13,
-    op_cntrmask, // = 0x14,
-    op_rmoveto, // = 0x15,
-    op_hmoveto, // = 0x16,
-    op_vstemhm, // = 0x17,
-    op_rcurveline, // = 0x18,
-    op_rlinecurve, // = 0x19,
-    op_vvcurveto, // = 0x1a,
-    op_hhcurveto, // = 0x1b,
-    op_callgsubr, // = 0x1d,
-    op_vhcurveto, // = 0x1e,
-    op_hvcurveto, // = 0x1f,
-    // Two-byte operators
-    op_and, // = 0x0c_03,
-    op_or, // = 0x0c_04,
-    op_not, // = 0x0c_05,
-    op_abs, // = 0x0c_09,
-    op_add, // = 0x0c_0a,
-    op_sub, // = 0x0c_0b,
-    op_div, // = 0x0c_0c,
-    op_neg, // = 0x0c_0e,
-    op_eq, // = 0x0c_0f,
-    op_drop, // = 0x0c_12,
-    op_put, // = 0x0c_14,
-    op_get, // = 0x0c_15,
-    op_ifelse, // = 0x01_6c,
-    op_random, // = 0x0c_17,
-    op_mul, // = 0x0c_18,
-    op_sqrt, // = 0x0c_1a,
-    op_dup, // = 0x0c_1b,
-    op_exch, // = 0x0c_1c,
-    op_index, // = 0x0c_1d,
-    op_roll, // = 0x0c_1e,
-    op_hflex, // = 0x0c_22,
-    op_flex, // = 0x0c_23,
-    op_hflex1, // = 0x0c_24,
-    op_flex1, // = 0x0c_25,
-    //
-    op_width,
+#[test]
+fn test_char_string_ifelse() {
+    use CharStringOperator::*;
+
+    // "10 20 1 2 ifelse hlineto 10 20 2 1 ifelse vlineto"
+    let mut char_string = CharString::from(vec![
+        149, 159, 140, 141, 12, 22, 6, //
+        149, 159, 141, 140, 12, 22, 7,
+    ]);
+    char_string.parse(&[], &[]).unwrap();
+    assert_eq!(
+        char_string.commands(),
+        &[
+            CharStringCommand::new(Hlineto, vec![10.0]),
+            CharStringCommand::new(Vlineto, vec![20.0]),
+        ]
+    );
+}
+
+#[test]
+fn test_char_string_errors() {
+    // "0 0 rmoveto 2 endchar": operator 2 is reserved
+    let mut char_string = CharString::from(vec![139, 139, 21, 2, 14]);
+    assert!(char_string.parse(&[], &[]).is_err());
+    assert!(char_string.commands().is_empty());
+
+    // "12 37": operator 12 37 is `flex1`, but 12 38 is reserved
+    let mut char_string = CharString::from(vec![12, 38]);
+    assert!(char_string.parse(&[], &[]).is_err());
+
+    // "0 callsubr" without local subroutines
+    let mut char_string = CharString::from(vec![139, 10, 14]);
+    assert!(char_string.parse(&[], &[]).is_err());
+
+    // A subroutine that calls itself: "-107 callsubr"
+    let subrs = vec![CharString::from(vec![32, 10])];
+    let mut char_string = CharString::from(vec![32, 10, 14]);
+    let err = char_string.parse(&[], &subrs).unwrap_err();
+    assert!(err.to_string().contains("nesting"));
 }
-*/
@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::{Buffer, ReadBuffer};
 
@@ -21,7 +22,7 @@ pub struct Table_VORG {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_VORG(&mut self, buffer: &mut Buffer) {
+    pub fn parse_VORG(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let version = buffer.get_version::<u16>();
         let default_vert_origin_y = buffer.get();
         let num_vert_origin_y_metrics = buffer.get();
@@ -32,6 +33,7 @@ impl Font {
             num_vert_origin_y_metrics,
             vert_origin_y_metrics,
         });
+        Ok(())
     }
 }
 
@@ -1,5 +1,7 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::tables::bitmap::ebdt::BitmapData;
+use crate::types::Tag;
 use crate::util::Buffer;
 
 /// ## `CBDT` &mdash; Color Bitmap Data Table
@@ -20,10 +22,14 @@ pub struct Table_CBDT {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_CBDT(&mut self, buffer: &mut Buffer) {
+    pub fn parse_CBDT(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let cbdt_start = buffer.offset();
         let version = buffer.get_version::<u16>();
-        let strikes = &self.CBLC.as_ref().unwrap().strikes;
+        let strikes = &self
+            .CBLC
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"CBLC")))?
+            .strikes;
         let bitmap_data = strikes
             .iter()
             .map(|strike| {
@@ -32,7 +38,15 @@ impl Font {
                     buffer.set_offset_from(cbdt_start, index_sub_table.image_data_offset);
                     match index_sub_table.image_format {
                         17 => {
-                            let len = index_sub_table.sbit_offsets.as_ref().unwrap().len() - 1;
+                            let len = match &index_sub_table.sbit_offsets {
+                                Some(sbit_offsets) => sbit_offsets.len() - 1,
+                                None => {
+                                    return Err(FontError::UnknownTableFormat {
+                                        tag: Tag::new(b"CBDT"),
+                                        format: index_sub_table.image_format,
+                                    })
+                                }
+                            };
                             (0..len).for_each(|_| {
                                 let small_metrics = Some(buffer.get());
                                 let data_len: u32 = buffer.get();
@@ -44,15 +58,21 @@ impl Font {
                                 })
                             })
                         }
-                        _ => unimplemented!(),
+                        format => {
+                            return Err(FontError::UnknownTableFormat {
+                                tag: Tag::new(b"CBDT"),
+                                format,
+                            })
+                        }
                     }
                 }
-                strike_bitmap_data
+                Ok(strike_bitmap_data)
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         self.CBDT = Some(Table_CBDT {
             version,
             bitmap_data,
         });
+        Ok(())
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::tables::bitmap::eblc::Strike;
 use crate::util::Buffer;
@@ -21,7 +22,7 @@ pub struct Table_CBLC {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_CBLC(&mut self, buffer: &mut Buffer) {
+    pub fn parse_CBLC(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let cblc_start = buffer.offset();
         let version = buffer.get_version::<u16>();
         let num_strikes = buffer.get();
@@ -30,6 +31,7 @@ impl Font {
             version,
             num_strikes,
             strikes,
-        })
+        });
+        Ok(())
     }
 }
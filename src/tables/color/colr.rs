@@ -1,4 +1,6 @@
+use crate::error::FontError;
 use crate::font::Font;
+use crate::types::{u24, F2Dot14, Fixed, Tag};
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
 
@@ -13,12 +15,18 @@ use read_buffer_derive::ReadBuffer;
 #[derive(Debug)]
 pub struct Table_COLR {
     version: u16,
-    pub color_glyphs: Vec<ColorGlyph>,
+    pub base_glyph_records: Vec<BaseGlyphRecord>,
+    pub layer_records: Vec<LayerRecord>,
+    // Version 1
+    /// Paint graphs of the base glyphs, sorted by glyph ID.
+    pub base_glyph_paint_records: Vec<BaseGlyphPaintRecord>,
+    /// Paints referred to by [`Paint::ColrLayers`].
+    pub layer_list: Vec<Paint>,
 }
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_COLR(&mut self, buffer: &mut Buffer) {
+    pub fn parse_COLR(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let colr_start = buffer.offset();
         let version = buffer.get();
         let num_base_glyph_records: u16 = buffer.get();
@@ -27,47 +35,673 @@ impl Font {
         let num_layer_records: u16 = buffer.get();
 
         buffer.set_offset_from(colr_start, base_glyph_records_offset);
-        let base_glyph_records: Vec<BaseGlyphRecord> =
-            buffer.get_vec(num_base_glyph_records);
+        let mut base_glyph_records: Vec<BaseGlyphRecord> = buffer.get_vec(num_base_glyph_records);
+        // The records should already be sorted, but binary search relies on it.
+        base_glyph_records.sort_by_key(|rec| rec.glyph_id);
+
+        // Version 1
+        let mut base_glyph_list_offset: u32 = 0;
+        let mut layer_list_offset: u32 = 0;
+        if version >= 1 {
+            base_glyph_list_offset = buffer.get();
+            layer_list_offset = buffer.get();
+            // TODO: clip list and variations
+            buffer.skip::<u32>(3);
+        }
 
         buffer.set_offset_from(colr_start, layer_records_offset);
-        let layer_records: Vec<Layer> = buffer.get_vec(num_layer_records);
+        let layer_records = buffer.get_vec(num_layer_records);
 
-        let color_glyphs = base_glyph_records
-            .iter()
-            .map(|rec| {
-                let layers = (0..rec.num_layers)
-                    .map(|i| layer_records[(rec.first_layer_index + i) as usize])
-                    .collect();
-                ColorGlyph {
-                    glyph_id: rec.glyph_id,
-                    layers,
-                }
-            })
-            .collect();
+        let mut base_glyph_paint_records = Vec::new();
+        if base_glyph_list_offset != 0 {
+            buffer.set_offset_from(colr_start, base_glyph_list_offset);
+            base_glyph_paint_records = read_base_glyph_list(buffer);
+            base_glyph_paint_records.sort_by_key(|rec| rec.glyph_id);
+        }
+        let mut layer_list = Vec::new();
+        if layer_list_offset != 0 {
+            buffer.set_offset_from(colr_start, layer_list_offset);
+            layer_list = read_paint_list(buffer);
+        }
 
         self.COLR = Some(Table_COLR {
             version,
-            color_glyphs,
+            base_glyph_records,
+            layer_records,
+            base_glyph_paint_records,
+            layer_list,
         });
+        Ok(())
     }
 }
 
-#[derive(Debug)]
-pub struct ColorGlyph {
-    pub glyph_id: u16,
-    pub layers: Vec<Layer>,
+impl Table_COLR {
+    /// Return the number of base glyph records in the version 0 part of the table.
+    pub fn v0_base_glyph_record_count(&self) -> u16 {
+        self.base_glyph_records.len() as u16
+    }
+
+    /// Return the number of color layers of the base glyph `glyph_id`, or 0 if the glyph
+    /// has no color layers.
+    pub fn v0_layer_count(&self, glyph_id: u16) -> u16 {
+        self.base_glyph_record(glyph_id)
+            .map_or(0, |rec| rec.num_layers)
+    }
+
+    /// Return the `(layer_glyph_id, palette_color_index)` pair of the `layer_index`-th layer
+    /// of the base glyph `glyph_id`.
+    pub fn v0_layer(&self, glyph_id: u16, layer_index: u16) -> Option<(u16, u16)> {
+        let rec = self.base_glyph_record(glyph_id)?;
+        if layer_index >= rec.num_layers {
+            return None;
+        }
+        self.layer_records
+            .get(rec.first_layer_index as usize + layer_index as usize)
+            .map(|layer| (layer.glyph_id, layer.palette_index))
+    }
+
+    /// Return the IDs of the glyphs that have a version 1 paint graph.
+    pub fn v1_glyph_ids(&self) -> Vec<u16> {
+        self.base_glyph_paint_records
+            .iter()
+            .map(|rec| rec.glyph_id)
+            .collect()
+    }
+
+    /// Return the root of the version 1 paint graph of the base glyph `glyph_id`.
+    pub fn v1_paint_graph(&self, glyph_id: u16) -> Option<&Paint> {
+        self.base_glyph_paint_records
+            .binary_search_by_key(&glyph_id, |rec| rec.glyph_id)
+            .ok()
+            .map(|i| &self.base_glyph_paint_records[i].paint)
+    }
+
+    /// Return the children of `paint`. Unlike [`Paint::children`], the layers of
+    /// [`Paint::ColrLayers`] and the paint graph referred to by [`Paint::ColrGlyph`] are
+    /// resolved as well.
+    pub fn v1_paint_children<'a>(&'a self, paint: &'a Paint) -> Vec<&'a Paint> {
+        match *paint {
+            Paint::ColrLayers {
+                num_layers,
+                first_layer_index,
+            } => {
+                let first = first_layer_index as usize;
+                let last = (first + num_layers as usize).min(self.layer_list.len());
+                self.layer_list
+                    .get(first..last)
+                    .map_or_else(Vec::new, |layers| layers.iter().collect())
+            }
+            Paint::ColrGlyph { glyph_id } => self.v1_paint_graph(glyph_id).into_iter().collect(),
+            _ => paint.children(),
+        }
+    }
+
+    /// Return all the nodes of the paint graph of `glyph_id` in depth-first pre-order,
+    /// following the layer and glyph references. A node that is already being visited is
+    /// not entered again, so that a malformed graph with cycles is still finite.
+    pub fn v1_paint_nodes(&self, glyph_id: u16) -> Vec<&Paint> {
+        let mut nodes = Vec::new();
+        if let Some(root) = self.v1_paint_graph(glyph_id) {
+            self.collect_paint_nodes(root, &mut Vec::new(), &mut nodes);
+        }
+        nodes
+    }
+
+    fn collect_paint_nodes<'a>(
+        &'a self,
+        paint: &'a Paint,
+        path: &mut Vec<&'a Paint>,
+        nodes: &mut Vec<&'a Paint>,
+    ) {
+        if path.iter().any(|&node| std::ptr::eq(node, paint)) {
+            return;
+        }
+        nodes.push(paint);
+        path.push(paint);
+        for child in self.v1_paint_children(paint) {
+            self.collect_paint_nodes(child, path, nodes);
+        }
+        path.pop();
+    }
+
+    fn base_glyph_record(&self, glyph_id: u16) -> Option<&BaseGlyphRecord> {
+        self.base_glyph_records
+            .binary_search_by_key(&glyph_id, |rec| rec.glyph_id)
+            .ok()
+            .map(|i| &self.base_glyph_records[i])
+    }
 }
 
 #[derive(Debug, ReadBuffer)]
-struct BaseGlyphRecord {
-    glyph_id: u16,
-    first_layer_index: u16,
-    num_layers: u16,
+pub struct BaseGlyphRecord {
+    pub glyph_id: u16,
+    pub first_layer_index: u16,
+    pub num_layers: u16,
 }
 
 #[derive(Debug, ReadBuffer, Clone, Copy)]
-pub struct Layer {
+pub struct LayerRecord {
     pub glyph_id: u16,
     pub palette_index: u16,
 }
+
+#[derive(Debug)]
+pub struct BaseGlyphPaintRecord {
+    pub glyph_id: u16,
+    pub paint: Paint,
+}
+
+fn read_base_glyph_list(buffer: &mut Buffer) -> Vec<BaseGlyphPaintRecord> {
+    let base_glyph_list_start = buffer.offset();
+    let num_records: u32 = buffer.get();
+    let records: Vec<(u16, u32)> = (0..num_records)
+        .map(|_| (buffer.get(), buffer.get()))
+        .collect();
+    records
+        .iter()
+        .map(|&(glyph_id, paint_offset)| {
+            buffer.set_offset_from(base_glyph_list_start, paint_offset);
+            BaseGlyphPaintRecord {
+                glyph_id,
+                paint: buffer.get(),
+            }
+        })
+        .collect()
+}
+
+fn read_paint_list(buffer: &mut Buffer) -> Vec<Paint> {
+    let layer_list_start = buffer.offset();
+    let num_layers: u32 = buffer.get();
+    let paint_offsets: Vec<u32> = buffer.get_vec(num_layers);
+    paint_offsets
+        .iter()
+        .map(|&offset| {
+            buffer.set_offset_from(layer_list_start, offset);
+            buffer.get()
+        })
+        .collect()
+}
+
+/// A node of a version 1 paint graph. The variable formats share the variant of the
+/// corresponding static format, with `var_index_base` set.
+#[derive(Debug)]
+pub enum Paint {
+    /// Format 1.
+    ColrLayers {
+        num_layers: u8,
+        first_layer_index: u32,
+    },
+    /// Formats 2 and 3.
+    Solid {
+        palette_index: u16,
+        alpha: F2Dot14,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 4 and 5.
+    LinearGradient {
+        color_line: ColorLine,
+        x0: i16,
+        y0: i16,
+        x1: i16,
+        y1: i16,
+        x2: i16,
+        y2: i16,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 6 and 7.
+    RadialGradient {
+        color_line: ColorLine,
+        x0: i16,
+        y0: i16,
+        radius0: u16,
+        x1: i16,
+        y1: i16,
+        radius1: u16,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 8 and 9.
+    SweepGradient {
+        color_line: ColorLine,
+        center_x: i16,
+        center_y: i16,
+        start_angle: F2Dot14,
+        end_angle: F2Dot14,
+        var_index_base: Option<u32>,
+    },
+    /// Format 10.
+    Glyph { paint: Box<Paint>, glyph_id: u16 },
+    /// Format 11: the paint graph of another base glyph.
+    ColrGlyph { glyph_id: u16 },
+    /// Formats 12 and 13.
+    Transform {
+        paint: Box<Paint>,
+        transform: Affine2x3,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 14 and 15.
+    Translate {
+        paint: Box<Paint>,
+        dx: i16,
+        dy: i16,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 16 to 19.
+    Scale {
+        paint: Box<Paint>,
+        scale_x: F2Dot14,
+        scale_y: F2Dot14,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 20 to 23.
+    ScaleUniform {
+        paint: Box<Paint>,
+        scale: F2Dot14,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 24 to 27.
+    Rotate {
+        paint: Box<Paint>,
+        angle: F2Dot14,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 28 to 31.
+    Skew {
+        paint: Box<Paint>,
+        x_skew_angle: F2Dot14,
+        y_skew_angle: F2Dot14,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Format 32.
+    Composite {
+        source_paint: Box<Paint>,
+        composite_mode: u8,
+        backdrop_paint: Box<Paint>,
+    },
+}
+
+impl Paint {
+    /// Return the format number of the paint table.
+    pub fn format(&self) -> u8 {
+        // Offsets of the "around center" and variable formats from the base format
+        let var = |var_index_base: &Option<u32>| var_index_base.is_some() as u8;
+        let center = |center: &Option<(i16, i16)>| 2 * center.is_some() as u8;
+        match self {
+            Self::ColrLayers { .. } => 1,
+            Self::Solid { var_index_base, .. } => 2 + var(var_index_base),
+            Self::LinearGradient { var_index_base, .. } => 4 + var(var_index_base),
+            Self::RadialGradient { var_index_base, .. } => 6 + var(var_index_base),
+            Self::SweepGradient { var_index_base, .. } => 8 + var(var_index_base),
+            Self::Glyph { .. } => 10,
+            Self::ColrGlyph { .. } => 11,
+            Self::Transform { var_index_base, .. } => 12 + var(var_index_base),
+            Self::Translate { var_index_base, .. } => 14 + var(var_index_base),
+            Self::Scale {
+                center: c,
+                var_index_base,
+                ..
+            } => 16 + center(c) + var(var_index_base),
+            Self::ScaleUniform {
+                center: c,
+                var_index_base,
+                ..
+            } => 20 + center(c) + var(var_index_base),
+            Self::Rotate {
+                center: c,
+                var_index_base,
+                ..
+            } => 24 + center(c) + var(var_index_base),
+            Self::Skew {
+                center: c,
+                var_index_base,
+                ..
+            } => 28 + center(c) + var(var_index_base),
+            Self::Composite { .. } => 32,
+        }
+    }
+
+    /// Return the child paints that are stored in this paint table. The references of
+    /// [`Paint::ColrLayers`] and [`Paint::ColrGlyph`] are not resolved, see
+    /// [`Table_COLR::v1_paint_children`].
+    pub fn children(&self) -> Vec<&Paint> {
+        match self {
+            Self::Glyph { paint, .. }
+            | Self::Transform { paint, .. }
+            | Self::Translate { paint, .. }
+            | Self::Scale { paint, .. }
+            | Self::ScaleUniform { paint, .. }
+            | Self::Rotate { paint, .. }
+            | Self::Skew { paint, .. } => vec![paint],
+            Self::Composite {
+                source_paint,
+                backdrop_paint,
+                ..
+            } => vec![source_paint, backdrop_paint],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl ReadBuffer for Paint {
+    fn read(buffer: &mut Buffer) -> Self {
+        let mut paint_budget = MAX_PAINT_COUNT;
+        read_paint(buffer, 0, &mut paint_budget)
+    }
+}
+
+/// Maximum nesting level of a paint graph. Deeper graphs, including the cyclic ones, are
+/// rejected.
+const MAX_PAINT_DEPTH: usize = 64;
+
+/// Maximum number of paints read from a root paint. Sub-graphs shared by several offsets are
+/// read each time they are referred to, so that a small table can expand exponentially.
+const MAX_PAINT_COUNT: usize = 0x10000;
+
+/// Read the paint at `depth` in the graph, and its children. `paint_budget` is the number of
+/// paints that can still be read from the root paint.
+fn read_paint(buffer: &mut Buffer, depth: usize, paint_budget: &mut usize) -> Paint {
+    let malformed = |msg: &str| FontError::Malformed {
+        table: Tag::new(b"COLR"),
+        msg: msg.to_string(),
+    };
+    if depth > MAX_PAINT_DEPTH {
+        buffer.set_error(malformed("the paint graph is nested too deeply or cyclic"));
+        return Paint::empty();
+    }
+    if *paint_budget == 0 {
+        buffer.set_error(malformed("the paint graph has too many paints"));
+        return Paint::empty();
+    }
+    *paint_budget -= 1;
+    Paint::read_format(buffer, depth, paint_budget)
+}
+
+impl Paint {
+    /// An empty paint, for the paints that cannot be read.
+    fn empty() -> Self {
+        Self::ColrLayers {
+            num_layers: 0,
+            first_layer_index: 0,
+        }
+    }
+
+    fn read_format(buffer: &mut Buffer, depth: usize, paint_budget: &mut usize) -> Self {
+        let start = buffer.offset();
+        let format: u8 = buffer.get();
+        // Child paints and color lines are referred to by 24-bit offsets from the start
+        let mut get_paint = |buffer: &mut Buffer, offset: u24| {
+            buffer.set_offset_from(start, offset);
+            Box::new(read_paint(buffer, depth + 1, paint_budget))
+        };
+        let get_color_line = |buffer: &mut Buffer, offset: u24| {
+            buffer.set_offset_from(start, offset);
+            read_color_line(buffer, format % 2 == 1)
+        };
+        let get_var_index_base = |buffer: &mut Buffer| match format % 2 {
+            1 => Some(buffer.get()),
+            _ => None,
+        };
+        match format {
+            1 => Self::ColrLayers {
+                num_layers: buffer.get(),
+                first_layer_index: buffer.get(),
+            },
+            2 | 3 => Self::Solid {
+                palette_index: buffer.get(),
+                alpha: buffer.get(),
+                var_index_base: get_var_index_base(buffer),
+            },
+            4 | 5 => {
+                let color_line_offset = buffer.get();
+                let (x0, y0, x1, y1, x2, y2) = (
+                    buffer.get(),
+                    buffer.get(),
+                    buffer.get(),
+                    buffer.get(),
+                    buffer.get(),
+                    buffer.get(),
+                );
+                let var_index_base = get_var_index_base(buffer);
+                Self::LinearGradient {
+                    color_line: get_color_line(buffer, color_line_offset),
+                    x0,
+                    y0,
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    var_index_base,
+                }
+            }
+            6 | 7 => {
+                let color_line_offset = buffer.get();
+                let (x0, y0, radius0) = (buffer.get(), buffer.get(), buffer.get());
+                let (x1, y1, radius1) = (buffer.get(), buffer.get(), buffer.get());
+                let var_index_base = get_var_index_base(buffer);
+                Self::RadialGradient {
+                    color_line: get_color_line(buffer, color_line_offset),
+                    x0,
+                    y0,
+                    radius0,
+                    x1,
+                    y1,
+                    radius1,
+                    var_index_base,
+                }
+            }
+            8 | 9 => {
+                let color_line_offset = buffer.get();
+                let (center_x, center_y) = (buffer.get(), buffer.get());
+                let (start_angle, end_angle) = (buffer.get(), buffer.get());
+                let var_index_base = get_var_index_base(buffer);
+                Self::SweepGradient {
+                    color_line: get_color_line(buffer, color_line_offset),
+                    center_x,
+                    center_y,
+                    start_angle,
+                    end_angle,
+                    var_index_base,
+                }
+            }
+            10 => {
+                let paint_offset = buffer.get();
+                let glyph_id = buffer.get();
+                Self::Glyph {
+                    paint: get_paint(buffer, paint_offset),
+                    glyph_id,
+                }
+            }
+            11 => Self::ColrGlyph {
+                glyph_id: buffer.get(),
+            },
+            12 | 13 => {
+                let paint_offset = buffer.get();
+                let transform_offset: u24 = buffer.get();
+                buffer.set_offset_from(start, transform_offset);
+                let transform = buffer.get();
+                // The variation index is stored in the `VarAffine2x3` table
+                let var_index_base = get_var_index_base(buffer);
+                Self::Transform {
+                    paint: get_paint(buffer, paint_offset),
+                    transform,
+                    var_index_base,
+                }
+            }
+            14 | 15 => {
+                let paint_offset = buffer.get();
+                let (dx, dy) = (buffer.get(), buffer.get());
+                let var_index_base = get_var_index_base(buffer);
+                Self::Translate {
+                    paint: get_paint(buffer, paint_offset),
+                    dx,
+                    dy,
+                    var_index_base,
+                }
+            }
+            16..=31 => {
+                // Scale, uniform scale, rotate and skew, each with 4 formats:
+                // static, variable, around center and variable around center.
+                let kind = (format - 16) / 4;
+                let paint_offset = buffer.get();
+                let values: Vec<F2Dot14> =
+                    buffer.get_vec(if kind == 0 || kind == 3 { 2 } else { 1 });
+                let center = match (format / 2) % 2 {
+                    1 => Some((buffer.get(), buffer.get())),
+                    _ => None,
+                };
+                let var_index_base = get_var_index_base(buffer);
+                let paint = get_paint(buffer, paint_offset);
+                match kind {
+                    0 => Self::Scale {
+                        paint,
+                        scale_x: values[0],
+                        scale_y: values[1],
+                        center,
+                        var_index_base,
+                    },
+                    1 => Self::ScaleUniform {
+                        paint,
+                        scale: values[0],
+                        center,
+                        var_index_base,
+                    },
+                    2 => Self::Rotate {
+                        paint,
+                        angle: values[0],
+                        center,
+                        var_index_base,
+                    },
+                    _ => Self::Skew {
+                        paint,
+                        x_skew_angle: values[0],
+                        y_skew_angle: values[1],
+                        center,
+                        var_index_base,
+                    },
+                }
+            }
+            32 => {
+                let source_paint_offset = buffer.get();
+                let composite_mode = buffer.get();
+                let backdrop_paint_offset = buffer.get();
+                Self::Composite {
+                    source_paint: get_paint(buffer, source_paint_offset),
+                    composite_mode,
+                    backdrop_paint: get_paint(buffer, backdrop_paint_offset),
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format as u16);
+                Self::empty()
+            }
+        }
+    }
+}
+
+/// Color line of a gradient paint.
+#[derive(Debug)]
+pub struct ColorLine {
+    /// Extend mode: 0 for pad, 1 for repeat and 2 for reflect.
+    pub extend: u8,
+    pub color_stops: Vec<ColorStop>,
+}
+
+#[derive(Debug)]
+pub struct ColorStop {
+    pub stop_offset: F2Dot14,
+    pub palette_index: u16,
+    pub alpha: F2Dot14,
+    /// Only present in variable color lines.
+    pub var_index_base: Option<u32>,
+}
+
+fn read_color_line(buffer: &mut Buffer, is_var: bool) -> ColorLine {
+    let extend = buffer.get();
+    let num_stops: u16 = buffer.get();
+    let color_stops = (0..num_stops)
+        .map(|_| ColorStop {
+            stop_offset: buffer.get(),
+            palette_index: buffer.get(),
+            alpha: buffer.get(),
+            var_index_base: if is_var { Some(buffer.get()) } else { None },
+        })
+        .collect();
+    ColorLine {
+        extend,
+        color_stops,
+    }
+}
+
+/// Affine transformation matrix.
+#[derive(Debug, ReadBuffer)]
+pub struct Affine2x3 {
+    pub xx: Fixed,
+    pub yx: Fixed,
+    pub xy: Fixed,
+    pub yy: Fixed,
+    pub dx: Fixed,
+    pub dy: Fixed,
+}
+
+#[test]
+fn test_paint_glyph() {
+    // PaintGlyph (glyph 5) with a PaintSolid child at offset 6
+    let mut buffer = Buffer::new(vec![10, 0, 0, 6, 0, 5, 2, 0, 1, 0x40, 0x00]);
+    let paint: Paint = buffer.get();
+    assert_eq!(paint.format(), 10);
+    let children = paint.children();
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].format(), 2);
+    assert!(children[0].children().is_empty());
+}
+
+#[test]
+fn test_paint_graph_cycle() {
+    // Glyphs 1 and 2 refer to each other
+    let colr_glyph = |glyph_id, referred_glyph_id| BaseGlyphPaintRecord {
+        glyph_id,
+        paint: Paint::ColrGlyph {
+            glyph_id: referred_glyph_id,
+        },
+    };
+    let colr = Table_COLR {
+        version: 1,
+        base_glyph_records: Vec::new(),
+        layer_records: Vec::new(),
+        base_glyph_paint_records: vec![colr_glyph(1, 2), colr_glyph(2, 1)],
+        layer_list: Vec::new(),
+    };
+    assert_eq!(colr.v1_glyph_ids(), vec![1, 2]);
+    assert_eq!(colr.v1_paint_nodes(1).len(), 2);
+    assert!(colr.v1_paint_graph(3).is_none());
+}
+
+#[test]
+fn test_paint_self_reference() {
+    // PaintGlyph whose child is itself
+    let mut buffer = Buffer::new(vec![10, 0, 0, 0, 0, 5]);
+    let paint: Paint = buffer.get();
+    assert_eq!(paint.format(), 10);
+    assert!(matches!(
+        buffer.take_error(),
+        Some(FontError::Malformed { .. })
+    ));
+}
+
+#[test]
+fn test_paint_shared_children() {
+    // 20 nested PaintComposite, each with both children at the next one, which would be
+    // expanded to 2^20 paints
+    let mut bytes = [32, 0, 0, 8, 0, 0, 0, 8].repeat(20);
+    bytes.extend(&[2, 0, 1, 0x40, 0x00]);
+    let mut buffer = Buffer::new(bytes);
+    let paint: Paint = buffer.get();
+    assert_eq!(paint.format(), 32);
+    assert!(matches!(
+        buffer.take_error(),
+        Some(FontError::Malformed { .. })
+    ));
+}
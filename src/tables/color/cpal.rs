@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
@@ -24,7 +25,7 @@ pub struct Table_CPAL {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_CPAL(&mut self, buffer: &mut Buffer) {
+    pub fn parse_CPAL(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let cpal_start = buffer.offset();
         let version = buffer.get();
         let num_palette_entries = buffer.get();
@@ -62,19 +63,35 @@ impl Font {
             .collect();
 
         if version == 1 {
-            buffer.set_offset_from(cpal_start, palette_types_array_offset);
-            let palette_types = buffer.get_vec(num_palettes);
-            buffer.set_offset_from(cpal_start, palette_labels_array_offset);
-            let palette_labels = buffer.get_vec(num_palettes);
-            buffer.set_offset_from(cpal_start, palette_entry_labels_array_offset);
-            let palette_entry_labels = buffer.get_vec(num_palettes);
-
-            (0..num_palettes).for_each(|i| {
-                let i = i as usize;
-                palettes[i].r#type = Some(palette_types[i]);
-                palettes[i].label = Some(palette_labels[i]);
-                palettes[i].entry_label = Some(palette_entry_labels[i]);
-            });
+            // Offsets are NULL if the arrays are not provided.
+            if palette_types_array_offset != 0 {
+                buffer.set_offset_from(cpal_start, palette_types_array_offset);
+                let palette_types: Vec<u32> = buffer.get_vec(num_palettes);
+                palettes
+                    .iter_mut()
+                    .zip(palette_types)
+                    .for_each(|(palette, palette_type)| palette.r#type = palette_type);
+            }
+            if palette_labels_array_offset != 0 {
+                buffer.set_offset_from(cpal_start, palette_labels_array_offset);
+                let palette_labels: Vec<u16> = buffer.get_vec(num_palettes);
+                palettes
+                    .iter_mut()
+                    .zip(palette_labels)
+                    .for_each(|(palette, label)| palette.label = name_id_or_none(label));
+            }
+            // Entry labels are shared by all the palettes.
+            if palette_entry_labels_array_offset != 0 {
+                buffer.set_offset_from(cpal_start, palette_entry_labels_array_offset);
+                let entry_labels: Vec<Option<u16>> = buffer
+                    .get_vec::<u16, _>(num_palette_entries)
+                    .into_iter()
+                    .map(name_id_or_none)
+                    .collect();
+                palettes
+                    .iter_mut()
+                    .for_each(|palette| palette.entry_labels = entry_labels.clone());
+            }
         }
 
         self.CPAL = Some(Table_CPAL {
@@ -83,16 +100,56 @@ impl Font {
             num_palettes,
             num_color_records,
             palettes,
-        })
+        });
+        Ok(())
     }
 }
 
 #[derive(Debug, Default)]
 pub struct Palette {
     color_records: Vec<ColorRecord>,
-    r#type: Option<u32>,
+    r#type: u32,
     label: Option<u16>,
-    entry_label: Option<u16>,
+    entry_labels: Vec<Option<u16>>,
+}
+
+impl Palette {
+    const USABLE_WITH_LIGHT_BACKGROUND: u32 = 0x0001;
+    const USABLE_WITH_DARK_BACKGROUND: u32 = 0x0002;
+
+    /// Check whether the palette is appropriate to use when displaying the font on a
+    /// light background such as white.
+    pub fn is_light_palette(&self) -> bool {
+        self.r#type & Self::USABLE_WITH_LIGHT_BACKGROUND != 0
+    }
+
+    /// Check whether the palette is appropriate to use when displaying the font on a
+    /// dark background such as black.
+    pub fn is_dark_palette(&self) -> bool {
+        self.r#type & Self::USABLE_WITH_DARK_BACKGROUND != 0
+    }
+
+    /// Return the `name` table ID of the palette label. The string can be looked up with
+    /// [`Font::name_record`].
+    pub fn label_name_id(&self) -> Option<u16> {
+        self.label
+    }
+
+    /// Return the `name` table ID of the label of the entry at `entry_index`.
+    pub fn entry_label_name_id(&self, entry_index: u16) -> Option<u16> {
+        self.entry_labels
+            .get(entry_index as usize)
+            .copied()
+            .flatten()
+    }
+}
+
+/// `0xFFFF` is used when no name ID is provided.
+fn name_id_or_none(name_id: u16) -> Option<u16> {
+    match name_id {
+        0xFFFF => None,
+        name_id => Some(name_id),
+    }
 }
 
 /// Each color record has BGRA values. The color space for these values is sRGB.
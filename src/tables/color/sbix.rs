@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::Buffer;
@@ -17,9 +18,13 @@ pub struct Table_sbix {
 
 impl Font {
     #[allow(unused_variables)]
-    pub fn parse_sbix(&mut self, buffer: &mut Buffer) {
+    pub fn parse_sbix(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let sbix_start = buffer.offset();
-        let num_glyphs = self.maxp.as_ref().unwrap().num_glyphs as usize;
+        let num_glyphs = self
+            .maxp
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"maxp")))?
+            .num_glyphs as usize;
         let version: u16 = buffer.get();
         let flags: u16 = buffer.get();
         let num_strikes: u32 = buffer.get();
@@ -32,6 +37,7 @@ impl Font {
             })
             .collect();
         self.sbix = Some(Table_sbix { strikes });
+        Ok(())
     }
 }
 
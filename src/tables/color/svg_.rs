@@ -1,5 +1,7 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::Buffer;
+use std::ops::RangeInclusive;
 
 /// ## `SVG` &mdash; The SVG (Scalable Vector Graphics) Table
 ///
@@ -17,7 +19,7 @@ pub struct Table_SVG_ {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_SVG_(&mut self, buffer: &mut Buffer) {
+    pub fn parse_SVG_(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let svg_start = buffer.offset();
         let version = buffer.get();
         let svg_doc_list_offset: u32 = buffer.get();
@@ -26,12 +28,100 @@ impl Font {
         let num_entries = buffer.get();
         let doc_records = (0..num_entries)
             .map(|_| SvgDocRecord::read(buffer, svg_doc_start))
-            .collect();
+            .collect::<Result<_, _>>()?;
         self.SVG_ = Some(Table_SVG_ {
             version,
             num_entries,
             doc_records,
-        })
+        });
+        Ok(())
+    }
+
+    /// Add or replace the SVG document of `glyph_id`. An empty `SVG` table is created if the
+    /// font doesn't have one.
+    pub fn embed_svg_glyph(&mut self, glyph_id: u16, svg: &str) {
+        self.SVG_
+            .get_or_insert_with(|| Table_SVG_ {
+                version: 0,
+                num_entries: 0,
+                doc_records: Vec::new(),
+            })
+            .set_glyph(glyph_id, glyph_id, svg);
+    }
+}
+
+impl Table_SVG_ {
+    /// Return the number of SVG document records.
+    pub fn document_count(&self) -> usize {
+        self.doc_records.len()
+    }
+
+    /// Return the glyph ID ranges covered by the SVG documents.
+    pub fn glyph_ids(&self) -> Vec<RangeInclusive<u16>> {
+        self.doc_records
+            .iter()
+            .map(|rec| rec.start_glyph_id..=rec.end_glyph_id)
+            .collect()
+    }
+
+    /// Use `svg` as the SVG document of glyphs from `start_glyph_id` to `end_glyph_id`.
+    /// Existing records overlapping the range are trimmed (or split) so that the records
+    /// remain sorted and non-overlapping. An inverted range is swapped.
+    pub fn set_glyph(&mut self, start_glyph_id: u16, end_glyph_id: u16, svg: &str) {
+        let (start_glyph_id, end_glyph_id) = if start_glyph_id <= end_glyph_id {
+            (start_glyph_id, end_glyph_id)
+        } else {
+            (end_glyph_id, start_glyph_id)
+        };
+        self.remove_range(start_glyph_id, end_glyph_id);
+        let index = self
+            .doc_records
+            .partition_point(|rec| rec.end_glyph_id < start_glyph_id);
+        self.doc_records.insert(
+            index,
+            SvgDocRecord {
+                start_glyph_id,
+                end_glyph_id,
+                svg_doc: svg.to_string(),
+            },
+        );
+        self.num_entries = self.doc_records.len() as u16;
+    }
+
+    /// Remove the SVG document of `glyph_id`. Return `false` if the glyph has no document.
+    pub fn remove_glyph(&mut self, glyph_id: u16) -> bool {
+        let removed = self.remove_range(glyph_id, glyph_id);
+        self.num_entries = self.doc_records.len() as u16;
+        removed
+    }
+
+    fn remove_range(&mut self, start: u16, end: u16) -> bool {
+        let mut removed = false;
+        let mut doc_records = Vec::with_capacity(self.doc_records.len());
+        for rec in self.doc_records.drain(..) {
+            if rec.end_glyph_id < start || rec.start_glyph_id > end {
+                doc_records.push(rec);
+                continue;
+            }
+            removed = true;
+            // Keep the parts of the record outside of the range.
+            if rec.start_glyph_id < start {
+                doc_records.push(SvgDocRecord {
+                    start_glyph_id: rec.start_glyph_id,
+                    end_glyph_id: start - 1,
+                    svg_doc: rec.svg_doc.clone(),
+                });
+            }
+            if rec.end_glyph_id > end {
+                doc_records.push(SvgDocRecord {
+                    start_glyph_id: end + 1,
+                    end_glyph_id: rec.end_glyph_id,
+                    svg_doc: rec.svg_doc,
+                });
+            }
+        }
+        self.doc_records = doc_records;
+        removed
     }
 }
 
@@ -43,38 +133,69 @@ pub struct SvgDocRecord {
 }
 
 impl SvgDocRecord {
-    fn read(buffer: &mut Buffer, start: usize) -> Self {
+    fn read(buffer: &mut Buffer, start: usize) -> Result<Self, FontError> {
         let offset = buffer.offset();
         let start_glyph_id = buffer.get();
         let end_glyph_id = buffer.get();
         let svg_doc_offset: u32 = buffer.get();
         let svg_doc_length: u32 = buffer.get();
         buffer.set_offset_from(start, svg_doc_offset);
-        let svg_doc = Self::get_svg_doc(buffer, svg_doc_length as usize);
+        let svg_doc = Self::get_svg_doc(buffer, svg_doc_length as usize)?;
         buffer.set_offset(offset + 12); // u16 + u16 + u32 + u32
-        Self {
+        Ok(Self {
             start_glyph_id,
             end_glyph_id,
             svg_doc,
-        }
+        })
     }
 
-    fn get_svg_doc(buffer: &mut Buffer, len: usize) -> String {
+    fn get_svg_doc(buffer: &mut Buffer, len: usize) -> Result<String, FontError> {
         let utf8 = if len > 3 && Self::check_gzip_header(buffer) {
-            let mut orig_buffer = buffer.gz_decompress(len).unwrap();
+            let mut orig_buffer = buffer
+                .gz_decompress(len)
+                .map_err(|err| FontError::Decompression(err.to_string()))?;
             orig_buffer.get_vec(orig_buffer.len())
         } else {
             buffer.get_vec(len)
         };
-        String::from_utf8(utf8).unwrap()
+        String::from_utf8(utf8).map_err(|err| FontError::Encoding(err.utf8_error()))
     }
 
     fn check_gzip_header(buffer: &mut Buffer) -> bool {
         let start = buffer.offset();
-        let header: Vec<u8> = buffer.get_vec(GZIP_HEADER.len());
+        let header = buffer.get_bytes(GZIP_HEADER.len());
         buffer.set_offset(start);
         header == GZIP_HEADER
     }
 }
 
 const GZIP_HEADER: &[u8] = &[0x1F, 0x8B, 0x08];
+
+#[test]
+fn test_set_glyph() {
+    let mut svg = Table_SVG_ {
+        version: 0,
+        num_entries: 0,
+        doc_records: Vec::new(),
+    };
+    svg.set_glyph(10, 20, "a");
+    svg.set_glyph(30, 40, "b");
+
+    // Overlapping both records
+    svg.set_glyph(15, 35, "c");
+    assert_eq!(svg.glyph_ids(), [10..=14, 15..=35, 36..=40]);
+
+    // Inside a record, which is split
+    svg.set_glyph(12, 12, "d");
+    assert_eq!(
+        svg.glyph_ids(),
+        [10..=11, 12..=12, 13..=14, 15..=35, 36..=40]
+    );
+    let docs: Vec<&str> = svg.doc_records.iter().map(|rec| &*rec.svg_doc).collect();
+    assert_eq!(docs, ["a", "d", "a", "c", "b"]);
+
+    // Inverted range
+    svg.set_glyph(50, 45, "e");
+    assert_eq!(svg.glyph_ids().last(), Some(&(45..=50)));
+    assert_eq!(svg.num_entries, 6);
+}
@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::{Buffer, ReadBuffer};
@@ -21,7 +22,7 @@ pub struct Table_BASE {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_BASE(&mut self, buffer: &mut Buffer) {
+    pub fn parse_BASE(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let base_start = buffer.offset();
         let version = buffer.get_version::<u16>();
         let horiz_axis_offset: u16 = buffer.get();
@@ -37,9 +38,63 @@ impl Font {
             horiz_axis: buffer.get_or_none(base_start, horiz_axis_offset),
             vert_axis: buffer.get_or_none(base_start, vert_axis_offset),
         });
+        Ok(())
     }
 }
 
+impl Table_BASE {
+    /// Return the axis table for the given direction.
+    pub fn axis(&self, axis: BaseAxis) -> Option<&Axis> {
+        match axis {
+            BaseAxis::Horizontal => self.horiz_axis.as_ref(),
+            BaseAxis::Vertical => self.vert_axis.as_ref(),
+        }
+    }
+
+    /// Return the coordinate of `baseline` for `script` along `axis`.
+    ///
+    /// The `BASE` table stores baseline coordinates per script only. Language systems may
+    /// override the min/max extents but not the baselines, so every `lang` of a script gets
+    /// the same value.
+    pub fn baseline_value_for_lang(
+        &self,
+        script: Tag,
+        _lang: Tag,
+        axis: BaseAxis,
+        baseline: Tag,
+    ) -> Option<i16> {
+        let axis = self.axis(axis)?;
+        let index = axis.base_tag_list.iter().position(|&tag| tag == baseline)?;
+        let base_script = axis.base_script(script)?;
+        let base_values = base_script.base_values.as_ref()?;
+        base_values
+            .base_coords
+            .get(index)
+            .map(|coord| coord.coordinate)
+    }
+
+    /// Return the minimum extent of `script` and `lang` along `axis`, falling back to the
+    /// default value of the script if the language system is not found.
+    pub fn min_extent(&self, script: Tag, lang: Tag, axis: BaseAxis) -> Option<i16> {
+        let min_max = self.axis(axis)?.base_script(script)?.min_max(lang)?;
+        min_max.min_coord.as_ref().map(|coord| coord.coordinate)
+    }
+
+    /// Return the maximum extent of `script` and `lang` along `axis`, falling back to the
+    /// default value of the script if the language system is not found.
+    pub fn max_extent(&self, script: Tag, lang: Tag, axis: BaseAxis) -> Option<i16> {
+        let min_max = self.axis(axis)?.base_script(script)?.min_max(lang)?;
+        min_max.max_coord.as_ref().map(|coord| coord.coordinate)
+    }
+}
+
+/// The text direction an [`Axis`] applies to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BaseAxis {
+    Horizontal,
+    Vertical,
+}
+
 #[derive(Debug)]
 pub struct Axis {
     pub base_tag_list: Vec<Tag>,
@@ -72,6 +127,16 @@ impl ReadBuffer for Axis {
     }
 }
 
+impl Axis {
+    /// Return the `BaseScript` table of `script`.
+    pub fn base_script(&self, script: Tag) -> Option<&BaseScript> {
+        self.base_script_list
+            .iter()
+            .find(|rec| rec.base_script_tag == script)
+            .map(|rec| &rec.base_script)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct BaseScriptRecord {
     pub base_script_tag: Tag,
@@ -115,6 +180,18 @@ impl ReadBuffer for BaseScript {
     }
 }
 
+impl BaseScript {
+    /// Return the `MinMax` table of `lang`, or the default one of the script if the language
+    /// system is not found.
+    pub fn min_max(&self, lang: Tag) -> Option<&MinMax> {
+        self.base_lang_sys_records
+            .iter()
+            .find(|rec| rec.base_lang_sys_tag == lang)
+            .and_then(|rec| rec.min_max.as_ref())
+            .or(self.default_min_max.as_ref())
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct BaseLangSysRecord {
     pub base_lang_sys_tag: Tag,
@@ -226,7 +303,7 @@ impl ReadBuffer for BaseCoord {
                 base_coord.base_coord_point = Some(buffer.get());
             }
             3 => base_coord.device_offset = Some(buffer.get()),
-            _ => unreachable!(),
+            _ => buffer.set_unknown_format(format),
         }
         base_coord
     }
@@ -0,0 +1,344 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::tables::layout::gsub::read_from;
+use crate::tables::layout::math::Coverage;
+use crate::util::{Buffer, ReadBuffer};
+use read_buffer_derive::ReadBuffer;
+
+/// ## `GDEF` &mdash; Glyph Definition Table
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/gdef>.
+///
+/// The Glyph Definition (`GDEF`) table provides various glyph properties used in OpenType
+/// Layout processing. It classifies glyphs as base, ligature, mark or component, which is
+/// used by the lookup flags of `GSUB` and `GPOS` to skip over certain classes of glyphs.
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+pub struct Table_GDEF {
+    version: String,
+    pub glyph_class_def: Option<ClassDef>,
+    pub attach_list: Option<AttachList>,
+    pub lig_caret_list: Option<LigCaretList>,
+    pub mark_attach_class_def: Option<ClassDef>,
+    /// Mark glyph sets (since version 1.2), which are referred to by the mark filtering set
+    /// of `GSUB` and `GPOS` lookups.
+    pub mark_glyph_sets: Vec<Coverage>,
+}
+
+impl Font {
+    #[allow(non_snake_case)]
+    pub fn parse_GDEF(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let gdef_start = buffer.offset();
+        let version = buffer.get_version::<u16>();
+        let glyph_class_def_offset: u16 = buffer.get();
+        let attach_list_offset: u16 = buffer.get();
+        let lig_caret_list_offset: u16 = buffer.get();
+        let mark_attach_class_def_offset: u16 = buffer.get();
+        let mark_glyph_sets_def_offset: u16 = if version == "1.0" { 0 } else { buffer.get() };
+        self.GDEF = Some(Table_GDEF {
+            version,
+            glyph_class_def: buffer.get_or_none(gdef_start, glyph_class_def_offset),
+            attach_list: buffer.get_or_none(gdef_start, attach_list_offset),
+            lig_caret_list: buffer.get_or_none(gdef_start, lig_caret_list_offset),
+            mark_attach_class_def: buffer.get_or_none(gdef_start, mark_attach_class_def_offset),
+            mark_glyph_sets: match mark_glyph_sets_def_offset {
+                0 => Vec::new(),
+                offset => {
+                    buffer.set_offset_from(gdef_start, offset);
+                    read_mark_glyph_sets(buffer)
+                }
+            },
+        });
+        Ok(())
+    }
+}
+
+impl Table_GDEF {
+    /// Return the glyph class of `glyph_id`, or `None` if the glyph is not covered by the
+    /// glyph class definition table (or there is no such table).
+    pub fn glyph_class(&self, glyph_id: u16) -> Option<GlyphClass> {
+        let class_def = self.glyph_class_def.as_ref()?;
+        match GlyphClass::from(class_def.class(glyph_id)) {
+            GlyphClass::Unclassified => None,
+            glyph_class => Some(glyph_class),
+        }
+    }
+
+    /// Return the attachment point indices of `glyph_id`.
+    pub fn attach_points(&self, glyph_id: u16) -> Option<&[u16]> {
+        let attach_list = self.attach_list.as_ref()?;
+        let index = attach_list.coverage.index_of(glyph_id)?;
+        attach_list.attach_points.get(index).map(Vec::as_slice)
+    }
+
+    /// Return the caret values of the ligature glyph `glyph_id`, in increasing coordinate
+    /// order.
+    pub fn lig_carets(&self, glyph_id: u16) -> Option<&[CaretValue]> {
+        let lig_caret_list = self.lig_caret_list.as_ref()?;
+        let index = lig_caret_list.coverage.index_of(glyph_id)?;
+        lig_caret_list.lig_glyphs.get(index).map(Vec::as_slice)
+    }
+
+    /// Return the mark attachment class of `glyph_id`, or 0 if it has none.
+    pub fn mark_attach_class(&self, glyph_id: u16) -> u16 {
+        self.mark_attach_class_def
+            .as_ref()
+            .map_or(0, |class_def| class_def.class(glyph_id))
+    }
+
+    /// Return whether `glyph_id` is in the mark glyph set at `set_index`.
+    pub fn is_in_mark_glyph_set(&self, set_index: u16, glyph_id: u16) -> bool {
+        self.mark_glyph_sets
+            .get(set_index as usize)
+            .is_some_and(|coverage| coverage.contains(glyph_id))
+    }
+}
+
+fn read_mark_glyph_sets(buffer: &mut Buffer) -> Vec<Coverage> {
+    let mark_glyph_sets_start = buffer.offset();
+    let format: u16 = buffer.get();
+    if format != 1 {
+        buffer.set_unknown_format(format);
+        return Vec::new();
+    }
+    let mark_glyph_set_count: u16 = buffer.get();
+    let coverage_offsets: Vec<u32> = buffer.get_vec(mark_glyph_set_count);
+    coverage_offsets
+        .iter()
+        .map(|&offset| {
+            buffer.set_offset_from(mark_glyph_sets_start, offset);
+            buffer.get()
+        })
+        .collect()
+}
+
+/// Attachment point list, which lists the contour points of the glyphs used as attachment
+/// points, e.g. for cursive and mark attachment.
+#[derive(Debug)]
+pub struct AttachList {
+    pub coverage: Coverage,
+    /// Point indices of each glyph, ordered by coverage index.
+    pub attach_points: Vec<Vec<u16>>,
+}
+
+impl ReadBuffer for AttachList {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let coverage_offset: u16 = buffer.get();
+        let glyph_count: u16 = buffer.get();
+        let attach_point_offsets: Vec<u16> = buffer.get_vec(glyph_count);
+        let attach_points = attach_point_offsets
+            .iter()
+            .map(|&offset| {
+                buffer.set_offset_from(start, offset);
+                let point_count: u16 = buffer.get();
+                buffer.get_vec(point_count)
+            })
+            .collect();
+        Self {
+            coverage: read_from(buffer, start, coverage_offset),
+            attach_points,
+        }
+    }
+}
+
+/// Ligature caret list, which defines the caret positions of the ligature glyphs.
+#[derive(Debug)]
+pub struct LigCaretList {
+    pub coverage: Coverage,
+    /// Caret values of each ligature glyph, ordered by coverage index.
+    pub lig_glyphs: Vec<Vec<CaretValue>>,
+}
+
+impl ReadBuffer for LigCaretList {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let coverage_offset: u16 = buffer.get();
+        let lig_glyph_count: u16 = buffer.get();
+        let lig_glyph_offsets: Vec<u16> = buffer.get_vec(lig_glyph_count);
+        let lig_glyphs = lig_glyph_offsets
+            .iter()
+            .map(|&offset| {
+                let lig_glyph_start = start + offset as usize;
+                buffer.set_offset(lig_glyph_start);
+                let caret_count: u16 = buffer.get();
+                let caret_value_offsets: Vec<u16> = buffer.get_vec(caret_count);
+                caret_value_offsets
+                    .iter()
+                    .map(|&offset| read_from(buffer, lig_glyph_start, offset))
+                    .collect()
+            })
+            .collect();
+        Self {
+            coverage: read_from(buffer, start, coverage_offset),
+            lig_glyphs,
+        }
+    }
+}
+
+/// Caret value of a ligature glyph.
+#[derive(Debug, PartialEq)]
+pub enum CaretValue {
+    /// Design units only.
+    Format1 { coordinate: i16 },
+    /// Contour point index on the ligature glyph.
+    Format2 { caret_value_point_index: u16 },
+    /// Design units plus a device (or variation index) table, which is kept as an offset
+    /// from the caret value table.
+    Format3 { coordinate: i16, device_offset: u16 },
+}
+
+impl ReadBuffer for CaretValue {
+    fn read(buffer: &mut Buffer) -> Self {
+        let format: u16 = buffer.get();
+        match format {
+            1 => Self::Format1 {
+                coordinate: buffer.get(),
+            },
+            2 => Self::Format2 {
+                caret_value_point_index: buffer.get(),
+            },
+            3 => Self::Format3 {
+                coordinate: buffer.get(),
+                device_offset: buffer.get(),
+            },
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format1 { coordinate: 0 }
+            }
+        }
+    }
+}
+
+/// Glyph classes defined in the glyph class definition table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GlyphClass {
+    Unclassified = 0,
+    /// Single character, spacing glyph.
+    Base = 1,
+    /// Multiple character, spacing glyph.
+    Ligature = 2,
+    /// Non-spacing combining glyph.
+    Mark = 3,
+    /// Part of single character, spacing glyph.
+    Component = 4,
+}
+
+impl From<u16> for GlyphClass {
+    fn from(class: u16) -> Self {
+        match class {
+            1 => Self::Base,
+            2 => Self::Ligature,
+            3 => Self::Mark,
+            4 => Self::Component,
+            _ => Self::Unclassified,
+        }
+    }
+}
+
+// Shared Formats
+
+/// Class definition table, which groups glyphs into classes.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClassDef {
+    Format1 {
+        start_glyph_id: u16,
+        class_values: Vec<u16>,
+    },
+    Format2 {
+        class_ranges: Vec<ClassRangeRecord>,
+    },
+}
+
+impl ClassDef {
+    /// Return the class of `glyph_id`. Glyphs not assigned to any class are in class 0.
+    pub fn class(&self, glyph_id: u16) -> u16 {
+        match self {
+            Self::Format1 {
+                start_glyph_id,
+                class_values,
+            } => glyph_id
+                .checked_sub(*start_glyph_id)
+                .and_then(|i| class_values.get(i as usize).copied())
+                .unwrap_or(0),
+            Self::Format2 { class_ranges } => class_ranges
+                .iter()
+                .find(|rec| rec.start_glyph_id <= glyph_id && glyph_id <= rec.end_glyph_id)
+                .map_or(0, |rec| rec.class),
+        }
+    }
+}
+
+impl ReadBuffer for ClassDef {
+    fn read(buffer: &mut Buffer) -> Self {
+        let format: u16 = buffer.get();
+        match format {
+            1 => {
+                let start_glyph_id = buffer.get();
+                let glyph_count: u16 = buffer.get();
+                Self::Format1 {
+                    start_glyph_id,
+                    class_values: buffer.get_vec(glyph_count),
+                }
+            }
+            2 => {
+                let class_range_count: u16 = buffer.get();
+                Self::Format2 {
+                    class_ranges: buffer.get_vec(class_range_count),
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format2 {
+                    class_ranges: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassRangeRecord {
+    pub start_glyph_id: u16,
+    pub end_glyph_id: u16,
+    pub class: u16,
+}
+
+#[test]
+fn test_lig_caret_list() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 28, 0, 1, 0, 6, // coverage, 1 ligature glyph
+        0, 3, 0, 8, 0, 12, 0, 16, // 3 carets
+        0, 1, 0, 0x64, // format 1: 100
+        0, 2, 0, 5, // format 2: point 5
+        0, 3, 0xFF, 0x38, 0, 0, // format 3: -200, no device table
+        0, 1, 0, 1, 0, 10, // coverage format 1: glyph 10
+    ]);
+    let lig_caret_list: LigCaretList = buffer.get();
+    assert_eq!(lig_caret_list.coverage.glyphs(), vec![10]);
+    assert_eq!(
+        lig_caret_list.lig_glyphs[0],
+        vec![
+            CaretValue::Format1 { coordinate: 100 },
+            CaretValue::Format2 {
+                caret_value_point_index: 5
+            },
+            CaretValue::Format3 {
+                coordinate: -200,
+                device_offset: 0
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_class_def_unknown_format() {
+    let mut buffer = Buffer::new(vec![0, 3, 0, 1, 0, 2]);
+    let class_def: ClassDef = buffer.get();
+    assert_eq!(class_def.class(1), 0);
+    assert_eq!(buffer.take_unknown_format(), Some(3));
+    assert_eq!(buffer.take_unknown_format(), None);
+}
@@ -0,0 +1,878 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::tables::layout::gdef::{ClassDef, Table_GDEF};
+use crate::tables::layout::gsub::{
+    active_features, follow_extension, read_feature_list, read_from, read_lookup_list,
+    read_script_list, ChainedContextSubstSubtable, ContextSubstSubtable, Feature, FeatureRecord,
+    FeatureVariations, LookupFlags, ScriptRecord,
+};
+use crate::tables::layout::math::Coverage;
+use crate::tables::otvar::fvar::Table_fvar;
+use crate::types::Tag;
+use crate::util::{Buffer, ReadBuffer};
+use std::collections::HashMap;
+
+/// ## `GPOS` &mdash; Glyph Positioning Table
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/gpos>.
+///
+/// The Glyph Positioning table (`GPOS`) provides precise control over glyph placement for
+/// sophisticated text layout and rendering in each script and language system that a font
+/// supports. It shares the script list, feature list and lookup list structures with `GSUB`.
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table_GPOS {
+    version: String,
+    pub script_list: Vec<ScriptRecord>,
+    pub feature_list: Vec<FeatureRecord>,
+    pub lookup_list: Vec<Lookup>,
+    /// Only in version 1.1.
+    pub feature_variations: Option<FeatureVariations>,
+}
+
+impl Font {
+    #[allow(non_snake_case)]
+    pub fn parse_GPOS(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let gpos_start = buffer.offset();
+        let version = buffer.get_version::<u16>();
+        let script_list_offset: u16 = buffer.get();
+        let feature_list_offset: u16 = buffer.get();
+        let lookup_list_offset: u16 = buffer.get();
+        let feature_variations_offset: u32 = if version == "1.1" { buffer.get() } else { 0 };
+
+        buffer.set_offset_from(gpos_start, script_list_offset);
+        let script_list = read_script_list(buffer);
+        buffer.set_offset_from(gpos_start, feature_list_offset);
+        let feature_list = read_feature_list(buffer);
+        buffer.set_offset_from(gpos_start, lookup_list_offset);
+        let lookup_list = read_lookup_list(buffer);
+        let feature_variations = buffer.get_or_none(gpos_start, feature_variations_offset);
+
+        self.GPOS = Some(Table_GPOS {
+            version,
+            script_list,
+            feature_list,
+            lookup_list,
+            feature_variations,
+        });
+        Ok(())
+    }
+}
+
+impl Table_GPOS {
+    /// Number of scripts in the script list.
+    pub fn script_count(&self) -> usize {
+        self.script_list.len()
+    }
+
+    /// Number of features in the feature list.
+    pub fn feature_count(&self) -> usize {
+        self.feature_list.len()
+    }
+
+    /// Number of lookups in the lookup list.
+    pub fn lookup_count(&self) -> usize {
+        self.lookup_list.len()
+    }
+
+    /// Return the first feature in the feature list tagged `tag`, e.g. to check whether
+    /// the font has `kern` at all.
+    pub fn feature_by_tag(&self, tag: Tag) -> Option<&Feature> {
+        self.feature_list
+            .iter()
+            .find(|rec| rec.feature_tag == tag)
+            .map(|rec| &rec.feature)
+    }
+
+    /// Return the lookup at `index` of the lookup list.
+    pub fn lookup(&self, index: u16) -> Option<&Lookup> {
+        self.lookup_list.get(index as usize)
+    }
+
+    /// Return the tags of the features that have any lookups at the normalized coordinates
+    /// `coords` (e.g. from [`Font::normalize_coordinates`]), after applying the feature
+    /// variations. The conditions of the feature variations refer to the axes in the order of
+    /// `fvar`, which should be provided for variable fonts. Missing axes are at 0.
+    pub fn active_features(
+        &self,
+        coords: &HashMap<Tag, f32>,
+        fvar: Option<&Table_fvar>,
+    ) -> Vec<Tag> {
+        active_features(&self.feature_list, &self.feature_variations, coords, fvar)
+    }
+
+    /// Return the `(mark_anchor, base_anchor)` pair for attaching `mark` to `base`, from the
+    /// first mark-to-base subtable that covers both glyphs.
+    pub fn mark_base_attachment(&self, mark: u16, base: u16) -> Option<(Anchor, Anchor)> {
+        self.subtables().find_map(|subtable| match subtable {
+            SubtableData::MarkToBase(mark_base) => mark_base.attachment(mark, base),
+            _ => None,
+        })
+    }
+
+    /// Return the `(mark_anchor, ligature_anchor)` pair for attaching `mark` to the
+    /// `component`-th component of `ligature`, from the first mark-to-ligature subtable that
+    /// covers both glyphs.
+    pub fn mark_ligature_attachment(
+        &self,
+        mark: u16,
+        ligature: u16,
+        component: usize,
+    ) -> Option<(Anchor, Anchor)> {
+        self.subtables().find_map(|subtable| match subtable {
+            SubtableData::MarkToLigature(mark_ligature) => {
+                mark_ligature.attachment(mark, ligature, component)
+            }
+            _ => None,
+        })
+    }
+
+    /// Return the `(mark1_anchor, mark2_anchor)` pair for attaching `mark1` to the preceding
+    /// mark `mark2`, from the first mark-to-mark subtable that covers both glyphs.
+    pub fn mark_mark_attachment(&self, mark1: u16, mark2: u16) -> Option<(Anchor, Anchor)> {
+        self.subtables().find_map(|subtable| match subtable {
+            SubtableData::MarkToMark(mark_mark) => mark_mark.attachment(mark1, mark2),
+            _ => None,
+        })
+    }
+
+    /// Return the horizontal kerning (the `x_advance` adjustment of the first glyph) between
+    /// `left` and `right`. In each pair adjustment lookup, the first subtable that covers the
+    /// pair is used, and the values from all such lookups are added up.
+    pub fn kern_for_pair(&self, left: u16, right: u16) -> i16 {
+        self.lookup_list
+            .iter()
+            .filter_map(|lookup| {
+                lookup.subtables.iter().find_map(|subtable| match subtable {
+                    SubtableData::PairAdjust(pair_adjust) => pair_adjust.kern(left, right),
+                    _ => None,
+                })
+            })
+            .sum()
+    }
+
+    fn subtables(&self) -> impl Iterator<Item = &SubtableData> {
+        self.lookup_list
+            .iter()
+            .flat_map(|lookup| lookup.subtables.iter())
+    }
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lookup {
+    /// Extension lookups are resolved to the type of the subtables they wrap.
+    pub lookup_type: u16,
+    pub lookup_flag: u16,
+    subtable_count: u16,
+    subtable_offsets: Vec<u16>,
+    /// Index of the mark glyph set in `GDEF`, only present if the lookup flag sets
+    /// `USE_MARK_FILTERING_SET`.
+    pub mark_filtering_set: Option<u16>,
+    pub subtables: Vec<SubtableData>,
+}
+
+impl Lookup {
+    /// Return the parsed lookup flags.
+    pub fn flags(&self) -> LookupFlags {
+        LookupFlags::from(self.lookup_flag)
+    }
+
+    /// Return whether the lookup applies to `glyph_id`. See [`LookupFlags`].
+    pub fn effective_for_glyph(&self, glyph_id: u16, gdef: &Table_GDEF) -> bool {
+        self.flags()
+            .effective_for_glyph(self.mark_filtering_set, glyph_id, gdef)
+    }
+}
+
+impl ReadBuffer for Lookup {
+    fn read(buffer: &mut Buffer) -> Self {
+        const EXTENSION: u16 = 9;
+
+        let lookup_start = buffer.offset();
+        let lookup_type = buffer.get();
+        let lookup_flag = buffer.get();
+        let subtable_count = buffer.get();
+        let subtable_offsets: Vec<u16> = buffer.get_vec(subtable_count);
+        let mark_filtering_set = if LookupFlags::from(lookup_flag).use_mark_filtering_set {
+            Some(buffer.get())
+        } else {
+            None
+        };
+        // All subtables of an extension lookup must wrap the same lookup type.
+        let mut subtable_type = lookup_type;
+        let subtables = subtable_offsets
+            .iter()
+            .map(|&offset| {
+                buffer.set_offset_from(lookup_start, offset);
+                subtable_type = follow_extension(buffer, lookup_type, EXTENSION);
+                SubtableData::read(buffer, subtable_type)
+            })
+            .collect();
+        Self {
+            lookup_type: subtable_type,
+            lookup_flag,
+            subtable_count,
+            subtable_offsets,
+            mark_filtering_set,
+            subtables,
+        }
+    }
+}
+
+/// A parsed lookup subtable. The variant is determined by the lookup type. Extension
+/// subtables (type 9) are resolved to the subtables they wrap.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubtableData {
+    /// Type 1: adjust the position of a single glyph.
+    SingleAdjust(SingleAdjustSubtable),
+    /// Type 2: adjust the positions of a pair of glyphs.
+    PairAdjust(PairAdjustSubtable),
+    /// Type 3: attach cursive glyphs.
+    CursiveAttach(CursiveAttachSubtable),
+    /// Type 4: attach a combining mark to a base glyph.
+    MarkToBase(MarkToBaseSubtable),
+    /// Type 5: attach a combining mark to a ligature.
+    MarkToLigature(MarkToLigatureSubtable),
+    /// Type 6: attach a combining mark to another mark.
+    MarkToMark(MarkToMarkSubtable),
+    /// Type 7: position one or more glyphs in context. The subtable has the same layout as
+    /// in `GSUB`, and its lookup records refer to `GPOS` lookups.
+    Context(ContextSubstSubtable),
+    /// Type 8: position one or more glyphs in chained context.
+    ChainedContext(ChainedContextSubstSubtable),
+    /// Invalid lookup types.
+    Unsupported { lookup_type: u16 },
+}
+
+// We can't use trait `ReadBuffer` here because the format of the subtable depends on
+// `lookup_type`, which is stored in the lookup table.
+impl SubtableData {
+    fn read(buffer: &mut Buffer, lookup_type: u16) -> Self {
+        match lookup_type {
+            1 => Self::SingleAdjust(buffer.get()),
+            2 => Self::PairAdjust(buffer.get()),
+            3 => Self::CursiveAttach(buffer.get()),
+            4 => Self::MarkToBase(buffer.get()),
+            5 => Self::MarkToLigature(buffer.get()),
+            6 => Self::MarkToMark(buffer.get()),
+            7 => Self::Context(buffer.get()),
+            8 => Self::ChainedContext(buffer.get()),
+            _ => Self::Unsupported { lookup_type },
+        }
+    }
+}
+
+/// Single adjustment positioning subtable (`SinglePosFormat1/2`).
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SingleAdjustSubtable {
+    /// The same adjustment for all covered glyphs.
+    Format1 {
+        coverage: Coverage,
+        value_record: ValueRecord,
+    },
+    /// Adjustments of each covered glyph, ordered by coverage index.
+    Format2 {
+        coverage: Coverage,
+        value_records: Vec<ValueRecord>,
+    },
+}
+
+impl SingleAdjustSubtable {
+    /// Return the adjustment of `glyph_id`, or `None` if it is not covered by the subtable.
+    pub fn adjustment(&self, glyph_id: u16) -> Option<ValueRecord> {
+        match self {
+            Self::Format1 {
+                coverage,
+                value_record,
+            } => coverage.index_of(glyph_id).map(|_| *value_record),
+            Self::Format2 {
+                coverage,
+                value_records,
+            } => value_records.get(coverage.index_of(glyph_id)?).copied(),
+        }
+    }
+}
+
+impl ReadBuffer for SingleAdjustSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let format: u16 = buffer.get();
+        let coverage_offset: u16 = buffer.get();
+        let value_format: u16 = buffer.get();
+        match format {
+            1 => {
+                let value_record = ValueRecord::read(buffer, value_format);
+                Self::Format1 {
+                    coverage: read_from(buffer, start, coverage_offset),
+                    value_record,
+                }
+            }
+            2 => {
+                let value_count: u16 = buffer.get();
+                let value_records = (0..value_count)
+                    .map(|_| ValueRecord::read(buffer, value_format))
+                    .collect();
+                Self::Format2 {
+                    coverage: read_from(buffer, start, coverage_offset),
+                    value_records,
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format2 {
+                    coverage: Coverage::default(),
+                    value_records: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+/// Pair adjustment positioning subtable (`PairPosFormat1/2`).
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PairAdjustSubtable {
+    /// Adjustments for glyph pairs.
+    Format1 {
+        coverage: Coverage,
+        /// Pair value records of each covered first glyph, ordered by the second glyph.
+        pair_sets: Vec<Vec<PairValueRecord>>,
+    },
+    /// Adjustments for glyph class pairs.
+    Format2 {
+        coverage: Coverage,
+        class_def1: ClassDef,
+        class_def2: ClassDef,
+        /// Value record pairs indexed by the class of the first glyph, then the second.
+        class1_records: Vec<Vec<(ValueRecord, ValueRecord)>>,
+    },
+}
+
+impl PairAdjustSubtable {
+    /// Return the value records of the first and second glyphs of the pair, or `None` if
+    /// the pair is not covered by the subtable.
+    pub fn pair_adjustment(&self, left: u16, right: u16) -> Option<(ValueRecord, ValueRecord)> {
+        match self {
+            Self::Format1 {
+                coverage,
+                pair_sets,
+            } => {
+                let pair_set = pair_sets.get(coverage.index_of(left)?)?;
+                let i = pair_set
+                    .binary_search_by_key(&right, |rec| rec.second_glyph)
+                    .ok()?;
+                let rec = &pair_set[i];
+                Some((rec.value_record1, rec.value_record2))
+            }
+            Self::Format2 {
+                coverage,
+                class_def1,
+                class_def2,
+                ..
+            } => {
+                coverage.index_of(left)?;
+                self.class_pair_adjustment(class_def1.class(left), class_def2.class(right))
+            }
+        }
+    }
+
+    /// Return the value records of the class pair (`left_class`, `right_class`) of a
+    /// format 2 subtable. Return `None` for format 1 subtables or out-of-range classes.
+    pub fn class_pair_adjustment(
+        &self,
+        left_class: u16,
+        right_class: u16,
+    ) -> Option<(ValueRecord, ValueRecord)> {
+        match self {
+            Self::Format1 { .. } => None,
+            Self::Format2 { class1_records, .. } => class1_records
+                .get(left_class as usize)?
+                .get(right_class as usize)
+                .copied(),
+        }
+    }
+
+    /// Return the horizontal kerning of the class pair (`left_class`, `right_class`) of a
+    /// format 2 subtable.
+    pub fn kern_class_pair(&self, left_class: u16, right_class: u16) -> Option<i16> {
+        self.class_pair_adjustment(left_class, right_class)
+            .map(|(value_record1, _)| value_record1.x_advance)
+    }
+
+    fn kern(&self, left: u16, right: u16) -> Option<i16> {
+        self.pair_adjustment(left, right)
+            .map(|(value_record1, _)| value_record1.x_advance)
+    }
+}
+
+impl ReadBuffer for PairAdjustSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let format: u16 = buffer.get();
+        let coverage_offset: u16 = buffer.get();
+        let value_format1: u16 = buffer.get();
+        let value_format2: u16 = buffer.get();
+        match format {
+            1 => {
+                let pair_set_count: u16 = buffer.get();
+                let pair_set_offsets: Vec<u16> = buffer.get_vec(pair_set_count);
+                let pair_sets = pair_set_offsets
+                    .iter()
+                    .map(|&offset| {
+                        buffer.set_offset_from(start, offset);
+                        let pair_value_count: u16 = buffer.get();
+                        (0..pair_value_count)
+                            .map(|_| PairValueRecord {
+                                second_glyph: buffer.get(),
+                                value_record1: ValueRecord::read(buffer, value_format1),
+                                value_record2: ValueRecord::read(buffer, value_format2),
+                            })
+                            .collect()
+                    })
+                    .collect();
+                Self::Format1 {
+                    coverage: read_from(buffer, start, coverage_offset),
+                    pair_sets,
+                }
+            }
+            2 => {
+                let class_def1_offset: u16 = buffer.get();
+                let class_def2_offset: u16 = buffer.get();
+                let class1_count: u16 = buffer.get();
+                let class2_count: u16 = buffer.get();
+                let class1_records = (0..class1_count)
+                    .map(|_| {
+                        (0..class2_count)
+                            .map(|_| {
+                                (
+                                    ValueRecord::read(buffer, value_format1),
+                                    ValueRecord::read(buffer, value_format2),
+                                )
+                            })
+                            .collect()
+                    })
+                    .collect();
+                Self::Format2 {
+                    coverage: read_from(buffer, start, coverage_offset),
+                    class_def1: read_from(buffer, start, class_def1_offset),
+                    class_def2: read_from(buffer, start, class_def2_offset),
+                    class1_records,
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format1 {
+                    coverage: Coverage::default(),
+                    pair_sets: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairValueRecord {
+    pub second_glyph: u16,
+    pub value_record1: ValueRecord,
+    pub value_record2: ValueRecord,
+}
+
+/// Cursive attachment positioning subtable (`CursivePosFormat1`).
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CursiveAttachSubtable {
+    pub coverage: Coverage,
+    pub entry_exit_records: Vec<EntryExitRecord>,
+}
+
+impl CursiveAttachSubtable {
+    /// Return the entry and exit anchors of `glyph_id`.
+    pub fn entry_exit(&self, glyph_id: u16) -> Option<&EntryExitRecord> {
+        self.entry_exit_records
+            .get(self.coverage.index_of(glyph_id)?)
+    }
+}
+
+impl ReadBuffer for CursiveAttachSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let _format: u16 = buffer.get();
+        let coverage_offset: u16 = buffer.get();
+        let entry_exit_count: u16 = buffer.get();
+        let offsets: Vec<(u16, u16)> = (0..entry_exit_count)
+            .map(|_| (buffer.get(), buffer.get()))
+            .collect();
+        let entry_exit_records = offsets
+            .iter()
+            .map(
+                |&(entry_anchor_offset, exit_anchor_offset)| EntryExitRecord {
+                    entry_anchor: buffer.get_or_none(start, entry_anchor_offset),
+                    exit_anchor: buffer.get_or_none(start, exit_anchor_offset),
+                },
+            )
+            .collect();
+        Self {
+            coverage: read_from(buffer, start, coverage_offset),
+            entry_exit_records,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntryExitRecord {
+    pub entry_anchor: Option<Anchor>,
+    pub exit_anchor: Option<Anchor>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkToBaseSubtable {
+    pub mark_coverage: Coverage,
+    pub base_coverage: Coverage,
+    pub mark_array: MarkArray,
+    pub base_array: BaseArray,
+}
+
+impl MarkToBaseSubtable {
+    fn attachment(&self, mark: u16, base: u16) -> Option<(Anchor, Anchor)> {
+        attachment(
+            (&self.mark_coverage, &self.mark_array),
+            (&self.base_coverage, &self.base_array),
+            mark,
+            base,
+        )
+    }
+}
+
+impl ReadBuffer for MarkToBaseSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let (mark_coverage, base_coverage, mark_array, base_array) = read_mark_attachment(buffer);
+        Self {
+            mark_coverage,
+            base_coverage,
+            mark_array,
+            base_array,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkToLigatureSubtable {
+    pub mark_coverage: Coverage,
+    pub ligature_coverage: Coverage,
+    pub mark_array: MarkArray,
+    /// Ligature attach tables, ordered by ligature coverage index. Each of them has the
+    /// same layout as a `BaseArray`, with one record per ligature component.
+    pub ligature_array: Vec<BaseArray>,
+}
+
+impl MarkToLigatureSubtable {
+    fn attachment(&self, mark: u16, ligature: u16, component: usize) -> Option<(Anchor, Anchor)> {
+        let mark_record = self
+            .mark_array
+            .mark_records
+            .get(self.mark_coverage.index_of(mark)?)?;
+        let ligature_anchor = self
+            .ligature_array
+            .get(self.ligature_coverage.index_of(ligature)?)?
+            .base_records
+            .get(component)?
+            .base_anchors
+            .get(mark_record.mark_class as usize)
+            .copied()
+            .flatten()?;
+        Some((mark_record.mark_anchor, ligature_anchor))
+    }
+}
+
+impl ReadBuffer for MarkToLigatureSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let _format: u16 = buffer.get();
+        let mark_coverage_offset: u16 = buffer.get();
+        let ligature_coverage_offset: u16 = buffer.get();
+        let mark_class_count: u16 = buffer.get();
+        let mark_array_offset: u16 = buffer.get();
+        let ligature_array_offset: u16 = buffer.get();
+
+        let ligature_array_start = start + ligature_array_offset as usize;
+        buffer.set_offset(ligature_array_start);
+        let ligature_count: u16 = buffer.get();
+        let ligature_attach_offsets: Vec<u16> = buffer.get_vec(ligature_count);
+        let ligature_array = ligature_attach_offsets
+            .iter()
+            .map(|&offset| {
+                buffer.set_offset_from(ligature_array_start, offset);
+                BaseArray::read(buffer, mark_class_count)
+            })
+            .collect();
+        Self {
+            mark_coverage: read_from(buffer, start, mark_coverage_offset),
+            ligature_coverage: read_from(buffer, start, ligature_coverage_offset),
+            mark_array: read_from(buffer, start, mark_array_offset),
+            ligature_array,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkToMarkSubtable {
+    pub mark1_coverage: Coverage,
+    pub mark2_coverage: Coverage,
+    pub mark1_array: MarkArray,
+    /// The `Mark2Array` has the same layout as a `BaseArray`.
+    pub mark2_array: BaseArray,
+}
+
+impl MarkToMarkSubtable {
+    fn attachment(&self, mark1: u16, mark2: u16) -> Option<(Anchor, Anchor)> {
+        attachment(
+            (&self.mark1_coverage, &self.mark1_array),
+            (&self.mark2_coverage, &self.mark2_array),
+            mark1,
+            mark2,
+        )
+    }
+}
+
+impl ReadBuffer for MarkToMarkSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let (mark1_coverage, mark2_coverage, mark1_array, mark2_array) =
+            read_mark_attachment(buffer);
+        Self {
+            mark1_coverage,
+            mark2_coverage,
+            mark1_array,
+            mark2_array,
+        }
+    }
+}
+
+/// Read a `MarkBasePosFormat1` or `MarkMarkPosFormat1` subtable, which share the same layout.
+fn read_mark_attachment(buffer: &mut Buffer) -> (Coverage, Coverage, MarkArray, BaseArray) {
+    let start = buffer.offset();
+    let _format: u16 = buffer.get();
+    let mark_coverage_offset: u16 = buffer.get();
+    let base_coverage_offset: u16 = buffer.get();
+    let mark_class_count: u16 = buffer.get();
+    let mark_array_offset: u16 = buffer.get();
+    let base_array_offset: u16 = buffer.get();
+    buffer.set_offset_from(start, base_array_offset);
+    let base_array = BaseArray::read(buffer, mark_class_count);
+    (
+        read_from(buffer, start, mark_coverage_offset),
+        read_from(buffer, start, base_coverage_offset),
+        read_from(buffer, start, mark_array_offset),
+        base_array,
+    )
+}
+
+fn attachment(
+    (mark_coverage, mark_array): (&Coverage, &MarkArray),
+    (base_coverage, base_array): (&Coverage, &BaseArray),
+    mark: u16,
+    base: u16,
+) -> Option<(Anchor, Anchor)> {
+    let mark_record = mark_array.mark_records.get(mark_coverage.index_of(mark)?)?;
+    let base_anchor = base_array
+        .base_records
+        .get(base_coverage.index_of(base)?)?
+        .base_anchors
+        .get(mark_record.mark_class as usize)
+        .copied()
+        .flatten()?;
+    Some((mark_record.mark_anchor, base_anchor))
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkArray {
+    pub mark_records: Vec<MarkRecord>,
+}
+
+impl ReadBuffer for MarkArray {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let mark_count: u16 = buffer.get();
+        let records: Vec<(u16, u16)> = (0..mark_count)
+            .map(|_| (buffer.get(), buffer.get()))
+            .collect();
+        let mark_records = records
+            .iter()
+            .map(|&(mark_class, mark_anchor_offset)| MarkRecord {
+                mark_class,
+                mark_anchor: read_from(buffer, start, mark_anchor_offset),
+            })
+            .collect();
+        Self { mark_records }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkRecord {
+    pub mark_class: u16,
+    pub mark_anchor: Anchor,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaseArray {
+    pub base_records: Vec<BaseRecord>,
+}
+
+// We can't use trait `ReadBuffer` here because reading `BaseArray` requires
+// `mark_class_count`, which is stored in the subtable.
+impl BaseArray {
+    fn read(buffer: &mut Buffer, mark_class_count: u16) -> Self {
+        let start = buffer.offset();
+        let base_count: u16 = buffer.get();
+        let offsets: Vec<Vec<u16>> = (0..base_count)
+            .map(|_| buffer.get_vec(mark_class_count))
+            .collect();
+        let base_records = offsets
+            .iter()
+            .map(|anchor_offsets| BaseRecord {
+                base_anchors: anchor_offsets
+                    .iter()
+                    .map(|&offset| buffer.get_or_none(start, offset))
+                    .collect(),
+            })
+            .collect();
+        Self { base_records }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaseRecord {
+    /// Anchors indexed by mark class. NULL anchors are `None`.
+    pub base_anchors: Vec<Option<Anchor>>,
+}
+
+/// Positioning adjustments of a glyph, in design units. Values absent in the value format
+/// are 0, and device table offsets are kept as is (0 for none).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueRecord {
+    pub x_placement: i16,
+    pub y_placement: i16,
+    pub x_advance: i16,
+    pub y_advance: i16,
+    pub x_placement_device_offset: u16,
+    pub y_placement_device_offset: u16,
+    pub x_advance_device_offset: u16,
+    pub y_advance_device_offset: u16,
+}
+
+// We can't use trait `ReadBuffer` here because the fields present in a `ValueRecord`
+// are determined by `value_format`, which is stored in the subtable.
+impl ValueRecord {
+    fn read(buffer: &mut Buffer, value_format: u16) -> Self {
+        const X_PLACEMENT: u16 = 0x0001;
+        const Y_PLACEMENT: u16 = 0x0002;
+        const X_ADVANCE: u16 = 0x0004;
+        const Y_ADVANCE: u16 = 0x0008;
+        const X_PLACEMENT_DEVICE: u16 = 0x0010;
+        const Y_PLACEMENT_DEVICE: u16 = 0x0020;
+        const X_ADVANCE_DEVICE: u16 = 0x0040;
+        const Y_ADVANCE_DEVICE: u16 = 0x0080;
+
+        let mut get_if = |flag: u16| {
+            if value_format & flag != 0 {
+                buffer.get::<u16>()
+            } else {
+                0
+            }
+        };
+        Self {
+            x_placement: get_if(X_PLACEMENT) as i16,
+            y_placement: get_if(Y_PLACEMENT) as i16,
+            x_advance: get_if(X_ADVANCE) as i16,
+            y_advance: get_if(Y_ADVANCE) as i16,
+            x_placement_device_offset: get_if(X_PLACEMENT_DEVICE),
+            y_placement_device_offset: get_if(Y_PLACEMENT_DEVICE),
+            x_advance_device_offset: get_if(X_ADVANCE_DEVICE),
+            y_advance_device_offset: get_if(Y_ADVANCE_DEVICE),
+        }
+    }
+}
+
+/// Anchor point for attaching glyphs, in design units.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Anchor {
+    Format1 {
+        x: i16,
+        y: i16,
+    },
+    /// With an index to a glyph contour point for hinting.
+    Format2 {
+        x: i16,
+        y: i16,
+        anchor_point: u16,
+    },
+    /// With offsets (from the anchor table) to device tables for adjusting the coordinates.
+    Format3 {
+        x: i16,
+        y: i16,
+        x_device_offset: u16,
+        y_device_offset: u16,
+    },
+}
+
+impl Anchor {
+    /// Return the `(x, y)` coordinate of the anchor.
+    pub fn coordinate(&self) -> (i16, i16) {
+        match *self {
+            Self::Format1 { x, y } | Self::Format2 { x, y, .. } | Self::Format3 { x, y, .. } => {
+                (x, y)
+            }
+        }
+    }
+}
+
+impl ReadBuffer for Anchor {
+    fn read(buffer: &mut Buffer) -> Self {
+        let format: u16 = buffer.get();
+        let x = buffer.get();
+        let y = buffer.get();
+        match format {
+            1 => Self::Format1 { x, y },
+            2 => Self::Format2 {
+                x,
+                y,
+                anchor_point: buffer.get(),
+            },
+            3 => Self::Format3 {
+                x,
+                y,
+                x_device_offset: buffer.get(),
+                y_device_offset: buffer.get(),
+            },
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format1 { x, y }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_single_adjust() {
+    // Format 1 with value format X_ADVANCE, covering glyphs 3 and 5
+    let mut buffer = Buffer::new(vec![0, 1, 0, 8, 0, 4, 0xFF, 0x9C, 0, 1, 0, 2, 0, 3, 0, 5]);
+    let subtable: SingleAdjustSubtable = buffer.get();
+    let value_record = subtable.adjustment(5).unwrap();
+    assert_eq!(value_record.x_advance, -100);
+    assert_eq!(value_record.x_placement, 0);
+    assert_eq!(subtable.adjustment(4), None);
+}
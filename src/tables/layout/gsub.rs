@@ -1,7 +1,12 @@
+use crate::error::FontError;
 use crate::font::Font;
-use crate::types::Tag;
+use crate::tables::layout::gdef::{ClassDef, GlyphClass, Table_GDEF};
+use crate::tables::layout::math::Coverage;
+use crate::tables::otvar::fvar::Table_fvar;
+use crate::types::{F2Dot14, Tag};
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
+use std::collections::HashMap;
 
 /// ## `GSUB` &mdash; Glyph Substitution Table
 ///
@@ -13,69 +18,296 @@ use read_buffer_derive::ReadBuffer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_GSUB {
     version: String,
     pub script_list: Vec<ScriptRecord>,
     pub feature_list: Vec<FeatureRecord>,
     pub lookup_list: Vec<Lookup>,
+    /// Only in version 1.1.
+    pub feature_variations: Option<FeatureVariations>,
 }
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_GSUB(&mut self, buffer: &mut Buffer) {
+    pub fn parse_GSUB(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let gsub_start = buffer.offset();
         let version = buffer.get_version::<u16>();
         let script_list_offset: u16 = buffer.get();
         let feature_list_offset: u16 = buffer.get();
         let lookup_list_offset: u16 = buffer.get();
-        // TODO:
-        #[allow(unused_variables)]
-        let feature_variations_offset: Option<u32> = if version == "1.1" {
-            Some(buffer.get())
-        } else {
-            None
-        };
-
-        let script_list_start = gsub_start + script_list_offset as usize;
-        buffer.set_offset(script_list_start);
-        let num_scripts: u16 = buffer.get();
-        let mut script_list: Vec<ScriptRecord> = buffer.get_vec(num_scripts);
-        script_list.iter_mut().for_each(|rec| {
-            buffer.set_offset_from(script_list_start, rec.script_offset);
-            rec.script = buffer.get();
-        });
+        let feature_variations_offset: u32 = if version == "1.1" { buffer.get() } else { 0 };
 
-        let feature_list_start = gsub_start + feature_list_offset as usize;
-        buffer.set_offset(feature_list_start);
-        let num_features: u16 = buffer.get();
-        let mut feature_list: Vec<FeatureRecord> = buffer.get_vec(num_features);
-        feature_list.iter_mut().for_each(|rec| {
-            buffer.set_offset_from(feature_list_start, rec.feature_offset);
-            rec.feature = buffer.get();
-        });
-
-        let lookup_list_start = gsub_start + lookup_list_offset as usize;
-        buffer.set_offset(lookup_list_start);
-        let num_lookups: u16 = buffer.get();
-        let lookup_offsets: Vec<u16> = buffer.get_vec(num_lookups);
-        let lookup_list = lookup_offsets
-            .iter()
-            .map(|&offset| {
-                buffer.set_offset_from(lookup_list_start, offset);
-                buffer.get()
-            })
-            .collect();
+        buffer.set_offset_from(gsub_start, script_list_offset);
+        let script_list = read_script_list(buffer);
+        buffer.set_offset_from(gsub_start, feature_list_offset);
+        let feature_list = read_feature_list(buffer);
+        buffer.set_offset_from(gsub_start, lookup_list_offset);
+        let lookup_list = read_lookup_list(buffer);
+        let feature_variations = buffer.get_or_none(gsub_start, feature_variations_offset);
 
         self.GSUB = Some(Table_GSUB {
             version,
             script_list,
             feature_list,
             lookup_list,
+            feature_variations,
         });
+        Ok(())
     }
 }
 
+impl Table_GSUB {
+    /// Number of scripts in the script list.
+    pub fn script_count(&self) -> usize {
+        self.script_list.len()
+    }
+
+    /// Number of features in the feature list.
+    pub fn feature_count(&self) -> usize {
+        self.feature_list.len()
+    }
+
+    /// Number of lookups in the lookup list.
+    pub fn lookup_count(&self) -> usize {
+        self.lookup_list.len()
+    }
+
+    /// Return the first feature in the feature list tagged `tag`, regardless of the scripts
+    /// and language systems that refer to it.
+    pub fn feature_by_tag(&self, tag: Tag) -> Option<&Feature> {
+        self.feature_list
+            .iter()
+            .find(|rec| rec.feature_tag == tag)
+            .map(|rec| &rec.feature)
+    }
+
+    /// Return the lookup at `index` of the lookup list.
+    pub fn lookup(&self, index: u16) -> Option<&Lookup> {
+        self.lookup_list.get(index as usize)
+    }
+
+    /// Return the tags of the features that have any lookups at the normalized coordinates
+    /// `coords` (e.g. from [`Font::normalize_coordinates`]), after applying the feature
+    /// variations. The conditions of the feature variations refer to the axes in the order of
+    /// `fvar`, which should be provided for variable fonts. Missing axes are at 0.
+    pub fn active_features(
+        &self,
+        coords: &HashMap<Tag, f32>,
+        fvar: Option<&Table_fvar>,
+    ) -> Vec<Tag> {
+        active_features(&self.feature_list, &self.feature_variations, coords, fvar)
+    }
+
+    /// Return the parsed subtables of the lookup at `index`.
+    pub fn lookup_subtables(&self, index: u16) -> Option<&[SubtableData]> {
+        self.lookup(index).map(|lookup| lookup.subtables.as_slice())
+    }
+
+    /// Return the substitute of `glyph_id` in the single substitution lookup at
+    /// `lookup_index`.
+    pub fn single_substitute(&self, lookup_index: u16, glyph_id: u16) -> Option<u16> {
+        self.lookup_subtables(lookup_index)?
+            .iter()
+            .find_map(|subtable| match subtable {
+                SubtableData::Single(single) => single.substitute(glyph_id),
+                _ => None,
+            })
+    }
+
+    /// Return the sequence that replaces `glyph_id` in the multiple substitution lookup
+    /// at `lookup_index`.
+    pub fn multiple_substitute(&self, lookup_index: u16, glyph_id: u16) -> Option<Vec<u16>> {
+        self.lookup_subtables(lookup_index)?
+            .iter()
+            .find_map(|subtable| match subtable {
+                SubtableData::Multiple(multiple) => multiple.substitute(glyph_id),
+                _ => None,
+            })
+            .cloned()
+    }
+
+    /// Return the alternates of `glyph_id` in the alternate substitution lookup at
+    /// `lookup_index`.
+    pub fn alternate_glyphs(&self, lookup_index: u16, glyph_id: u16) -> Option<Vec<u16>> {
+        self.lookup_subtables(lookup_index)?
+            .iter()
+            .find_map(|subtable| match subtable {
+                SubtableData::Alternate(alternate) => alternate.alternates(glyph_id),
+                _ => None,
+            })
+            .cloned()
+    }
+
+    /// Return the ligature that replaces the glyphs at the start of `sequence` in the
+    /// ligature substitution lookup at `lookup_index`, together with the number of glyphs it
+    /// replaces. Ligatures are tried in their order in the font, i.e. by preference.
+    pub fn ligature_substitute(&self, lookup_index: u16, sequence: &[u16]) -> Option<(u16, usize)> {
+        self.lookup_subtables(lookup_index)?
+            .iter()
+            .find_map(|subtable| match subtable {
+                SubtableData::Ligature(ligature) => ligature.substitute(sequence),
+                _ => None,
+            })
+    }
+
+    /// Return the substitute of `glyph_id` in the first single substitution lookup that
+    /// covers it, scanning the lookup list in order. Features are not taken into account.
+    pub fn apply_single(&self, glyph_id: u16) -> Option<u16> {
+        (0..self.lookup_list.len() as u16)
+            .find_map(|lookup_index| self.single_substitute(lookup_index, glyph_id))
+    }
+
+    /// Return the substitutions of the first rule in the (chained) context substitution
+    /// lookup at `lookup_index` that matches `sequence` at `position`, or an empty vector if
+    /// no rule matches. Lookup flags are not taken into account, i.e. no glyphs are skipped.
+    pub fn context_substitute(
+        &self,
+        lookup_index: u16,
+        sequence: &[u16],
+        position: usize,
+    ) -> Vec<SubstLookupRecord> {
+        let subtables = match self.lookup_subtables(lookup_index) {
+            Some(subtables) => subtables,
+            None => return Vec::new(),
+        };
+        subtables
+            .iter()
+            .find_map(|subtable| match subtable {
+                SubtableData::Context(context) => context.apply(sequence, position),
+                SubtableData::ChainedContext(chained) => chained.apply(sequence, position),
+                _ => None,
+            })
+            .map_or_else(Vec::new, |records| records.to_vec())
+    }
+
+    /// Return the substitute of `glyph_id` in the reverse chaining substitution lookup at
+    /// `lookup_index`, given its context. The `backtrack` glyphs are in reverse logical order,
+    /// i.e. starting from the glyph closest to `glyph_id`.
+    pub fn reverse_substitute(
+        &self,
+        lookup_index: u16,
+        glyph_id: u16,
+        backtrack: &[u16],
+        lookahead: &[u16],
+    ) -> Option<u16> {
+        self.lookup_subtables(lookup_index)?
+            .iter()
+            .find_map(|subtable| match subtable {
+                SubtableData::ReverseChained(reverse) => {
+                    reverse.substitute(glyph_id, backtrack, lookahead)
+                }
+                _ => None,
+            })
+    }
+
+    /// Return whether any lookup of the features tagged `feature_tag` is a context (type 5),
+    /// chained context (type 6) or reverse chaining (type 8) substitution. Extension lookups
+    /// are resolved to their actual lookup type when parsing.
+    pub fn has_contextual_rules_for_feature(&self, feature_tag: Tag) -> bool {
+        self.feature_list
+            .iter()
+            .filter(|rec| rec.feature_tag == feature_tag)
+            .flat_map(|rec| rec.feature.lookup_list_indices.iter())
+            .filter_map(|&index| self.lookup(index))
+            .any(|lookup| matches!(lookup.lookup_type, 5 | 6 | 8))
+    }
+
+    /// Return whether any lookup substitutes a glyph that `gdef` classifies as a mark. Only
+    /// the coverage of the first input glyph is checked.
+    pub fn has_mark_substitution(&self, gdef: &Table_GDEF) -> bool {
+        self.lookup_list
+            .iter()
+            .flat_map(|lookup| lookup.subtables.iter())
+            .filter_map(|subtable| subtable.coverage())
+            .flat_map(|coverage| coverage.glyphs())
+            .any(|glyph_id| gdef.glyph_class(glyph_id) == Some(GlyphClass::Mark))
+    }
+}
+
+// The script list, feature list and lookup list are shared with `GPOS`.
+
+pub(crate) fn read_script_list(buffer: &mut Buffer) -> Vec<ScriptRecord> {
+    let script_list_start = buffer.offset();
+    let num_scripts: u16 = buffer.get();
+    let mut script_list: Vec<ScriptRecord> = buffer.get_vec(num_scripts);
+    script_list.iter_mut().for_each(|rec| {
+        buffer.set_offset_from(script_list_start, rec.script_offset);
+        rec.script = buffer.get();
+    });
+    script_list
+}
+
+pub(crate) fn read_feature_list(buffer: &mut Buffer) -> Vec<FeatureRecord> {
+    let feature_list_start = buffer.offset();
+    let num_features: u16 = buffer.get();
+    let mut feature_list: Vec<FeatureRecord> = buffer.get_vec(num_features);
+    feature_list.iter_mut().for_each(|rec| {
+        buffer.set_offset_from(feature_list_start, rec.feature_offset);
+        rec.feature = buffer.get();
+    });
+    feature_list
+}
+
+pub(crate) fn read_lookup_list<T: ReadBuffer>(buffer: &mut Buffer) -> Vec<T> {
+    let lookup_list_start = buffer.offset();
+    let num_lookups: u16 = buffer.get();
+    let lookup_offsets: Vec<u16> = buffer.get_vec(num_lookups);
+    lookup_offsets
+        .iter()
+        .map(|&offset| {
+            buffer.set_offset_from(lookup_list_start, offset);
+            buffer.get()
+        })
+        .collect()
+}
+
+pub(crate) fn active_features(
+    feature_list: &[FeatureRecord],
+    feature_variations: &Option<FeatureVariations>,
+    coords: &HashMap<Tag, f32>,
+    fvar: Option<&Table_fvar>,
+) -> Vec<Tag> {
+    let coords: Vec<f32> = fvar.map_or_else(Vec::new, |fvar| {
+        fvar.axes()
+            .iter()
+            .map(|axis| coords.get(&axis.axis_tag).copied().unwrap_or(0.0))
+            .collect()
+    });
+    let substitutions = feature_variations
+        .as_ref()
+        .and_then(|variations| variations.substitutions(&coords))
+        .unwrap_or_default();
+    let mut tags: Vec<Tag> = Vec::new();
+    feature_list.iter().enumerate().for_each(|(i, rec)| {
+        let feature = substitutions
+            .iter()
+            .find(|subst| subst.feature_index as usize == i)
+            .map_or(&rec.feature, |subst| &subst.alternate_feature);
+        if !feature.lookup_list_indices.is_empty() && !tags.contains(&rec.feature_tag) {
+            tags.push(rec.feature_tag);
+        }
+    });
+    tags
+}
+
+/// If `lookup_type` is `extension_type`, read the extension subtable header at the current
+/// offset and move to the subtable it points to. Return the lookup type of that subtable,
+/// or `lookup_type` itself for other lookups.
+pub(crate) fn follow_extension(buffer: &mut Buffer, lookup_type: u16, extension_type: u16) -> u16 {
+    if lookup_type != extension_type {
+        return lookup_type;
+    }
+    let start = buffer.offset();
+    let _format: u16 = buffer.get();
+    let extension_lookup_type = buffer.get();
+    let extension_offset: u32 = buffer.get();
+    buffer.set_offset_from(start, extension_offset);
+    extension_lookup_type
+}
+
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScriptRecord {
     pub script_tag: Tag,
     pub script: Script,
@@ -93,6 +325,7 @@ impl ReadBuffer for ScriptRecord {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Script {
     pub default_lang_sys: Option<LangSys>,
     pub lang_sys: Vec<(Tag, LangSys)>,
@@ -103,13 +336,13 @@ impl ReadBuffer for Script {
         let script_start = buffer.offset();
         let default_lang_sys_offset: u16 = buffer.get();
         let lang_sys_count: u16 = buffer.get();
-        let lang_sys_records: Vec<LangSysRecord> = buffer.get_vec(lang_sys_count);
+        let lang_sys_records: Vec<(Tag, u16)> = buffer.get_tagged_list(lang_sys_count);
         let default_lang_sys = buffer.get_or_none(script_start, default_lang_sys_offset);
         let lang_sys = lang_sys_records
             .iter()
-            .map(|rec| {
-                buffer.set_offset_from(script_start, rec.lang_sys_offset);
-                (rec.lang_sys_tag, buffer.get())
+            .map(|&(lang_sys_tag, lang_sys_offset)| {
+                buffer.set_offset_from(script_start, lang_sys_offset);
+                (lang_sys_tag, buffer.get())
             })
             .collect();
         Self {
@@ -119,13 +352,8 @@ impl ReadBuffer for Script {
     }
 }
 
-#[derive(ReadBuffer)]
-struct LangSysRecord {
-    lang_sys_tag: Tag,
-    lang_sys_offset: u16,
-}
-
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LangSys {
     pub required_feature_index: u16,
     pub feature_indices: Vec<u16>,
@@ -145,6 +373,7 @@ impl ReadBuffer for LangSys {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FeatureRecord {
     pub feature_tag: Tag,
     pub feature: Feature,
@@ -161,7 +390,137 @@ impl ReadBuffer for FeatureRecord {
     }
 }
 
+/// Feature variations, which substitute the features at certain regions of the design
+/// space of a variable font.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureVariations {
+    pub feature_variation_records: Vec<FeatureVariationRecord>,
+}
+
+impl FeatureVariations {
+    /// Return the feature substitutions of the first record whose conditions are all met
+    /// at the normalized coordinates `coords`. Missing coordinates are treated as 0.
+    pub fn substitutions(&self, coords: &[f32]) -> Option<&[FeatureTableSubstitution]> {
+        self.feature_variation_records
+            .iter()
+            .find(|rec| {
+                rec.condition_set.iter().all(|condition| {
+                    let coord = coords
+                        .get(condition.axis_index as usize)
+                        .map_or(0.0, |&x| x);
+                    f32::from(condition.filter_range_min_value) <= coord
+                        && coord <= f32::from(condition.filter_range_max_value)
+                })
+            })
+            .map(|rec| rec.substitutions.as_slice())
+    }
+}
+
+impl ReadBuffer for FeatureVariations {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        buffer.skip::<u16>(2); // majorVersion = 1, minorVersion = 0
+        let feature_variation_record_count: u32 = buffer.get();
+        let offsets: Vec<(u32, u32)> = (0..feature_variation_record_count)
+            .map(|_| (buffer.get(), buffer.get()))
+            .collect();
+        let feature_variation_records = offsets
+            .iter()
+            .map(
+                |&(condition_set_offset, feature_table_substitution_offset)| {
+                    FeatureVariationRecord {
+                        condition_set: read_condition_set(buffer, start, condition_set_offset),
+                        substitutions: read_feature_table_substitution(
+                            buffer,
+                            start,
+                            feature_table_substitution_offset,
+                        ),
+                    }
+                },
+            )
+            .collect();
+        Self {
+            feature_variation_records,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureVariationRecord {
+    /// All the conditions must be met. An empty condition set matches everywhere.
+    pub condition_set: Vec<Condition>,
+    pub substitutions: Vec<FeatureTableSubstitution>,
+}
+
+fn read_condition_set(buffer: &mut Buffer, start: usize, offset: u32) -> Vec<Condition> {
+    if offset == 0 {
+        return Vec::new();
+    }
+    buffer.set_offset_from(start, offset);
+    let condition_set_start = buffer.offset();
+    let condition_count: u16 = buffer.get();
+    let condition_offsets: Vec<u32> = buffer.get_vec(condition_count);
+    condition_offsets
+        .iter()
+        .filter_map(|&offset| {
+            buffer.set_offset_from(condition_set_start, offset);
+            match buffer.get::<u16>() {
+                1 => Some(buffer.get()),
+                // Unknown formats are ignored.
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn read_feature_table_substitution(
+    buffer: &mut Buffer,
+    start: usize,
+    offset: u32,
+) -> Vec<FeatureTableSubstitution> {
+    if offset == 0 {
+        return Vec::new();
+    }
+    buffer.set_offset_from(start, offset);
+    let substitution_start = buffer.offset();
+    buffer.skip::<u16>(2); // majorVersion = 1, minorVersion = 0
+    let substitution_count: u16 = buffer.get();
+    let records: Vec<(u16, u32)> = (0..substitution_count)
+        .map(|_| (buffer.get(), buffer.get()))
+        .collect();
+    records
+        .iter()
+        .map(|&(feature_index, alternate_feature_offset)| {
+            buffer.set_offset_from(substitution_start, alternate_feature_offset);
+            FeatureTableSubstitution {
+                feature_index,
+                alternate_feature: buffer.get(),
+            }
+        })
+        .collect()
+}
+
+/// Condition table format 1: a range of values of a design axis.
+#[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Condition {
+    pub axis_index: u16,
+    pub filter_range_min_value: F2Dot14,
+    pub filter_range_max_value: F2Dot14,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureTableSubstitution {
+    /// Index of the feature in the feature list to be substituted.
+    pub feature_index: u16,
+    pub alternate_feature: Feature,
+}
+
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Feature {
     pub feature_params_offset: u16,
     pub lookup_list_indices: Vec<u16>,
@@ -180,27 +539,960 @@ impl ReadBuffer for Feature {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Lookup {
+    /// Extension lookups are resolved to the type of the subtables they wrap.
     pub lookup_type: u16,
     pub lookup_flag: u16,
     subtable_count: u16,
     subtable_offsets: Vec<u16>,
-    pub mark_filtering_set: u16,
+    /// Index of the mark glyph set in `GDEF`, only present if the lookup flag sets
+    /// `USE_MARK_FILTERING_SET`.
+    pub mark_filtering_set: Option<u16>,
+    pub subtables: Vec<SubtableData>,
+}
+
+impl Lookup {
+    /// Return the name of the lookup type.
+    pub fn lookup_type_name(&self) -> &'static str {
+        match self.lookup_type {
+            1 => "Single",
+            2 => "Multiple",
+            3 => "Alternate",
+            4 => "Ligature",
+            5 => "Context",
+            6 => "Chained Context",
+            7 => "Extension",
+            8 => "Reverse Chained",
+            _ => "Unknown",
+        }
+    }
+
+    /// Return the parsed lookup flags.
+    pub fn flags(&self) -> LookupFlags {
+        LookupFlags::from(self.lookup_flag)
+    }
+
+    /// Return whether the lookup applies to `glyph_id`, i.e. the glyph is not skipped
+    /// according to the lookup flags and its class in `gdef`.
+    pub fn effective_for_glyph(&self, glyph_id: u16, gdef: &Table_GDEF) -> bool {
+        self.flags()
+            .effective_for_glyph(self.mark_filtering_set, glyph_id, gdef)
+    }
+}
+
+/// Lookup flags, shared by `GSUB` and `GPOS` lookups.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LookupFlags {
+    /// Only used by `GPOS` cursive attachment lookups.
+    pub right_to_left: bool,
+    pub ignore_base_glyphs: bool,
+    pub ignore_ligatures: bool,
+    pub ignore_marks: bool,
+    pub use_mark_filtering_set: bool,
+    /// If not 0, skip marks whose mark attachment class in `GDEF` is different.
+    pub mark_attachment_type: u8,
+}
+
+impl LookupFlags {
+    /// Return whether `glyph_id` is processed (rather than skipped) under these flags.
+    /// `mark_filtering_set` is the mark glyph set of the lookup, if any.
+    pub(crate) fn effective_for_glyph(
+        &self,
+        mark_filtering_set: Option<u16>,
+        glyph_id: u16,
+        gdef: &Table_GDEF,
+    ) -> bool {
+        match gdef.glyph_class(glyph_id) {
+            Some(GlyphClass::Base) => !self.ignore_base_glyphs,
+            Some(GlyphClass::Ligature) => !self.ignore_ligatures,
+            Some(GlyphClass::Mark) if self.ignore_marks => false,
+            Some(GlyphClass::Mark) => match mark_filtering_set {
+                Some(set_index) if self.use_mark_filtering_set => {
+                    gdef.is_in_mark_glyph_set(set_index, glyph_id)
+                }
+                _ if self.mark_attachment_type != 0 => {
+                    gdef.mark_attach_class(glyph_id) == u16::from(self.mark_attachment_type)
+                }
+                _ => true,
+            },
+            _ => true,
+        }
+    }
+}
+
+impl From<u16> for LookupFlags {
+    fn from(lookup_flag: u16) -> Self {
+        const RIGHT_TO_LEFT: u16 = 0x0001;
+        const IGNORE_BASE_GLYPHS: u16 = 0x0002;
+        const IGNORE_LIGATURES: u16 = 0x0004;
+        const IGNORE_MARKS: u16 = 0x0008;
+        const USE_MARK_FILTERING_SET: u16 = 0x0010;
+        const MARK_ATTACHMENT_TYPE_MASK: u16 = 0xFF00;
+
+        Self {
+            right_to_left: lookup_flag & RIGHT_TO_LEFT != 0,
+            ignore_base_glyphs: lookup_flag & IGNORE_BASE_GLYPHS != 0,
+            ignore_ligatures: lookup_flag & IGNORE_LIGATURES != 0,
+            ignore_marks: lookup_flag & IGNORE_MARKS != 0,
+            use_mark_filtering_set: lookup_flag & USE_MARK_FILTERING_SET != 0,
+            mark_attachment_type: ((lookup_flag & MARK_ATTACHMENT_TYPE_MASK) >> 8) as u8,
+        }
+    }
 }
 
 impl ReadBuffer for Lookup {
     fn read(buffer: &mut Buffer) -> Self {
+        const EXTENSION: u16 = 7;
+
+        let lookup_start = buffer.offset();
         let lookup_type = buffer.get();
         let lookup_flag = buffer.get();
         let subtable_count = buffer.get();
-        let subtable_offsets = buffer.get_vec(subtable_count);
-        let mark_filtering_set = buffer.get();
+        let subtable_offsets: Vec<u16> = buffer.get_vec(subtable_count);
+        let mark_filtering_set = if LookupFlags::from(lookup_flag).use_mark_filtering_set {
+            Some(buffer.get())
+        } else {
+            None
+        };
+        // All subtables of an extension lookup must wrap the same lookup type.
+        let mut subtable_type = lookup_type;
+        let subtables = subtable_offsets
+            .iter()
+            .map(|&offset| {
+                buffer.set_offset_from(lookup_start, offset);
+                subtable_type = follow_extension(buffer, lookup_type, EXTENSION);
+                SubtableData::read(buffer, subtable_type)
+            })
+            .collect();
         Self {
-            lookup_type,
+            lookup_type: subtable_type,
             lookup_flag,
             subtable_count,
             subtable_offsets,
             mark_filtering_set,
+            subtables,
+        }
+    }
+}
+
+/// A parsed lookup subtable. The variant is determined by the lookup type.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubtableData {
+    /// Type 1: replace one glyph with one glyph.
+    Single(SingleSubstSubtable),
+    /// Type 2: replace one glyph with a sequence of glyphs.
+    Multiple(MultipleSubstSubtable),
+    /// Type 3: replace one glyph with one of many alternates.
+    Alternate(AlternateSubstSubtable),
+    /// Type 4: replace a sequence of glyphs with one glyph.
+    Ligature(LigatureSubstSubtable),
+    /// Type 5: replace one or more glyphs in context.
+    Context(ContextSubstSubtable),
+    /// Type 6: replace one or more glyphs in chained context.
+    ChainedContext(ChainedContextSubstSubtable),
+    /// Type 8: replace one glyph in chained context, processed in reverse order.
+    ReverseChained(ReverseChainSubstSubtable),
+    /// Unknown lookup types.
+    Unsupported { lookup_type: u16 },
+}
+
+// We can't use trait `ReadBuffer` here because the format of the subtable depends on
+// `lookup_type`, which is stored in the lookup table.
+impl SubtableData {
+    fn read(buffer: &mut Buffer, lookup_type: u16) -> Self {
+        match lookup_type {
+            1 => Self::Single(buffer.get()),
+            2 => Self::Multiple(buffer.get()),
+            3 => Self::Alternate(buffer.get()),
+            4 => Self::Ligature(buffer.get()),
+            5 => Self::Context(buffer.get()),
+            6 => Self::ChainedContext(buffer.get()),
+            8 => Self::ReverseChained(buffer.get()),
+            _ => Self::Unsupported { lookup_type },
         }
     }
+
+    /// Return the coverage of the (first) input glyph of the subtable.
+    fn coverage(&self) -> Option<&Coverage> {
+        match self {
+            Self::Single(SingleSubstSubtable::Format1 { coverage, .. })
+            | Self::Single(SingleSubstSubtable::Format2 { coverage, .. })
+            | Self::Multiple(MultipleSubstSubtable { coverage, .. })
+            | Self::Alternate(AlternateSubstSubtable { coverage, .. })
+            | Self::Ligature(LigatureSubstSubtable { coverage, .. })
+            | Self::ReverseChained(ReverseChainSubstSubtable { coverage, .. })
+            | Self::Context(ContextSubstSubtable::Format1 { coverage, .. })
+            | Self::Context(ContextSubstSubtable::Format2 { coverage, .. })
+            | Self::ChainedContext(ChainedContextSubstSubtable::Format1 { coverage, .. })
+            | Self::ChainedContext(ChainedContextSubstSubtable::Format2 { coverage, .. }) => {
+                Some(coverage)
+            }
+            Self::Context(ContextSubstSubtable::Format3 { coverages, .. }) => coverages.first(),
+            Self::ChainedContext(ChainedContextSubstSubtable::Format3 {
+                input_coverages, ..
+            }) => input_coverages.first(),
+            Self::Unsupported { .. } => None,
+        }
+    }
+}
+
+/// Single substitution subtable.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SingleSubstSubtable {
+    /// The substitute is the glyph ID plus `delta_glyph_id`, modulo 65536.
+    Format1 {
+        coverage: Coverage,
+        delta_glyph_id: i16,
+    },
+    /// The substitutes are listed explicitly, ordered by coverage index.
+    Format2 {
+        coverage: Coverage,
+        substitute_glyph_ids: Vec<u16>,
+    },
+}
+
+impl SingleSubstSubtable {
+    fn substitute(&self, glyph_id: u16) -> Option<u16> {
+        match self {
+            Self::Format1 {
+                coverage,
+                delta_glyph_id,
+            } => {
+                coverage.index_of(glyph_id)?;
+                Some(glyph_id.wrapping_add(*delta_glyph_id as u16))
+            }
+            Self::Format2 {
+                coverage,
+                substitute_glyph_ids,
+            } => substitute_glyph_ids
+                .get(coverage.index_of(glyph_id)?)
+                .copied(),
+        }
+    }
+}
+
+impl ReadBuffer for SingleSubstSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let format: u16 = buffer.get();
+        let coverage_offset: u16 = buffer.get();
+        match format {
+            1 => Self::Format1 {
+                delta_glyph_id: buffer.get(),
+                coverage: read_from(buffer, start, coverage_offset),
+            },
+            2 => {
+                let glyph_count: u16 = buffer.get();
+                Self::Format2 {
+                    substitute_glyph_ids: buffer.get_vec(glyph_count),
+                    coverage: read_from(buffer, start, coverage_offset),
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format2 {
+                    substitute_glyph_ids: Vec::new(),
+                    coverage: Coverage::default(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultipleSubstSubtable {
+    pub coverage: Coverage,
+    pub sequences: Vec<Vec<u16>>,
+}
+
+impl MultipleSubstSubtable {
+    fn substitute(&self, glyph_id: u16) -> Option<&Vec<u16>> {
+        let index = self.coverage.index_of(glyph_id)?;
+        self.sequences.get(index)
+    }
+}
+
+impl ReadBuffer for MultipleSubstSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let (coverage, sequences) = read_glyph_sequences(buffer);
+        Self {
+            coverage,
+            sequences,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlternateSubstSubtable {
+    pub coverage: Coverage,
+    pub alternate_sets: Vec<Vec<u16>>,
+}
+
+impl AlternateSubstSubtable {
+    fn alternates(&self, glyph_id: u16) -> Option<&Vec<u16>> {
+        let index = self.coverage.index_of(glyph_id)?;
+        self.alternate_sets.get(index)
+    }
+}
+
+impl ReadBuffer for AlternateSubstSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let (coverage, alternate_sets) = read_glyph_sequences(buffer);
+        Self {
+            coverage,
+            alternate_sets,
+        }
+    }
+}
+
+/// Ligature substitution subtable.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LigatureSubstSubtable {
+    /// Coverage of the first components of the ligatures.
+    pub coverage: Coverage,
+    /// Ligatures starting with each covered glyph, ordered by coverage index.
+    pub ligature_sets: Vec<Vec<Ligature>>,
+}
+
+impl LigatureSubstSubtable {
+    fn substitute(&self, sequence: &[u16]) -> Option<(u16, usize)> {
+        let (&first, rest) = sequence.split_first()?;
+        self.ligature_sets
+            .get(self.coverage.index_of(first)?)?
+            .iter()
+            .find(|ligature| rest.starts_with(&ligature.component_glyph_ids))
+            .map(|ligature| {
+                (
+                    ligature.ligature_glyph,
+                    ligature.component_glyph_ids.len() + 1,
+                )
+            })
+    }
+}
+
+impl ReadBuffer for LigatureSubstSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let _format: u16 = buffer.get();
+        let coverage_offset: u16 = buffer.get();
+        let ligature_set_count: u16 = buffer.get();
+        let ligature_set_offsets: Vec<u16> = buffer.get_vec(ligature_set_count);
+        let ligature_sets = ligature_set_offsets
+            .iter()
+            .map(|&offset| {
+                let ligature_set_start = start + offset as usize;
+                buffer.set_offset(ligature_set_start);
+                let ligature_count: u16 = buffer.get();
+                let ligature_offsets: Vec<u16> = buffer.get_vec(ligature_count);
+                ligature_offsets
+                    .iter()
+                    .map(|&offset| read_from(buffer, ligature_set_start, offset))
+                    .collect()
+            })
+            .collect();
+        Self {
+            coverage: read_from(buffer, start, coverage_offset),
+            ligature_sets,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ligature {
+    pub ligature_glyph: u16,
+    /// Components of the ligature, starting from the second one.
+    pub component_glyph_ids: Vec<u16>,
+}
+
+impl ReadBuffer for Ligature {
+    fn read(buffer: &mut Buffer) -> Self {
+        let ligature_glyph = buffer.get();
+        let component_count: u16 = buffer.get();
+        Self {
+            ligature_glyph,
+            component_glyph_ids: buffer.get_vec(component_count.saturating_sub(1)),
+        }
+    }
+}
+
+/// Read a coverage table and a list of glyph arrays. `MultipleSubstFormat1` and
+/// `AlternateSubstFormat1` share this layout.
+fn read_glyph_sequences(buffer: &mut Buffer) -> (Coverage, Vec<Vec<u16>>) {
+    let start = buffer.offset();
+    let _format: u16 = buffer.get();
+    let coverage_offset: u16 = buffer.get();
+    let count: u16 = buffer.get();
+    let offsets: Vec<u16> = buffer.get_vec(count);
+    let sequences = offsets
+        .iter()
+        .map(|&offset| {
+            buffer.set_offset_from(start, offset);
+            let glyph_count: u16 = buffer.get();
+            buffer.get_vec(glyph_count)
+        })
+        .collect();
+    buffer.set_offset_from(start, coverage_offset);
+    (buffer.get(), sequences)
+}
+
+/// Contextual substitution subtable (`SequenceContextFormat1/2/3`).
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContextSubstSubtable {
+    /// Simple glyph contexts.
+    Format1 {
+        coverage: Coverage,
+        rule_sets: Vec<Vec<SequenceRule>>,
+    },
+    /// Class-based glyph contexts.
+    Format2 {
+        coverage: Coverage,
+        class_def: ClassDef,
+        rule_sets: Vec<Vec<SequenceRule>>,
+    },
+    /// Coverage-based glyph contexts.
+    Format3 {
+        coverages: Vec<Coverage>,
+        subst_lookup_records: Vec<SubstLookupRecord>,
+    },
+}
+
+impl ContextSubstSubtable {
+    fn apply(&self, sequence: &[u16], position: usize) -> Option<&[SubstLookupRecord]> {
+        let glyph_id = *sequence.get(position)?;
+        let input = &sequence[position + 1..];
+        let rule = match self {
+            Self::Format1 {
+                coverage,
+                rule_sets,
+            } => rule_sets
+                .get(coverage.index_of(glyph_id)?)?
+                .iter()
+                .find(|rule| input.starts_with(&rule.input_sequence)),
+            Self::Format2 {
+                coverage,
+                class_def,
+                rule_sets,
+            } => {
+                coverage.index_of(glyph_id)?;
+                rule_sets
+                    .get(class_def.class(glyph_id) as usize)?
+                    .iter()
+                    .find(|rule| match_classes(class_def, input, &rule.input_sequence))
+            }
+            Self::Format3 {
+                coverages,
+                subst_lookup_records,
+            } => {
+                return match_coverages(coverages, &sequence[position..])
+                    .then_some(subst_lookup_records.as_slice());
+            }
+        };
+        rule.map(|rule| rule.subst_lookup_records.as_slice())
+    }
+}
+
+impl ReadBuffer for ContextSubstSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let format: u16 = buffer.get();
+        match format {
+            1 => {
+                let coverage_offset: u16 = buffer.get();
+                let rule_set_count: u16 = buffer.get();
+                let rule_set_offsets: Vec<u16> = buffer.get_vec(rule_set_count);
+                Self::Format1 {
+                    rule_sets: read_rule_sets(buffer, start, &rule_set_offsets),
+                    coverage: read_from(buffer, start, coverage_offset),
+                }
+            }
+            2 => {
+                let coverage_offset: u16 = buffer.get();
+                let class_def_offset: u16 = buffer.get();
+                let rule_set_count: u16 = buffer.get();
+                let rule_set_offsets: Vec<u16> = buffer.get_vec(rule_set_count);
+                Self::Format2 {
+                    rule_sets: read_rule_sets(buffer, start, &rule_set_offsets),
+                    coverage: read_from(buffer, start, coverage_offset),
+                    class_def: read_from(buffer, start, class_def_offset),
+                }
+            }
+            3 => {
+                let glyph_count: u16 = buffer.get();
+                let subst_count: u16 = buffer.get();
+                let coverage_offsets: Vec<u16> = buffer.get_vec(glyph_count);
+                let subst_lookup_records = buffer.get_vec(subst_count);
+                Self::Format3 {
+                    coverages: read_coverages(buffer, start, &coverage_offsets),
+                    subst_lookup_records,
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format3 {
+                    coverages: Vec::new(),
+                    subst_lookup_records: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+/// Chained contextual substitution subtable (`ChainedSequenceContextFormat1/2/3`).
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChainedContextSubstSubtable {
+    /// Simple glyph contexts.
+    Format1 {
+        coverage: Coverage,
+        rule_sets: Vec<Vec<ChainedSequenceRule>>,
+    },
+    /// Class-based glyph contexts.
+    Format2 {
+        coverage: Coverage,
+        backtrack_class_def: Option<ClassDef>,
+        input_class_def: ClassDef,
+        lookahead_class_def: Option<ClassDef>,
+        rule_sets: Vec<Vec<ChainedSequenceRule>>,
+    },
+    /// Coverage-based glyph contexts.
+    Format3 {
+        backtrack_coverages: Vec<Coverage>,
+        input_coverages: Vec<Coverage>,
+        lookahead_coverages: Vec<Coverage>,
+        subst_lookup_records: Vec<SubstLookupRecord>,
+    },
+}
+
+impl ChainedContextSubstSubtable {
+    fn apply(&self, sequence: &[u16], position: usize) -> Option<&[SubstLookupRecord]> {
+        let glyph_id = *sequence.get(position)?;
+        // The backtrack sequence is stored in reverse order.
+        let backtrack: Vec<u16> = sequence[..position].iter().rev().copied().collect();
+        let input = &sequence[position + 1..];
+        let rule = match self {
+            Self::Format1 {
+                coverage,
+                rule_sets,
+            } => rule_sets
+                .get(coverage.index_of(glyph_id)?)?
+                .iter()
+                .find(|rule| {
+                    backtrack.starts_with(&rule.backtrack_sequence)
+                        && input.starts_with(&rule.input_sequence)
+                        && input[rule.input_sequence.len()..].starts_with(&rule.lookahead_sequence)
+                }),
+            Self::Format2 {
+                coverage,
+                backtrack_class_def,
+                input_class_def,
+                lookahead_class_def,
+                rule_sets,
+            } => {
+                coverage.index_of(glyph_id)?;
+                let class = |class_def: &Option<ClassDef>, glyph_id| {
+                    class_def.as_ref().map_or(0, |c| c.class(glyph_id))
+                };
+                let match_optional_classes = |class_def, glyphs: &[u16], classes: &[u16]| {
+                    glyphs.len() >= classes.len()
+                        && glyphs
+                            .iter()
+                            .zip(classes)
+                            .all(|(&glyph_id, &c)| class(class_def, glyph_id) == c)
+                };
+                rule_sets
+                    .get(input_class_def.class(glyph_id) as usize)?
+                    .iter()
+                    .find(|rule| {
+                        match_optional_classes(
+                            backtrack_class_def,
+                            &backtrack,
+                            &rule.backtrack_sequence,
+                        ) && match_classes(input_class_def, input, &rule.input_sequence)
+                            && match_optional_classes(
+                                lookahead_class_def,
+                                &input[rule.input_sequence.len()..],
+                                &rule.lookahead_sequence,
+                            )
+                    })
+            }
+            Self::Format3 {
+                backtrack_coverages,
+                input_coverages,
+                lookahead_coverages,
+                subst_lookup_records,
+            } => {
+                let input = &sequence[position..];
+                let matched = match_coverages(backtrack_coverages, &backtrack)
+                    && match_coverages(input_coverages, input)
+                    && input.len() >= input_coverages.len()
+                    && match_coverages(lookahead_coverages, &input[input_coverages.len()..]);
+                return matched.then_some(subst_lookup_records.as_slice());
+            }
+        };
+        rule.map(|rule| rule.subst_lookup_records.as_slice())
+    }
+}
+
+impl ReadBuffer for ChainedContextSubstSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let format: u16 = buffer.get();
+        match format {
+            1 => {
+                let coverage_offset: u16 = buffer.get();
+                let rule_set_count: u16 = buffer.get();
+                let rule_set_offsets: Vec<u16> = buffer.get_vec(rule_set_count);
+                Self::Format1 {
+                    rule_sets: read_rule_sets(buffer, start, &rule_set_offsets),
+                    coverage: read_from(buffer, start, coverage_offset),
+                }
+            }
+            2 => {
+                let coverage_offset: u16 = buffer.get();
+                let backtrack_class_def_offset: u16 = buffer.get();
+                let input_class_def_offset: u16 = buffer.get();
+                let lookahead_class_def_offset: u16 = buffer.get();
+                let rule_set_count: u16 = buffer.get();
+                let rule_set_offsets: Vec<u16> = buffer.get_vec(rule_set_count);
+                Self::Format2 {
+                    rule_sets: read_rule_sets(buffer, start, &rule_set_offsets),
+                    coverage: read_from(buffer, start, coverage_offset),
+                    backtrack_class_def: buffer.get_or_none(start, backtrack_class_def_offset),
+                    input_class_def: read_from(buffer, start, input_class_def_offset),
+                    lookahead_class_def: buffer.get_or_none(start, lookahead_class_def_offset),
+                }
+            }
+            3 => {
+                let backtrack_count: u16 = buffer.get();
+                let backtrack_offsets: Vec<u16> = buffer.get_vec(backtrack_count);
+                let input_count: u16 = buffer.get();
+                let input_offsets: Vec<u16> = buffer.get_vec(input_count);
+                let lookahead_count: u16 = buffer.get();
+                let lookahead_offsets: Vec<u16> = buffer.get_vec(lookahead_count);
+                let subst_count: u16 = buffer.get();
+                let subst_lookup_records = buffer.get_vec(subst_count);
+                Self::Format3 {
+                    backtrack_coverages: read_coverages(buffer, start, &backtrack_offsets),
+                    input_coverages: read_coverages(buffer, start, &input_offsets),
+                    lookahead_coverages: read_coverages(buffer, start, &lookahead_offsets),
+                    subst_lookup_records,
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format3 {
+                    backtrack_coverages: Vec::new(),
+                    input_coverages: Vec::new(),
+                    lookahead_coverages: Vec::new(),
+                    subst_lookup_records: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+/// A rule of a contextual substitution subtable. In format 2 subtables, the input sequence
+/// contains classes rather than glyph IDs.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequenceRule {
+    /// The input sequence, starting from the second glyph.
+    pub input_sequence: Vec<u16>,
+    pub subst_lookup_records: Vec<SubstLookupRecord>,
+}
+
+impl ReadBuffer for SequenceRule {
+    fn read(buffer: &mut Buffer) -> Self {
+        let glyph_count: u16 = buffer.get();
+        let subst_count: u16 = buffer.get();
+        Self {
+            input_sequence: buffer.get_vec(glyph_count.saturating_sub(1)),
+            subst_lookup_records: buffer.get_vec(subst_count),
+        }
+    }
+}
+
+/// A rule of a chained contextual substitution subtable. In format 2 subtables, the
+/// sequences contain classes rather than glyph IDs.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainedSequenceRule {
+    /// The backtrack sequence, in reverse logical order.
+    pub backtrack_sequence: Vec<u16>,
+    /// The input sequence, starting from the second glyph.
+    pub input_sequence: Vec<u16>,
+    pub lookahead_sequence: Vec<u16>,
+    pub subst_lookup_records: Vec<SubstLookupRecord>,
+}
+
+impl ReadBuffer for ChainedSequenceRule {
+    fn read(buffer: &mut Buffer) -> Self {
+        let backtrack_count: u16 = buffer.get();
+        let backtrack_sequence = buffer.get_vec(backtrack_count);
+        let input_count: u16 = buffer.get();
+        let input_sequence = buffer.get_vec(input_count.saturating_sub(1));
+        let lookahead_count: u16 = buffer.get();
+        let lookahead_sequence = buffer.get_vec(lookahead_count);
+        let subst_count: u16 = buffer.get();
+        Self {
+            backtrack_sequence,
+            input_sequence,
+            lookahead_sequence,
+            subst_lookup_records: buffer.get_vec(subst_count),
+        }
+    }
+}
+
+/// Reverse chaining contextual single substitution subtable (`ReverseChainSingleSubstFormat1`).
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReverseChainSubstSubtable {
+    pub coverage: Coverage,
+    /// Coverages of the backtrack sequence, in reverse logical order.
+    pub backtrack_coverages: Vec<Coverage>,
+    pub lookahead_coverages: Vec<Coverage>,
+    /// Substitutes of the glyphs, ordered by coverage index.
+    pub substitute_glyph_ids: Vec<u16>,
+}
+
+impl ReverseChainSubstSubtable {
+    fn substitute(&self, glyph_id: u16, backtrack: &[u16], lookahead: &[u16]) -> Option<u16> {
+        let index = self.coverage.index_of(glyph_id)?;
+        if match_coverages(&self.backtrack_coverages, backtrack)
+            && match_coverages(&self.lookahead_coverages, lookahead)
+        {
+            self.substitute_glyph_ids.get(index).copied()
+        } else {
+            None
+        }
+    }
+}
+
+impl ReadBuffer for ReverseChainSubstSubtable {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let _format: u16 = buffer.get();
+        let coverage_offset: u16 = buffer.get();
+        let backtrack_count: u16 = buffer.get();
+        let backtrack_offsets: Vec<u16> = buffer.get_vec(backtrack_count);
+        let lookahead_count: u16 = buffer.get();
+        let lookahead_offsets: Vec<u16> = buffer.get_vec(lookahead_count);
+        let glyph_count: u16 = buffer.get();
+        let substitute_glyph_ids = buffer.get_vec(glyph_count);
+        Self {
+            coverage: read_from(buffer, start, coverage_offset),
+            backtrack_coverages: read_coverages(buffer, start, &backtrack_offsets),
+            lookahead_coverages: read_coverages(buffer, start, &lookahead_offsets),
+            substitute_glyph_ids,
+        }
+    }
+}
+
+/// Apply the lookup at `lookup_list_index` to the glyph at `glyph_sequence_index` in the
+/// input sequence.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubstLookupRecord {
+    pub glyph_sequence_index: u16,
+    pub lookup_list_index: u16,
+}
+
+fn match_classes(class_def: &ClassDef, glyphs: &[u16], classes: &[u16]) -> bool {
+    glyphs.len() >= classes.len()
+        && glyphs
+            .iter()
+            .zip(classes)
+            .all(|(&glyph_id, &class)| class_def.class(glyph_id) == class)
+}
+
+fn match_coverages(coverages: &[Coverage], glyphs: &[u16]) -> bool {
+    glyphs.len() >= coverages.len()
+        && glyphs
+            .iter()
+            .zip(coverages)
+            .all(|(&glyph_id, coverage)| coverage.contains(glyph_id))
+}
+
+pub(crate) fn read_from<T: ReadBuffer>(buffer: &mut Buffer, start: usize, offset: u16) -> T {
+    buffer.set_offset_from(start, offset);
+    buffer.get()
+}
+
+fn read_coverages(buffer: &mut Buffer, start: usize, offsets: &[u16]) -> Vec<Coverage> {
+    offsets
+        .iter()
+        .map(|&offset| read_from(buffer, start, offset))
+        .collect()
+}
+
+/// Read the rule sets of a (chained) sequence context subtable. NULL rule sets are empty.
+fn read_rule_sets<T: ReadBuffer>(
+    buffer: &mut Buffer,
+    start: usize,
+    offsets: &[u16],
+) -> Vec<Vec<T>> {
+    offsets
+        .iter()
+        .map(|&offset| {
+            if offset == 0 {
+                return Vec::new();
+            }
+            let rule_set_start = start + offset as usize;
+            buffer.set_offset(rule_set_start);
+            let rule_count: u16 = buffer.get();
+            let rule_offsets: Vec<u16> = buffer.get_vec(rule_count);
+            rule_offsets
+                .iter()
+                .map(|&offset| read_from(buffer, rule_set_start, offset))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_feature_variations() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 0, 0, 0, 0, 1, // version 1.0, 1 record
+        0, 0, 0, 16, 0, 0, 0, 30, // condition set, feature table substitution
+        0, 1, 0, 0, 0, 6, // 1 condition
+        0, 1, 0, 0, 0x20, 0, 0x40, 0, // axis 0 in [0.5, 1.0]
+        0, 1, 0, 0, 0, 1, // version 1.0, 1 substitution
+        0, 0, 0, 0, 0, 12, // feature 0
+        0, 0, 0, 1, 0, 5, // lookup 5
+    ]);
+    let feature_variations: FeatureVariations = buffer.get();
+    let substitutions = feature_variations.substitutions(&[0.75]).unwrap();
+    assert_eq!(substitutions[0].feature_index, 0);
+    assert_eq!(substitutions[0].alternate_feature.lookup_list_indices, [5]);
+    assert!(feature_variations.substitutions(&[0.25]).is_none());
+    assert!(feature_variations.substitutions(&[]).is_none());
+}
+
+#[test]
+fn test_active_features() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 0, 0, 0, 0, 1, // version 1.0, 1 record
+        0, 0, 0, 16, 0, 0, 0, 30, // condition set, feature table substitution
+        0, 1, 0, 0, 0, 6, // 1 condition
+        0, 1, 0, 1, 0x20, 0, 0x40, 0, // axis 1 in [0.5, 1.0]
+        0, 1, 0, 0, 0, 1, // version 1.0, 1 substitution
+        0, 0, 0, 0, 0, 12, // feature 0
+        0, 0, 0, 1, 0, 5, // lookup 5
+    ]);
+    let feature_variations = Some(buffer.get());
+    let feature_list = [FeatureRecord {
+        feature_tag: Tag::new(b"rvrn"),
+        feature: Feature {
+            feature_params_offset: 0,
+            lookup_list_indices: Vec::new(),
+        },
+        feature_offset: 0,
+    }];
+    // Axes `wdth` and `wght`, both from 100 to 900
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 0, 0, 16, 0, 2, 0, 2, 0, 20, 0, 0, 0, 8,
+        b'w', b'd', b't', b'h', 0, 100, 0, 0, 1, 144, 0, 0, 3, 132, 0, 0, 0, 0, 1, 0,
+        b'w', b'g', b'h', b't', 0, 100, 0, 0, 1, 144, 0, 0, 3, 132, 0, 0, 0, 0, 1, 1,
+    ]);
+    let mut font = Font::default();
+    font.parse_fvar(&mut buffer).unwrap();
+
+    let active = |coords: &[(&[u8; 4], f32)], fvar| {
+        let coords = coords.iter().map(|&(tag, x)| (Tag::new(tag), x)).collect();
+        active_features(&feature_list, &feature_variations, &coords, fvar)
+    };
+    let rvrn = vec![Tag::new(b"rvrn")];
+    assert_eq!(active(&[(b"wght", 0.75)], font.fvar.as_ref()), rvrn);
+    assert_eq!(
+        active(&[(b"wdth", 0.0), (b"wght", 1.0)], font.fvar.as_ref()),
+        rvrn
+    );
+    assert!(active(&[(b"wdth", 0.75)], font.fvar.as_ref()).is_empty());
+    assert!(active(&[], font.fvar.as_ref()).is_empty());
+    assert!(active(&[(b"wght", 0.75)], None).is_empty());
+}
+
+#[test]
+fn test_lookup_flags() {
+    assert_eq!(LookupFlags::from(0), LookupFlags::default());
+    let flags = LookupFlags::from(0x0312);
+    assert!(flags.ignore_base_glyphs);
+    assert!(flags.use_mark_filtering_set);
+    assert!(!flags.right_to_left && !flags.ignore_ligatures && !flags.ignore_marks);
+    assert_eq!(flags.mark_attachment_type, 3);
+}
+
+#[test]
+fn test_single_subst_delta_wraps() {
+    // Format 1 with delta -2, covering glyph 1
+    let mut buffer = Buffer::new(vec![0, 1, 0, 6, 0xFF, 0xFE, 0, 1, 0, 1, 0, 1]);
+    let subtable: SingleSubstSubtable = buffer.get();
+    assert_eq!(subtable.substitute(1), Some(0xFFFF));
+    assert_eq!(subtable.substitute(2), None);
+}
+
+#[test]
+fn test_ligature_subst() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 8, 0, 1, 0, 14, // format 1, coverage, 1 ligature set
+        0, 1, 0, 1, 0, 10, // coverage: glyph 10
+        0, 2, 0, 6, 0, 14, // 2 ligatures
+        0, 100, 0, 3, 0, 11, 0, 12, // 10 11 12 => 100
+        0, 101, 0, 2, 0, 11, // 10 11 => 101
+    ]);
+    let subtable: LigatureSubstSubtable = buffer.get();
+    assert_eq!(subtable.substitute(&[10, 11, 12, 13]), Some((100, 3)));
+    assert_eq!(subtable.substitute(&[10, 11, 13]), Some((101, 2)));
+    assert_eq!(subtable.substitute(&[10]), None);
+    assert_eq!(subtable.substitute(&[11, 12]), None);
+}
+
+#[test]
+fn test_chained_context_subst_format_3() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 3, // format 3
+        0, 1, 0, 22, // 1 backtrack coverage
+        0, 2, 0, 28, 0, 36, // 2 input coverages
+        0, 1, 0, 42, // 1 lookahead coverage
+        0, 1, 0, 1, 0, 5, // apply lookup 5 to the second input glyph
+        0, 1, 0, 1, 0, 1, // backtrack coverage: glyph 1
+        0, 1, 0, 2, 0, 2, 0, 12, // input coverage: glyphs 2 and 12
+        0, 1, 0, 1, 0, 3, // input coverage: glyph 3
+        0, 1, 0, 1, 0, 4, // lookahead coverage: glyph 4
+    ]);
+    let subtable: ChainedContextSubstSubtable = buffer.get();
+    let records = [SubstLookupRecord {
+        glyph_sequence_index: 1,
+        lookup_list_index: 5,
+    }];
+    let apply = |sequence: &[u16], position| subtable.apply(sequence, position);
+    // Hits, where only the glyph right before the input sequence is the backtrack
+    assert_eq!(apply(&[1, 2, 3, 4], 1), Some(&records[..]));
+    assert_eq!(apply(&[1, 12, 3, 4], 1), Some(&records[..]));
+    assert_eq!(apply(&[7, 1, 2, 3, 4, 8], 2), Some(&records[..]));
+    // Backtrack misses
+    assert_eq!(apply(&[9, 2, 3, 4], 1), None);
+    assert_eq!(apply(&[1, 9, 2, 3, 4], 2), None);
+    assert_eq!(apply(&[2, 3, 4], 0), None);
+    // Input misses
+    assert_eq!(apply(&[1, 3, 3, 4], 1), None);
+    assert_eq!(apply(&[1, 2, 12, 4], 1), None);
+    assert_eq!(apply(&[1, 2], 1), None);
+    // Lookahead misses
+    assert_eq!(apply(&[1, 2, 3, 9], 1), None);
+    assert_eq!(apply(&[1, 2, 3], 1), None);
 }
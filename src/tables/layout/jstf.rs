@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::{Buffer, ReadBuffer};
@@ -20,7 +21,7 @@ pub struct Table_JSTF {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_JSTF(&mut self, buffer: &mut Buffer) {
+    pub fn parse_JSTF(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let jstf_start = buffer.offset();
         let version = buffer.get_version::<u16>();
         let jstf_script_count: u16 = buffer.get();
@@ -33,6 +34,7 @@ impl Font {
             version,
             jstf_script_records,
         });
+        Ok(())
     }
 }
 
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
@@ -16,6 +17,7 @@ use read_buffer_derive::ReadBuffer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_MATH {
     version: String,
     pub math_constants: MathConstants,
@@ -25,7 +27,7 @@ pub struct Table_MATH {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_MATH(&mut self, buffer: &mut Buffer) {
+    pub fn parse_MATH(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let math_start = buffer.offset();
         let version = buffer.get_version::<u16>();
         let math_constants_offset: u16 = buffer.get();
@@ -47,10 +49,44 @@ impl Font {
             math_glyph_info,
             math_variants,
         });
+        Ok(())
     }
 }
 
+impl Table_MATH {
+    /// Return the math kern value of `glyph_id` at `corner` for the given correction height,
+    /// or `None` if the glyph has no math kern at that corner.
+    pub fn kern_value(
+        &self,
+        glyph_id: u16,
+        corner: KernCorner,
+        correction_height: i16,
+    ) -> Option<i16> {
+        let math_kern_info = self.math_glyph_info.math_kern_info.as_ref()?;
+        let index = math_kern_info.math_kern_coverage.index_of(glyph_id)?;
+        let record = math_kern_info.math_kern.get(index)?;
+        let math_kern = match corner {
+            KernCorner::TopRight => &record.top_right_math_kern,
+            KernCorner::TopLeft => &record.top_left_math_kern,
+            KernCorner::BottomRight => &record.bottom_right_math_kern,
+            KernCorner::BottomLeft => &record.bottom_left_math_kern,
+        };
+        math_kern.as_ref()?.kern_value(correction_height)
+    }
+}
+
+/// Corners of a glyph with math kerning.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KernCorner {
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathConstants {
     pub script_percent_scale_down: i16,
     pub script_script_percent_scale_down: i16,
@@ -111,11 +147,36 @@ pub struct MathConstants {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathGlyphInfo {
-    pub math_italics_correction_info: MathItalicsCorrectionInfo,
-    pub math_top_accent_attachment: MathTopAccentAttachment,
-    pub extended_shape_coverage: Coverage,
-    pub math_kern_info: MathKernInfo,
+    pub math_italics_correction_info: Option<MathItalicsCorrectionInfo>,
+    pub math_top_accent_attachment: Option<MathTopAccentAttachment>,
+    pub extended_shape_coverage: Option<Coverage>,
+    pub math_kern_info: Option<MathKernInfo>,
+}
+
+impl MathGlyphInfo {
+    /// Return the italics correction of `glyph_id`, or `None` if it has no such value.
+    pub fn italics_correction(&self, glyph_id: u16) -> Option<i16> {
+        self.math_italics_correction_info
+            .as_ref()?
+            .italics_correction(glyph_id)
+    }
+
+    /// Return the top accent attachment of `glyph_id`, or `None` if it has no such value.
+    pub fn top_accent_attachment(&self, glyph_id: u16) -> Option<i16> {
+        self.math_top_accent_attachment
+            .as_ref()?
+            .top_accent_attachment(glyph_id)
+    }
+
+    /// Whether `glyph_id` is an extended shape, i.e. a glyph that is used as a base of a
+    /// tall shape (typically stretched vertically), like large operators and delimiters.
+    pub fn is_extended_shape(&self, glyph_id: u16) -> bool {
+        self.extended_shape_coverage
+            .as_ref()
+            .is_some_and(|coverage| coverage.contains(glyph_id))
+    }
 }
 
 impl ReadBuffer for MathGlyphInfo {
@@ -126,28 +187,33 @@ impl ReadBuffer for MathGlyphInfo {
         let extended_shape_coverage_offset: u16 = buffer.get();
         let math_kern_info_offset: u16 = buffer.get();
 
-        macro_rules! _get {
-            ($offset:expr) => {{
-                buffer.set_offset_from(start, $offset);
-                buffer.get()
-            }};
-        }
-
+        // All of the offsets may be NULL.
         Self {
-            math_italics_correction_info: _get!(math_italics_correction_info_offset),
-            math_top_accent_attachment: _get!(math_top_accent_attachment_offset),
-            extended_shape_coverage: _get!(extended_shape_coverage_offset),
-            math_kern_info: _get!(math_kern_info_offset),
+            math_italics_correction_info: buffer
+                .get_or_none(start, math_italics_correction_info_offset),
+            math_top_accent_attachment: buffer
+                .get_or_none(start, math_top_accent_attachment_offset),
+            extended_shape_coverage: buffer.get_or_none(start, extended_shape_coverage_offset),
+            math_kern_info: buffer.get_or_none(start, math_kern_info_offset),
         }
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathItalicsCorrectionInfo {
     pub italics_correction_coverage: Coverage,
     pub italics_correction: Vec<MathValueRecord>,
 }
 
+impl MathItalicsCorrectionInfo {
+    /// Return the italics correction of `glyph_id`, or `None` if the glyph is not covered.
+    pub fn italics_correction(&self, glyph_id: u16) -> Option<i16> {
+        let index = self.italics_correction_coverage.index_of(glyph_id)?;
+        self.italics_correction.get(index).map(|rec| rec.value)
+    }
+}
+
 impl ReadBuffer for MathItalicsCorrectionInfo {
     fn read(buffer: &mut Buffer) -> Self {
         let start = buffer.offset();
@@ -164,11 +230,21 @@ impl ReadBuffer for MathItalicsCorrectionInfo {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathTopAccentAttachment {
     pub top_accent_attachment_coverage: Coverage,
     pub top_accent_attachment: Vec<MathValueRecord>,
 }
 
+impl MathTopAccentAttachment {
+    /// Return the horizontal position of the top accent attachment point of `glyph_id`, or
+    /// `None` if the glyph is not covered.
+    pub fn top_accent_attachment(&self, glyph_id: u16) -> Option<i16> {
+        let index = self.top_accent_attachment_coverage.index_of(glyph_id)?;
+        self.top_accent_attachment.get(index).map(|rec| rec.value)
+    }
+}
+
 impl ReadBuffer for MathTopAccentAttachment {
     fn read(buffer: &mut Buffer) -> Self {
         let start = buffer.offset();
@@ -185,6 +261,7 @@ impl ReadBuffer for MathTopAccentAttachment {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathKernInfo {
     pub math_kern_coverage: Coverage,
     pub math_kern: Vec<MathKernInfoRecord>,
@@ -197,14 +274,11 @@ impl ReadBuffer for MathKernInfo {
         let math_kern_count: u16 = buffer.get();
         let mut math_kern: Vec<MathKernInfoRecord> = buffer.get_vec(math_kern_count);
         math_kern.iter_mut().for_each(|rec| {
-            rec.top_right_math_kern =
-                buffer.get_or_none(start, rec.top_right_math_kern_offset);
-            rec.top_left_math_kern =
-                buffer.get_or_none(start, rec.top_left_math_kern_offset);
+            rec.top_right_math_kern = buffer.get_or_none(start, rec.top_right_math_kern_offset);
+            rec.top_left_math_kern = buffer.get_or_none(start, rec.top_left_math_kern_offset);
             rec.bottom_right_math_kern =
                 buffer.get_or_none(start, rec.bottom_right_math_kern_offset);
-            rec.bottom_left_math_kern =
-                buffer.get_or_none(start, rec.bottom_left_math_kern_offset);
+            rec.bottom_left_math_kern = buffer.get_or_none(start, rec.bottom_left_math_kern_offset);
         });
         buffer.set_offset_from(start, math_kern_coverage_offset);
         let math_kern_coverage = buffer.get();
@@ -215,11 +289,16 @@ impl ReadBuffer for MathKernInfo {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathKernInfoRecord {
+    #[read_buffer(default)]
     pub top_right_math_kern: Option<MathKern>,
+    #[read_buffer(default)]
     pub top_left_math_kern: Option<MathKern>,
+    #[read_buffer(default)]
     pub bottom_right_math_kern: Option<MathKern>,
+    #[read_buffer(default)]
     pub bottom_left_math_kern: Option<MathKern>,
     top_right_math_kern_offset: u16,
     top_left_math_kern_offset: u16,
@@ -227,25 +306,53 @@ pub struct MathKernInfoRecord {
     bottom_left_math_kern_offset: u16,
 }
 
-impl ReadBuffer for MathKernInfoRecord {
-    fn read(buffer: &mut Buffer) -> Self {
-        Self {
-            top_right_math_kern_offset: buffer.get(),
-            top_left_math_kern_offset: buffer.get(),
-            bottom_right_math_kern_offset: buffer.get(),
-            bottom_left_math_kern_offset: buffer.get(),
-            ..Default::default()
-        }
-    }
-}
-
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathKern {
     pub height_count: u16,
     pub correction_height: Vec<MathValueRecord>,
     pub kern_values: Vec<MathValueRecord>,
 }
 
+impl MathKern {
+    /// Return the kern value at `correction_height`. The correction heights split the
+    /// vertical axis into `height_count + 1` intervals, each with its own kern value;
+    /// a height equal to a boundary belongs to the lower interval. Return `None` if there
+    /// is no kern value for that interval.
+    pub fn kern_value(&self, correction_height: i16) -> Option<i16> {
+        let i = self
+            .correction_height
+            .partition_point(|h| h.value < correction_height);
+        self.kern_values.get(i).map(|k| k.value)
+    }
+
+    /// Return the kern value at `correction_height`, interpolated linearly between the
+    /// kern values at the neighbouring correction heights. Heights out of the range of
+    /// `correction_height` use the first or the last kern value.
+    ///
+    /// **Note:** The specification defines a step function (see [`MathKern::kern_value`]),
+    /// which is what [`Table_MATH::kern_value`] uses. This is only a smoothed approximation.
+    pub fn kern_at_height(&self, correction_height: i16) -> i16 {
+        let heights = &self.correction_height;
+        let n = heights.len();
+        if n == 0 || correction_height <= heights[0].value {
+            return self.kern_values[0].value;
+        }
+        if correction_height >= heights[n - 1].value {
+            return self.kern_values[n].value;
+        }
+        // Now `heights[i - 1] < correction_height <= heights[i]`, with `0 < i < n`.
+        let i = heights.partition_point(|h| h.value < correction_height);
+        let (h0, h1) = (heights[i - 1].value as i32, heights[i].value as i32);
+        let (k0, k1) = (
+            self.kern_values[i - 1].value as i32,
+            self.kern_values[i].value as i32,
+        );
+        let h = correction_height as i32;
+        (k0 + (k1 - k0) * (h - h0) / (h1 - h0)) as i16
+    }
+}
+
 impl ReadBuffer for MathKern {
     fn read(buffer: &mut Buffer) -> Self {
         let height_count = buffer.get();
@@ -260,6 +367,7 @@ impl ReadBuffer for MathKern {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathVariants {
     pub min_connector_overlap: u16,
     pub vert_glyph_coverage: Coverage,
@@ -311,6 +419,7 @@ impl ReadBuffer for MathVariants {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathGlyphConstruction {
     pub glyph_assembly: Option<GlyphAssembly>,
     pub math_glyph_variant_records: Vec<MathGlyphVariantRecord>,
@@ -331,6 +440,7 @@ impl ReadBuffer for MathGlyphConstruction {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlyphAssembly {
     pub italics_correction: MathValueRecord,
     pub part_records: Vec<GlyphPartRecord>,
@@ -349,6 +459,7 @@ impl ReadBuffer for GlyphAssembly {
 }
 
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlyphPartRecord {
     pub glyph_id: u16,
     pub start_connector_length: u16,
@@ -358,6 +469,7 @@ pub struct GlyphPartRecord {
 }
 
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathGlyphVariantRecord {
     pub variant_glyph: u16,
     pub advance_measurement: u16,
@@ -366,6 +478,7 @@ pub struct MathGlyphVariantRecord {
 // Shared Formats
 
 #[derive(ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathValueRecord {
     pub value: i16,
     device_offset: u16,
@@ -380,42 +493,128 @@ impl fmt::Debug for MathValueRecord {
     }
 }
 
-#[derive(Debug)]
+/// Coverage table, which lists the glyphs covered by a lookup or a subtable. The coverage
+/// index of a glyph is its position in the sorted list.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coverage {
     format: u16,
-    pub glyph_array: Vec<u16>,
+    /// Covered glyphs in format 1. Use [`Coverage::glyphs`] for all the formats.
+    glyph_array: Vec<u16>,
+    /// Ranges of covered glyphs in format 2, which are kept as is rather than expanded.
+    range_records: Vec<RangeRecord>,
+}
+
+impl Coverage {
+    /// Return whether `glyph_id` is covered.
+    pub fn contains(&self, glyph_id: u16) -> bool {
+        self.index_of(glyph_id).is_some()
+    }
+
+    /// Return the coverage index of `glyph_id`. Glyphs and ranges are binary searched, as
+    /// they are sorted by glyph ID.
+    pub fn index_of(&self, glyph_id: u16) -> Option<usize> {
+        match self.format {
+            1 => self.glyph_array.binary_search(&glyph_id).ok(),
+            2 => {
+                let i = self
+                    .range_records
+                    .partition_point(|rec| rec.end_glyph_id < glyph_id);
+                let rec = self.range_records.get(i)?;
+                // `glyph_id <= end_glyph_id` here, but the range may be reversed.
+                if (rec.start_glyph_id..=rec.end_glyph_id).contains(&glyph_id) {
+                    let offset = (glyph_id - rec.start_glyph_id) as usize;
+                    Some(rec.start_coverage_index as usize + offset)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the covered glyphs, ordered by coverage index.
+    pub fn glyphs(&self) -> Vec<u16> {
+        match self.format {
+            2 => self
+                .range_records
+                .iter()
+                .flat_map(|rec| rec.start_glyph_id..=rec.end_glyph_id)
+                .collect(),
+            _ => self.glyph_array.clone(),
+        }
+    }
 }
 
 impl ReadBuffer for Coverage {
     fn read(buffer: &mut Buffer) -> Self {
         let format = buffer.get();
-        let glyph_array = match format {
+        let mut coverage = Self {
+            format,
+            ..Default::default()
+        };
+        match format {
             1 => {
                 let glyph_count: u16 = buffer.get();
-                buffer.get_vec(glyph_count)
+                coverage.glyph_array = buffer.get_vec(glyph_count);
             }
             2 => {
                 let range_count: u16 = buffer.get();
-                let range_records: Vec<RangeRecord> = buffer.get_vec(range_count);
-                let mut array = Vec::new();
-                range_records.iter().for_each(|rec| {
-                    (rec.start_glyph_id..=rec.end_glyph_id).for_each(|id| array.push(id));
-                });
-                array
+                coverage.range_records = buffer.get_vec(range_count);
             }
-            _ => unreachable!(),
-        };
-        Self {
-            format,
-            glyph_array,
+            _ => buffer.set_unknown_format(format),
         }
+        coverage
     }
 }
 
-#[derive(ReadBuffer)]
+#[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct RangeRecord {
     start_glyph_id: u16,
     end_glyph_id: u16,
-    // TODO:
-    _start_coverage_index: u16,
+    start_coverage_index: u16,
+}
+
+#[test]
+fn test_math_kern_at_height() {
+    // heightCount = 2, correctionHeight = [100, 200], kernValues = [10, 20, 40]
+    let mut buffer = Buffer::new(vec![
+        0, 2, //
+        0, 100, 0, 0, 0, 200, 0, 0, //
+        0, 10, 0, 0, 0, 20, 0, 0, 0, 40, 0, 0,
+    ]);
+    let math_kern: MathKern = buffer.get();
+    assert_eq!(math_kern.kern_at_height(-50), 10);
+    assert_eq!(math_kern.kern_at_height(100), 10);
+    assert_eq!(math_kern.kern_at_height(150), 15);
+    assert_eq!(math_kern.kern_at_height(200), 40);
+    assert_eq!(math_kern.kern_at_height(300), 40);
+    assert_eq!(math_kern.kern_value(150), Some(20));
+    assert_eq!(math_kern.kern_value(300), Some(40));
+
+    let mut truncated = math_kern;
+    truncated.kern_values.truncate(2);
+    assert_eq!(truncated.kern_value(150), Some(20));
+    assert_eq!(truncated.kern_value(300), None);
+}
+
+#[test]
+fn test_coverage_ranges() {
+    // Format 2: glyphs 10-12 and 20-21
+    let mut buffer = Buffer::new(vec![0, 2, 0, 2, 0, 10, 0, 12, 0, 0, 0, 20, 0, 21, 0, 3]);
+    let coverage: Coverage = buffer.get();
+    assert_eq!(coverage.index_of(10), Some(0));
+    assert_eq!(coverage.index_of(12), Some(2));
+    assert_eq!(coverage.index_of(21), Some(4));
+    assert_eq!(coverage.index_of(15), None);
+    assert!(!coverage.contains(9) && !coverage.contains(22));
+    assert!(coverage.glyph_array.is_empty());
+    assert_eq!(coverage.glyphs(), vec![10, 11, 12, 20, 21]);
+
+    // The coverage index exceeds u16
+    let mut buffer = Buffer::new(vec![0, 2, 0, 1, 0, 10, 0, 20, 0xFF, 0xFF]);
+    let coverage: Coverage = buffer.get();
+    assert_eq!(coverage.index_of(10), Some(0xFFFF));
+    assert_eq!(coverage.index_of(20), Some(0xFFFF + 10));
 }
@@ -1,4 +1,6 @@
+use crate::error::FontError;
 use crate::font::Font;
+use crate::types::Tag;
 use crate::util::{Buffer, ReadBuffer};
 
 /// ## `DSIG` &mdash; Digital Signature Table
@@ -21,7 +23,7 @@ pub struct Table_DSIG {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_DSIG(&mut self, buffer: &mut Buffer) {
+    pub fn parse_DSIG(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let dsig_start = buffer.offset();
         let version = buffer.get();
         let num_signatures = buffer.get();
@@ -29,21 +31,48 @@ impl Font {
         let mut signature_records: Vec<SignatureRecord> = buffer.get_vec(num_signatures);
         signature_records
             .iter_mut()
-            .for_each(|rec| match rec.format {
+            .try_for_each(|rec| match rec.format {
                 1 => {
                     buffer.set_offset_from(dsig_start, rec.signature_block_offset);
                     buffer.skip::<u16>(2);
                     let signature_length: u32 = buffer.get();
                     rec.signature = buffer.get_vec(signature_length);
+                    Ok(())
                 }
-                _ => unreachable!(),
-            });
+                format => Err(FontError::UnknownTableFormat {
+                    tag: Tag::new(b"DSIG"),
+                    format: format as u16,
+                }),
+            })?;
         self.DSIG = Some(Table_DSIG {
             version,
             num_signatures,
             flags,
             signature_records,
         });
+        Ok(())
+    }
+}
+
+impl Table_DSIG {
+    /// Return `true` if the table carries at least one signature.
+    ///
+    /// Note that the signatures are **not** verified. A full verification would need to:
+    ///
+    /// 1. decode each format 1 signature block as a PKCS#7 `SignedData` structure;
+    /// 2. recompute the font digest, with the `DSIG` table excluded and the `checksumAdjustment`
+    ///    of `head` zeroed (or the collection header hashed for a TTC), and compare it to the
+    ///    digest in the `SignedData`;
+    /// 3. check the signer's certificate chain against a set of trusted roots.
+    ///
+    /// A font that merely contains a `DSIG` table should therefore not be trusted.
+    pub fn is_present(&self) -> bool {
+        !self.signature_records.is_empty()
+    }
+
+    /// Return the number of signature records in the table.
+    pub fn signer_count(&self) -> usize {
+        self.signature_records.len()
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::Buffer;
 
@@ -25,7 +26,7 @@ pub struct Table_LTSH {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_LTSH(&mut self, buffer: &mut Buffer) {
+    pub fn parse_LTSH(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let version = buffer.get();
         let num_glyphs = buffer.get();
         let y_pels = buffer.get_vec(num_glyphs);
@@ -34,5 +35,6 @@ impl Font {
             num_glyphs,
             y_pels,
         });
+        Ok(())
     }
 }
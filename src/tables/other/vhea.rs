@@ -0,0 +1,59 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::types::Fixed;
+use crate::util::Buffer;
+
+/// ## `vhea` &mdash; Vertical Header Table
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/vhea>.
+///
+/// The vertical header table (tag name: `vhea`) contains information needed for
+/// vertical fonts. The glyphs of vertical fonts are written either top to bottom
+/// or bottom to top. This table contains information that is general to the
+/// font as a whole. Information that pertains to specific glyphs is given in
+/// the vertical metrics table (tag name: `vmtx`).
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+pub struct Table_vhea {
+    version: Fixed,
+    /// Named `vertTypoAscender` since version 1.1.
+    pub ascent: i16,
+    /// Named `vertTypoDescender` since version 1.1.
+    pub descent: i16,
+    /// Named `vertTypoLineGap` since version 1.1.
+    pub line_gap: i16,
+    pub advance_height_max: i16,
+    pub min_top_side_bearing: i16,
+    pub min_bottom_side_bearing: i16,
+    pub y_max_extent: i16,
+    pub caret_slope_rise: i16,
+    pub caret_slope_run: i16,
+    pub caret_offset: i16,
+    // Reserved 4 * `int16` here.
+    pub metric_data_format: i16,
+    pub num_of_long_ver_metrics: u16,
+}
+
+impl Font {
+    pub fn parse_vhea(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        self.vhea = Some(Table_vhea {
+            version: buffer.get(),
+            ascent: buffer.get(),
+            descent: buffer.get(),
+            line_gap: buffer.get(),
+            advance_height_max: buffer.get(),
+            min_top_side_bearing: buffer.get(),
+            min_bottom_side_bearing: buffer.get(),
+            y_max_extent: buffer.get(),
+            caret_slope_rise: buffer.get(),
+            caret_slope_run: buffer.get(),
+            caret_offset: buffer.get(),
+            metric_data_format: {
+                buffer.skip::<i16>(4);
+                buffer.get()
+            },
+            num_of_long_ver_metrics: buffer.get(),
+        });
+        Ok(())
+    }
+}
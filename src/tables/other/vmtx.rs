@@ -0,0 +1,107 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::types::Tag;
+use crate::util::{Buffer, ReadBuffer};
+use read_buffer_derive::ReadBuffer;
+
+/// ## `vmtx` &mdash; Vertical Metrics Table
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/vmtx>.
+///
+/// The vertical metrics table allows you to specify the vertical spacing for
+/// each glyph in a vertical font. This table consists of either one or two
+/// arrays that contain metric information (the advance heights and top
+/// sidebearings) for the vertical layout of each of the glyphs in the font.
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+pub struct Table_vmtx {
+    pub ver_metrics: Vec<LongVerMetric>,
+    pub top_side_bearings: Vec<i16>,
+}
+
+impl Font {
+    pub fn parse_vmtx(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let num_ver_metrics = self
+            .vhea
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"vhea")))?
+            .num_of_long_ver_metrics;
+        let num_glyphs = self
+            .maxp
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"maxp")))?
+            .num_glyphs;
+        self.vmtx = Some(Table_vmtx {
+            ver_metrics: buffer.get_vec(num_ver_metrics),
+            top_side_bearings: buffer.get_vec(num_glyphs.saturating_sub(num_ver_metrics)),
+        });
+        Ok(())
+    }
+
+    /// Return the advance height of `glyph_id`, or `None` if `vmtx` is not parsed or the
+    /// glyph is out of range.
+    pub fn advance_height(&self, glyph_id: usize) -> Option<u16> {
+        Some(self.ver_metric(glyph_id)?.0)
+    }
+
+    /// Return the top side bearing of `glyph_id`, or `None` if `vmtx` is not parsed or
+    /// the glyph is out of range.
+    pub fn tsb(&self, glyph_id: usize) -> Option<i16> {
+        Some(self.ver_metric(glyph_id)?.1)
+    }
+
+    fn ver_metric(&self, glyph_id: usize) -> Option<(u16, i16)> {
+        let vmtx = self.vmtx.as_ref()?;
+        let tsb = vmtx.top_side_bearing(glyph_id)?;
+        Some((vmtx.advance_height(glyph_id), tsb))
+    }
+}
+
+impl Table_vmtx {
+    /// Return the advance height of `glyph_id`. Glyphs after the last long metric record
+    /// share its advance height. Return 0 if there is no metric record.
+    pub fn advance_height(&self, glyph_id: usize) -> u16 {
+        self.ver_metrics
+            .get(glyph_id)
+            .or_else(|| self.ver_metrics.last())
+            .map_or(0, |rec| rec.advance_height)
+    }
+
+    /// Return the top side bearing of `glyph_id`.
+    pub fn top_side_bearing(&self, glyph_id: usize) -> Option<i16> {
+        match self.ver_metrics.get(glyph_id) {
+            Some(rec) => Some(rec.top_side_bearing),
+            None => self
+                .top_side_bearings
+                .get(glyph_id - self.ver_metrics.len())
+                .copied(),
+        }
+    }
+}
+
+#[derive(Debug, ReadBuffer)]
+pub struct LongVerMetric {
+    pub advance_height: u16,
+    pub top_side_bearing: i16,
+}
+
+#[test]
+fn test_vmtx_metrics() {
+    let mut buffer = Buffer::new(vec![0x03, 0xE8, 0, 50, 0x04, 0, 0, 60, 0, 70]);
+    let vmtx = Table_vmtx {
+        ver_metrics: buffer.get_vec(2),
+        top_side_bearings: buffer.get_vec(1),
+    };
+    assert_eq!(vmtx.advance_height(0), 1000);
+    assert_eq!(vmtx.advance_height(2), 1024);
+    assert_eq!(vmtx.top_side_bearing(1), Some(60));
+    assert_eq!(vmtx.top_side_bearing(2), Some(70));
+    assert_eq!(vmtx.top_side_bearing(3), None);
+
+    let mut font = Font::default();
+    assert_eq!(font.advance_height(0), None);
+    font.vmtx = Some(vmtx);
+    assert_eq!(font.advance_height(2), Some(1024));
+    assert_eq!(font.tsb(2), Some(70));
+    assert_eq!(font.tsb(3), None);
+}
@@ -1,4 +1,6 @@
+use crate::error::FontError;
 use crate::font::Font;
+use crate::tables::otvar::item_variation_store::{DeltaSetIndexMap, ItemVariationStore};
 use crate::types::F2Dot14;
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
@@ -21,10 +23,14 @@ pub struct Table_avar {
     // Reserved `uint16` here.
     axis_count: u16,
     axis_segment_maps: Vec<SegmentMaps>,
+    // Version 2.0
+    pub axis_index_map: Option<DeltaSetIndexMap>,
+    pub item_variation_store: Option<ItemVariationStore>,
 }
 
 impl Font {
-    pub fn parse_avar(&mut self, buffer: &mut Buffer) {
+    pub fn parse_avar(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let avar_start = buffer.offset();
         let version = buffer.get_version::<u16>();
         let axis_count = {
             buffer.skip::<u16>(1);
@@ -32,11 +38,56 @@ impl Font {
         };
         let axis_segment_maps = buffer.get_vec(axis_count);
 
+        let (axis_index_map, item_variation_store) = if version == "2.0" {
+            let axis_index_map_offset: u32 = buffer.get();
+            let var_store_offset: u32 = buffer.get();
+            (
+                buffer.get_or_none(avar_start, axis_index_map_offset),
+                buffer.get_or_none(avar_start, var_store_offset),
+            )
+        } else {
+            (None, None)
+        };
+
         self.avar = Some(Table_avar {
             version,
             axis_count,
             axis_segment_maps,
+            axis_index_map,
+            item_variation_store,
         });
+        Ok(())
+    }
+}
+
+impl Table_avar {
+    /// Apply the `avar` normalization to the default-normalized coordinates `coords` in place.
+    /// The segment maps are applied first; for version 2.0 tables, the deltas from the item
+    /// variation store, interpolated at the mapped coordinates, are then added. Version 1.0
+    /// tables only have the first step.
+    pub fn apply_v2(&self, coords: &mut [f32]) {
+        coords
+            .iter_mut()
+            .zip(&self.axis_segment_maps)
+            .for_each(|(coord, segment_maps)| *coord = segment_maps.map(*coord));
+
+        if let Some(store) = &self.item_variation_store {
+            let mapped: Vec<i16> = coords
+                .iter()
+                .map(|&coord| (coord * 16384.0).round() as i16)
+                .collect();
+            coords.iter_mut().enumerate().for_each(|(i, coord)| {
+                let (outer, inner) = match &self.axis_index_map {
+                    Some(map) => match map.get(i as u32) {
+                        Some(indices) => indices,
+                        None => return,
+                    },
+                    None => (0, i as u16),
+                };
+                let delta = store.delta(outer, inner, &mapped) / 16384.0;
+                *coord = (*coord + delta).clamp(-1.0, 1.0);
+            });
+        }
     }
 }
 
@@ -46,6 +97,33 @@ struct SegmentMaps {
     axis_value_maps: Vec<AxisValueMap>,
 }
 
+impl SegmentMaps {
+    /// Map a normalized coordinate with the piecewise-linear function defined by the maps.
+    fn map(&self, coord: f32) -> f32 {
+        let maps: Vec<(f32, f32)> = self
+            .axis_value_maps
+            .iter()
+            .map(|m| (f32::from(m.from_coordinate), f32::from(m.to_coordinate)))
+            .collect();
+        let (first, last) = match (maps.first(), maps.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return coord,
+        };
+        if coord <= first.0 {
+            return coord + first.1 - first.0;
+        }
+        if coord >= last.0 {
+            return coord + last.1 - last.0;
+        }
+        maps.windows(2)
+            .find(|w| coord <= w[1].0)
+            .map_or(coord, |w| {
+                let ((from_0, to_0), (from_1, to_1)) = (w[0], w[1]);
+                to_0 + (to_1 - to_0) * (coord - from_0) / (from_1 - from_0)
+            })
+    }
+}
+
 impl ReadBuffer for SegmentMaps {
     fn read(buffer: &mut Buffer) -> Self {
         let position_map_count = buffer.get();
@@ -62,3 +140,37 @@ struct AxisValueMap {
     pub from_coordinate: F2Dot14,
     pub to_coordinate: F2Dot14,
 }
+
+#[test]
+fn test_avar_apply_v2() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 2, 0, 0, 0, 0, 0, 1, // header
+        0, 4, 0xC0, 0, 0xC0, 0, 0, 0, 0, 0, 0x20, 0, 0x30, 0, 0x40, 0, 0x40, 0, // segment maps
+        0, 0, 0, 0, 0, 0, 0, 34, // axis index map and item variation store offsets
+        0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22, // item variation store
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0, // region 0: [0, 1, 1]
+        0, 1, 0, 1, 0, 1, 0, 0, 0xF8, 0, // data 0: delta -0.125
+    ]);
+    let mut font = Font::default();
+    font.parse_avar(&mut buffer).unwrap();
+    let avar = font.avar.unwrap();
+
+    // Segment maps only: (-1, -1), (0, 0), (0.5, 0.75), (1, 1)
+    let segment_maps = &avar.axis_segment_maps[0];
+    assert_eq!(segment_maps.map(-0.5), -0.5);
+    assert_eq!(segment_maps.map(0.25), 0.375);
+    assert_eq!(segment_maps.map(0.75), 0.875);
+    assert_eq!(segment_maps.map(1.0), 1.0);
+
+    // Segment maps and then the deltas, scaled by the mapped coordinate
+    let mut coords = [0.25];
+    avar.apply_v2(&mut coords);
+    assert_eq!(coords, [0.375 - 0.125 * 0.375]);
+    let mut coords = [1.0];
+    avar.apply_v2(&mut coords);
+    assert_eq!(coords, [0.875]);
+    let mut coords = [-0.5];
+    avar.apply_v2(&mut coords);
+    assert_eq!(coords, [-0.5]);
+}
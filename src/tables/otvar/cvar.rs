@@ -0,0 +1,82 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::tables::otvar::tuple_variation_store::{read_tuple_variation_store, TupleVariation};
+use crate::types::F2Dot14;
+use crate::util::Buffer;
+
+/// ## `cvar` &mdash; CVT Variations Table
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/cvar>.
+///
+/// The control value table (CVT) variations table is used in variable fonts to
+/// provide variation data for CVT values. It uses the tuple variation store
+/// format, where each point number refers to a CVT entry.
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+pub struct Table_cvar {
+    version: String,
+    axis_count: u16,
+    pub tuple_variations: Vec<TupleVariation>,
+}
+
+impl Font {
+    pub fn parse_cvar(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let cvar_start = buffer.offset();
+        let version = buffer.get_version::<u16>();
+        // The axis count is defined in `fvar`
+        let axis_count = self
+            .fvar
+            .as_ref()
+            .map_or(0, |fvar| fvar.axes().len() as u16);
+        self.cvar = Some(Table_cvar {
+            version,
+            axis_count,
+            tuple_variations: read_tuple_variation_store(buffer, cvar_start, axis_count),
+        });
+        Ok(())
+    }
+}
+
+impl Table_cvar {
+    /// Return the CVT values at the normalized coordinates `coords`. The deltas of all the
+    /// tuple variations are accumulated before rounding.
+    pub fn apply_deltas(&self, cvt: &[i16], coords: &[F2Dot14]) -> Vec<i16> {
+        let mut values: Vec<f32> = cvt.iter().map(|&n| f32::from(n)).collect();
+        for tuple_variation in &self.tuple_variations {
+            let scalar = tuple_variation.scalar(coords);
+            if scalar == 0.0 {
+                continue;
+            }
+            for (index, delta) in tuple_variation.point_deltas() {
+                if let Some(value) = values.get_mut(index as usize) {
+                    *value += delta as f32 * scalar;
+                }
+            }
+        }
+        values.iter().map(|n| n.round() as i16).collect()
+    }
+}
+
+#[test]
+fn test_cvar() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 0, 0, 2, 0, 20, // version, 2 tuple variations, data offset
+        0, 5, 0xA0, 0, 0x40, 0, // all points, peak 1.0
+        0, 9, 0xA0, 0, 0xC0, 0, // points 0 and 2, peak -1.0
+        0, 0x02, 10, 20, 30, // deltas: 10, 20, 30
+        2, 1, 0, 2, 0x41, 0, 100, 0xFF, 0x38, // deltas: 100, -200
+    ]);
+    buffer.skip::<u32>(1);
+    let cvar = Table_cvar {
+        version: String::from("1.0"),
+        axis_count: 1,
+        tuple_variations: read_tuple_variation_store(&mut buffer, 0, 1),
+    };
+    assert_eq!(cvar.tuple_variations.len(), 2);
+    let coords = |n: f32| vec![F2Dot14::from(n)];
+    let cvt = [100, 200, 300];
+    assert_eq!(cvar.apply_deltas(&cvt, &coords(0.0)), vec![100, 200, 300]);
+    assert_eq!(cvar.apply_deltas(&cvt, &coords(0.5)), vec![105, 210, 315]);
+    assert_eq!(cvar.apply_deltas(&cvt, &coords(-1.0)), vec![200, 200, 100]);
+}
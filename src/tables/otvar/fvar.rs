@@ -1,7 +1,9 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::{Fixed, Tag};
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
+use std::collections::HashMap;
 
 /// ## `fvar` &mdash; Font Variations Table
 ///
@@ -27,7 +29,7 @@ pub struct Table_fvar {
 }
 
 impl Font {
-    pub fn parse_fvar(&mut self, buffer: &mut Buffer) {
+    pub fn parse_fvar(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let version = buffer.get_version::<u16>();
         let axes_array_offset = buffer.get();
         let axis_count = {
@@ -52,11 +54,124 @@ impl Font {
             axes,
             instances,
         });
+        Ok(())
+    }
+
+    /// Return the variation axes as `(tag, name, min, default, max)` tuples, where the
+    /// axis names are looked up in the `name` table (`"Unknown"` if missing).
+    /// Return an empty vector if there is no `fvar` table.
+    pub fn axes_with_names(&self) -> Vec<(Tag, String, f32, f32, f32)> {
+        let fvar = match &self.fvar {
+            Some(fvar) => fvar,
+            None => return Vec::new(),
+        };
+        fvar.axes
+            .iter()
+            .map(|axis| {
+                let name = self
+                    .name_record(axis.axis_name_id)
+                    .map_or_else(|| String::from("Unknown"), |rec| rec.string.clone());
+                (
+                    axis.axis_tag,
+                    name,
+                    f32::from(axis.min_value),
+                    f32::from(axis.default_value),
+                    f32::from(axis.max_value),
+                )
+            })
+            .collect()
+    }
+
+    /// Convert user-space coordinates to normalized coordinates, with the `avar` mapping
+    /// applied if present. Axes missing in `user_coords` are at their default values.
+    /// Return an empty map if there is no `fvar` table.
+    pub fn normalize_coordinates(&self, user_coords: &HashMap<Tag, f32>) -> HashMap<Tag, f32> {
+        let fvar = match &self.fvar {
+            Some(fvar) => fvar,
+            None => return HashMap::new(),
+        };
+        let mut coords: Vec<f32> = fvar
+            .axes
+            .iter()
+            .map(|axis| {
+                let user_value = user_coords
+                    .get(&axis.axis_tag)
+                    .copied()
+                    .unwrap_or_else(|| f32::from(axis.default_value));
+                axis.normalize(user_value)
+            })
+            .collect();
+        if let Some(avar) = &self.avar {
+            avar.apply_v2(&mut coords);
+        }
+        fvar.axes
+            .iter()
+            .map(|axis| axis.axis_tag)
+            .zip(coords)
+            .collect()
+    }
+}
+
+impl Table_fvar {
+    /// Return the variation axes of the font.
+    pub fn axes(&self) -> &[VariationAxis] {
+        &self.axes
+    }
+
+    /// Return the number of named instances.
+    pub fn instance_count(&self) -> u16 {
+        self.instance_count
+    }
+
+    /// Clamp `value` to the range of `axis`. Return `None` if there is no such axis.
+    pub fn clamp_user_value(&self, axis: Tag, value: f32) -> Option<f32> {
+        let axis = self.axis(axis)?;
+        Some(
+            value
+                .max(f32::from(axis.min_value))
+                .min(f32::from(axis.max_value)),
+        )
+    }
+
+    /// Check whether `value` is within the range of `axis`.
+    pub fn is_valid_value(&self, axis: Tag, value: f32) -> bool {
+        self.clamp_user_value(axis, value) == Some(value)
+    }
+
+    /// Return the default value of each axis.
+    pub fn default_instance(&self) -> HashMap<Tag, f32> {
+        self.axes
+            .iter()
+            .map(|axis| (axis.axis_tag, f32::from(axis.default_value)))
+            .collect()
+    }
+
+    /// Convert `user_value` of `axis` to the normalized coordinate in [-1, 1], without the
+    /// `avar` mapping. Return `None` if there is no such axis.
+    pub fn user_to_normalized(&self, axis: Tag, user_value: f32) -> Option<f32> {
+        Some(self.axis(axis)?.normalize(user_value))
+    }
+
+    /// Convert the normalized coordinate of `axis` back to the user-space value. This is the
+    /// inverse of [`Table_fvar::user_to_normalized`] for values within the axis range.
+    pub fn normalized_to_user(&self, axis: Tag, normalized: f32) -> Option<f32> {
+        let axis = self.axis(axis)?;
+        let (min, default, max) = axis.values();
+        let normalized = normalized.clamp(-1.0, 1.0);
+        Some(if normalized < 0.0 {
+            default + normalized * (default - min)
+        } else {
+            default + normalized * (max - default)
+        })
+    }
+
+    fn axis(&self, tag: Tag) -> Option<&VariationAxis> {
+        self.axes.iter().find(|axis| axis.axis_tag == tag)
     }
 }
 
 #[derive(Debug, ReadBuffer)]
-struct VariationAxis {
+pub struct VariationAxis {
     pub axis_tag: Tag,
     pub min_value: Fixed,
     pub default_value: Fixed,
@@ -65,6 +180,29 @@ struct VariationAxis {
     pub axis_name_id: u16,
 }
 
+impl VariationAxis {
+    fn values(&self) -> (f32, f32, f32) {
+        (
+            f32::from(self.min_value),
+            f32::from(self.default_value),
+            f32::from(self.max_value),
+        )
+    }
+
+    /// The default normalization: map [min, default, max] to [-1, 0, 1] linearly.
+    fn normalize(&self, user_value: f32) -> f32 {
+        let (min, default, max) = self.values();
+        let value = user_value.clamp(min, max);
+        if value < default {
+            (value - default) / (default - min)
+        } else if value > default {
+            (value - default) / (max - default)
+        } else {
+            0.0
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Instance {
     pub subfamily_name_id: u16,
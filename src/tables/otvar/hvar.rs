@@ -1,4 +1,7 @@
+use crate::error::FontError;
 use crate::font::Font;
+use crate::tables::otvar::item_variation_store::{DeltaSetIndexMap, ItemVariationStore};
+use crate::types::F2Dot14;
 use crate::util::Buffer;
 
 /// ## `HVAR` &mdash; Horizontal Metrics Variations Table
@@ -15,21 +18,68 @@ use crate::util::Buffer;
 #[derive(Debug)]
 pub struct Table_HVAR {
     version: String,
-    _item_variation_store_offset: u32,
-    _advance_width_mapping_offset: u32,
-    _lsb_mapping_offset: u32,
-    _rsb_mapping_offset: u32,
+    pub item_variation_store: ItemVariationStore,
+    pub advance_width_mapping: Option<DeltaSetIndexMap>,
+    pub lsb_mapping: Option<DeltaSetIndexMap>,
+    pub rsb_mapping: Option<DeltaSetIndexMap>,
 }
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_HVAR(&mut self, buffer: &mut Buffer) {
+    pub fn parse_HVAR(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let hvar_start = buffer.offset();
+        let version = buffer.get_version::<u16>();
+        let item_variation_store_offset: u32 = buffer.get();
+        let advance_width_mapping_offset: u32 = buffer.get();
+        let lsb_mapping_offset: u32 = buffer.get();
+        let rsb_mapping_offset: u32 = buffer.get();
+
+        buffer.set_offset_from(hvar_start, item_variation_store_offset);
         self.HVAR = Some(Table_HVAR {
-            version: buffer.get_version::<u16>(),
-            _item_variation_store_offset: buffer.get(),
-            _advance_width_mapping_offset: buffer.get(),
-            _lsb_mapping_offset: buffer.get(),
-            _rsb_mapping_offset: buffer.get(),
+            version,
+            item_variation_store: buffer.get(),
+            advance_width_mapping: buffer.get_or_none(hvar_start, advance_width_mapping_offset),
+            lsb_mapping: buffer.get_or_none(hvar_start, lsb_mapping_offset),
+            rsb_mapping: buffer.get_or_none(hvar_start, rsb_mapping_offset),
         });
+        Ok(())
+    }
+}
+
+impl Table_HVAR {
+    /// Return the advance width delta of `glyph_id` at the normalized coordinates `coords`.
+    /// Without an advance width mapping, glyph IDs are used directly as inner indices of the
+    /// first item variation data. Return `None` if there is no variation data for the glyph.
+    pub fn advance_width_delta(&self, glyph_id: u16, coords: &[F2Dot14]) -> Option<f32> {
+        self.item_variation_store
+            .glyph_delta(self.advance_width_mapping.as_ref(), glyph_id, coords)
     }
 }
+
+#[test]
+fn test_advance_width_delta() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 0, 0, 0, 0, 20, 0, 0, 0, 52, 0, 0, 0, 0, 0, 0, 0, 0, // header
+        0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22, // item variation store
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0, // region 0: [0, 1, 1]
+        0, 2, 0, 0, 0, 1, 0, 0, 10, 0xEC, // data 0: deltas 10, -20
+        0, 0, 0, 2, 1, 0, // advance width mapping: (0, 1), (0, 0)
+    ]);
+    let mut font = Font::default();
+    font.parse_HVAR(&mut buffer).unwrap();
+    let hvar = font.HVAR.unwrap();
+    assert_eq!(
+        hvar.advance_width_delta(0, &[F2Dot14::from(1.0)]),
+        Some(-20.0)
+    );
+    assert_eq!(
+        hvar.advance_width_delta(1, &[F2Dot14::from(0.5)]),
+        Some(5.0)
+    );
+    assert_eq!(
+        hvar.advance_width_delta(7, &[F2Dot14::from(1.0)]),
+        Some(10.0)
+    );
+    assert_eq!(hvar.advance_width_delta(0, &[]), Some(0.0));
+}
@@ -0,0 +1,272 @@
+//! Common formats for variation data, which are shared by the `HVAR`, `VVAR`, `MVAR`,
+//! `GDEF`, `avar` and `CFF2` tables.
+//!
+//! See: <https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats>.
+
+use crate::types::F2Dot14;
+use crate::util::{Buffer, ReadBuffer};
+use read_buffer_derive::ReadBuffer;
+
+/// Item variation store, which contains the variation regions and the deltas of a set of
+/// items. Each item is addressed by an outer index (the item variation data subtable) and
+/// an inner index (the row within that subtable).
+#[derive(Debug)]
+pub struct ItemVariationStore {
+    pub format: u16,
+    pub variation_regions: Vec<VariationRegion>,
+    pub item_variation_data: Vec<ItemVariationData>,
+}
+
+impl ItemVariationStore {
+    /// Return the scalar of the region `region_index` at the normalized coordinates
+    /// `coords` (in `F2Dot14` units), i.e. the product of the tent functions of all axes.
+    /// Missing coordinates are treated as 0 (the default).
+    pub fn scalar(&self, region_index: u16, coords: &[i16]) -> f32 {
+        let region = match self.variation_regions.get(region_index as usize) {
+            Some(region) => region,
+            None => return 0.0,
+        };
+        region
+            .region_axes
+            .iter()
+            .enumerate()
+            .map(|(i, axis)| {
+                let coord = coords.get(i).map_or(0.0, |&n| f32::from(n) / 16384.0);
+                axis.scalar(coord)
+            })
+            .product()
+    }
+
+    /// Return the interpolated delta of the item (`outer`, `inner`) at the normalized
+    /// coordinates `coords`. Return 0 if the item does not exist.
+    pub fn delta(&self, outer: u16, inner: u16, coords: &[i16]) -> f32 {
+        let data = match self.item_variation_data.get(outer as usize) {
+            Some(data) => data,
+            None => return 0.0,
+        };
+        let deltas = match data.delta_sets.get(inner as usize) {
+            Some(deltas) => deltas,
+            None => return 0.0,
+        };
+        data.region_indexes
+            .iter()
+            .zip(deltas)
+            .map(|(&region_index, &delta)| delta as f32 * self.scalar(region_index, coords))
+            .sum()
+    }
+
+    /// Return the interpolated delta of the metric of `glyph_id` at the normalized coordinates
+    /// `coords`, as used by `HVAR` and `VVAR`. Without a `mapping`, glyph IDs are used directly
+    /// as inner indices of the first item variation data. Return `None` if there is no
+    /// variation data for the glyph.
+    pub fn glyph_delta(
+        &self,
+        mapping: Option<&DeltaSetIndexMap>,
+        glyph_id: u16,
+        coords: &[F2Dot14],
+    ) -> Option<f32> {
+        let (outer, inner) = match mapping {
+            Some(mapping) => mapping.get(glyph_id as u32)?,
+            None => (0, glyph_id),
+        };
+        self.item_variation_data
+            .get(outer as usize)?
+            .delta_sets
+            .get(inner as usize)?;
+        let coords: Vec<i16> = coords.iter().map(|&n| i16::from(n)).collect();
+        Some(self.delta(outer, inner, &coords))
+    }
+}
+
+impl ReadBuffer for ItemVariationStore {
+    fn read(buffer: &mut Buffer) -> Self {
+        let start = buffer.offset();
+        let format = buffer.get();
+        let variation_region_list_offset: u32 = buffer.get();
+        let item_variation_data_count: u16 = buffer.get();
+        let item_variation_data_offsets: Vec<u32> = buffer.get_vec(item_variation_data_count);
+
+        buffer.set_offset_from(start, variation_region_list_offset);
+        let axis_count: u16 = buffer.get();
+        let region_count: u16 = buffer.get();
+        let variation_regions = (0..region_count)
+            .map(|_| VariationRegion {
+                region_axes: buffer.get_vec(axis_count),
+            })
+            .collect();
+
+        let item_variation_data = item_variation_data_offsets
+            .iter()
+            .map(|&offset| {
+                buffer.set_offset_from(start, offset);
+                buffer.get()
+            })
+            .collect();
+
+        Self {
+            format,
+            variation_regions,
+            item_variation_data,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VariationRegion {
+    pub region_axes: Vec<RegionAxisCoordinates>,
+}
+
+#[derive(Debug, ReadBuffer)]
+pub struct RegionAxisCoordinates {
+    pub start_coord: F2Dot14,
+    pub peak_coord: F2Dot14,
+    pub end_coord: F2Dot14,
+}
+
+impl RegionAxisCoordinates {
+    /// The tent function of the axis.
+    fn scalar(&self, coord: f32) -> f32 {
+        let start = f32::from(self.start_coord);
+        let peak = f32::from(self.peak_coord);
+        let end = f32::from(self.end_coord);
+        // Invalid or non-participating axes
+        if start > peak || peak > end || (start < 0.0 && end > 0.0) || peak == 0.0 {
+            return 1.0;
+        }
+        if coord < start || coord > end {
+            0.0
+        } else if coord == peak {
+            1.0
+        } else if coord < peak {
+            (coord - start) / (peak - start)
+        } else {
+            (end - coord) / (end - peak)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ItemVariationData {
+    pub region_indexes: Vec<u16>,
+    /// Deltas of each item, in the order of `region_indexes`.
+    pub delta_sets: Vec<Vec<i32>>,
+}
+
+impl ReadBuffer for ItemVariationData {
+    fn read(buffer: &mut Buffer) -> Self {
+        const LONG_WORDS: u16 = 0x8000;
+        const WORD_DELTA_COUNT_MASK: u16 = 0x7FFF;
+
+        let item_count: u16 = buffer.get();
+        let word_delta_count: u16 = buffer.get();
+        let region_index_count: u16 = buffer.get();
+        let region_indexes = buffer.get_vec(region_index_count);
+
+        let long_words = word_delta_count & LONG_WORDS != 0;
+        let word_count = word_delta_count & WORD_DELTA_COUNT_MASK;
+        let delta_sets = (0..item_count)
+            .map(|_| {
+                (0..region_index_count)
+                    .map(|i| match (i < word_count, long_words) {
+                        (true, true) => buffer.get::<i32>(),
+                        (true, false) | (false, true) => buffer.get::<i16>() as i32,
+                        (false, false) => buffer.get::<i8>() as i32,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            region_indexes,
+            delta_sets,
+        }
+    }
+}
+
+/// Delta-set index mapping, which maps glyph IDs (or other indices) to the
+/// (`outer`, `inner`) indices of an [`ItemVariationStore`].
+#[derive(Debug)]
+pub struct DeltaSetIndexMap {
+    pub format: u8,
+    pub entry_format: u8,
+    /// The `(outer, inner)` index pairs.
+    pub map_data: Vec<(u16, u16)>,
+}
+
+impl DeltaSetIndexMap {
+    /// Return the `(outer, inner)` indices of `index`. Indices beyond the end of the map
+    /// use the last entry.
+    pub fn get(&self, index: u32) -> Option<(u16, u16)> {
+        self.map_data
+            .get(index as usize)
+            .or_else(|| self.map_data.last())
+            .copied()
+    }
+}
+
+impl ReadBuffer for DeltaSetIndexMap {
+    fn read(buffer: &mut Buffer) -> Self {
+        const INNER_INDEX_BIT_COUNT_MASK: u8 = 0x0F;
+        const MAP_ENTRY_SIZE_MASK: u8 = 0x30;
+
+        let format = buffer.get();
+        let entry_format: u8 = buffer.get();
+        let map_count = match format {
+            0 => buffer.get::<u16>() as u32,
+            1 => buffer.get::<u32>(),
+            format => {
+                buffer.set_unknown_format(format as u16);
+                0
+            }
+        };
+
+        let entry_size = ((entry_format & MAP_ENTRY_SIZE_MASK) >> 4) + 1;
+        let inner_bit_count = (entry_format & INNER_INDEX_BIT_COUNT_MASK) + 1;
+        let map_data = (0..map_count)
+            .map(|_| {
+                let entry =
+                    (0..entry_size).fold(0u32, |acc, _| (acc << 8) | buffer.get::<u8>() as u32);
+                let outer = entry >> inner_bit_count;
+                let inner = entry & ((1 << inner_bit_count) - 1);
+                (outer as u16, inner as u16)
+            })
+            .collect();
+
+        Self {
+            format,
+            entry_format,
+            map_data,
+        }
+    }
+}
+
+#[test]
+fn test_item_variation_store() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 0, 0, 16, 0, 2, 0, 0, 0, 26, 0, 0, 0, 36, // header
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0, // region 0: [0, 1, 1]
+        0, 2, 0, 0, 0, 1, 0, 0, 10, 0xEC, // data 0: deltas 10, -20 (bytes)
+        0, 1, 0x80, 1, 0, 1, 0, 0, 0, 1, 0x86, 0xA0, // data 1: delta 100000 (LONG_WORDS)
+    ]);
+    let store: ItemVariationStore = buffer.get();
+    assert_eq!(store.scalar(0, &[0x2000]), 0.5);
+    assert_eq!(store.scalar(0, &[-0x2000]), 0.0);
+    assert_eq!(store.scalar(0, &[]), 0.0);
+    assert_eq!(store.scalar(1, &[0x4000]), 0.0);
+    assert_eq!(store.delta(0, 0, &[0x2000]), 5.0);
+    assert_eq!(store.delta(0, 1, &[0x4000]), -20.0);
+    assert_eq!(store.delta(1, 0, &[0x4000]), 100000.0);
+    assert_eq!(store.delta(1, 1, &[0x4000]), 0.0);
+    assert_eq!(store.delta(2, 0, &[0x4000]), 0.0);
+}
+
+#[test]
+fn test_delta_set_index_map() {
+    // Format 0, 2-byte entries with 4 bits of inner index: (0, 1), (1, 0)
+    let mut buffer = Buffer::new(vec![0, 0x13, 0, 2, 0, 0x01, 0, 0x10]);
+    let map: DeltaSetIndexMap = buffer.get();
+    assert_eq!(map.get(0), Some((0, 1)));
+    assert_eq!(map.get(1), Some((1, 0)));
+    assert_eq!(map.get(5), Some((1, 0)));
+}
@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::{Buffer, ReadBuffer};
@@ -26,7 +27,7 @@ pub struct Table_MVAR {
 
 impl Font {
     #[allow(non_snake_case)]
-    pub fn parse_MVAR(&mut self, buffer: &mut Buffer) {
+    pub fn parse_MVAR(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let version = buffer.get_version::<u16>();
         let value_record_size = {
             buffer.skip::<u16>(1);
@@ -43,6 +44,7 @@ impl Font {
             item_variation_store_offset,
             value_records,
         });
+        Ok(())
     }
 }
 
@@ -0,0 +1,264 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::types::{Fixed, Tag};
+use crate::util::{Buffer, ReadBuffer};
+use read_buffer_derive::ReadBuffer;
+
+/// ## `STAT` &mdash; Style Attributes Table
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/stat>.
+///
+/// The style attributes table describes design attributes that distinguish
+/// font-style variants within a font family. It also provides associations
+/// between those attributes and name elements that may be used to present font
+/// options within application user interfaces.
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+pub struct Table_STAT {
+    version: String,
+    design_axis_size: u16,
+    design_axis_count: u16,
+    axis_value_count: u16,
+    pub design_axes: Vec<AxisRecord>,
+    pub axis_values: Vec<AxisValue>,
+    // Since version 1.1
+    elided_fallback_name_id: Option<u16>,
+}
+
+impl Font {
+    #[allow(non_snake_case)]
+    pub fn parse_STAT(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let stat_start = buffer.offset();
+        let version = buffer.get_version::<u16>();
+        let design_axis_size: u16 = buffer.get();
+        let design_axis_count: u16 = buffer.get();
+        let design_axes_offset: u32 = buffer.get();
+        let axis_value_count: u16 = buffer.get();
+        let offset_to_axis_value_offsets: u32 = buffer.get();
+        let elided_fallback_name_id = if version == "1.0" {
+            None
+        } else {
+            Some(buffer.get())
+        };
+
+        // The size of the axis records may be larger than what we know for future versions.
+        let design_axes = (0..design_axis_count as usize)
+            .map(|i| {
+                buffer.set_offset_from(
+                    stat_start,
+                    design_axes_offset as usize + i * design_axis_size as usize,
+                );
+                buffer.get()
+            })
+            .collect();
+
+        let axis_value_offsets_start = stat_start + offset_to_axis_value_offsets as usize;
+        buffer.set_offset(axis_value_offsets_start);
+        let axis_value_offsets: Vec<u16> = buffer.get_vec(axis_value_count);
+        let axis_values = axis_value_offsets
+            .iter()
+            .map(|&offset| {
+                buffer.set_offset_from(axis_value_offsets_start, offset);
+                buffer.get()
+            })
+            .collect();
+
+        self.STAT = Some(Table_STAT {
+            version,
+            design_axis_size,
+            design_axis_count,
+            axis_value_count,
+            design_axes,
+            axis_values,
+            elided_fallback_name_id,
+        });
+        Ok(())
+    }
+}
+
+impl Table_STAT {
+    /// Return the axis values that refer to the design axis `tag`, including the format 4
+    /// axis values that combine it with other axes.
+    pub fn axis_values_for_tag(&self, tag: Tag) -> Vec<&AxisValue> {
+        let axis_index = match self
+            .design_axes
+            .iter()
+            .position(|axis| axis.axis_tag == tag)
+        {
+            Some(index) => index as u16,
+            None => return Vec::new(),
+        };
+        self.axis_values
+            .iter()
+            .filter(|axis_value| axis_value.axis_indices().contains(&axis_index))
+            .collect()
+    }
+
+    /// Return the name ID used when all the axis value names of a font are elided, e.g.
+    /// "Regular". It is only available since version 1.1.
+    pub fn elided_fallback_name_id(&self) -> Option<u16> {
+        self.elided_fallback_name_id
+    }
+}
+
+/// Design axis record.
+#[derive(Debug, ReadBuffer)]
+pub struct AxisRecord {
+    pub axis_tag: Tag,
+    pub axis_name_id: u16,
+    /// The order of the axis when composing the names of the styles.
+    pub axis_ordering: u16,
+}
+
+/// Axis value table, which associates a name with a value (or range) of one or more axes.
+#[derive(Debug)]
+pub enum AxisValue {
+    /// Single value of an axis.
+    Format1 {
+        axis_index: u16,
+        flags: u16,
+        value_name_id: u16,
+        value: Fixed,
+    },
+    /// Range of values of an axis.
+    Format2 {
+        axis_index: u16,
+        flags: u16,
+        value_name_id: u16,
+        nominal_value: Fixed,
+        range_min_value: Fixed,
+        range_max_value: Fixed,
+    },
+    /// Single value of an axis, linked to another value, e.g. "Bold" to "Regular".
+    Format3 {
+        axis_index: u16,
+        flags: u16,
+        value_name_id: u16,
+        value: Fixed,
+        linked_value: Fixed,
+    },
+    /// Combination of values of multiple axes, as `(axis index, value)` pairs.
+    Format4 {
+        flags: u16,
+        value_name_id: u16,
+        axis_values: Vec<(u16, Fixed)>,
+    },
+}
+
+impl AxisValue {
+    /// The axis value applies to an older sibling font, e.g. a non-variable font.
+    pub const OLDER_SIBLING_FONT_ATTRIBUTE: u16 = 0x0001;
+    /// The name of the axis value may be omitted when composing the style names.
+    pub const ELIDABLE_AXIS_VALUE_NAME: u16 = 0x0002;
+
+    /// Return the indices of the design axes that the axis value refers to.
+    pub fn axis_indices(&self) -> Vec<u16> {
+        match self {
+            Self::Format1 { axis_index, .. }
+            | Self::Format2 { axis_index, .. }
+            | Self::Format3 { axis_index, .. } => vec![*axis_index],
+            Self::Format4 { axis_values, .. } => axis_values
+                .iter()
+                .map(|&(axis_index, _)| axis_index)
+                .collect(),
+        }
+    }
+
+    pub fn flags(&self) -> u16 {
+        match self {
+            Self::Format1 { flags, .. }
+            | Self::Format2 { flags, .. }
+            | Self::Format3 { flags, .. }
+            | Self::Format4 { flags, .. } => *flags,
+        }
+    }
+
+    pub fn value_name_id(&self) -> u16 {
+        match self {
+            Self::Format1 { value_name_id, .. }
+            | Self::Format2 { value_name_id, .. }
+            | Self::Format3 { value_name_id, .. }
+            | Self::Format4 { value_name_id, .. } => *value_name_id,
+        }
+    }
+
+    /// Return `true` if the name of the axis value is elidable.
+    pub fn is_elidable(&self) -> bool {
+        self.flags() & Self::ELIDABLE_AXIS_VALUE_NAME != 0
+    }
+}
+
+impl ReadBuffer for AxisValue {
+    fn read(buffer: &mut Buffer) -> Self {
+        let format: u16 = buffer.get();
+        match format {
+            1 => Self::Format1 {
+                axis_index: buffer.get(),
+                flags: buffer.get(),
+                value_name_id: buffer.get(),
+                value: buffer.get(),
+            },
+            2 => Self::Format2 {
+                axis_index: buffer.get(),
+                flags: buffer.get(),
+                value_name_id: buffer.get(),
+                nominal_value: buffer.get(),
+                range_min_value: buffer.get(),
+                range_max_value: buffer.get(),
+            },
+            3 => Self::Format3 {
+                axis_index: buffer.get(),
+                flags: buffer.get(),
+                value_name_id: buffer.get(),
+                value: buffer.get(),
+                linked_value: buffer.get(),
+            },
+            4 => {
+                let axis_count: u16 = buffer.get();
+                let flags = buffer.get();
+                let value_name_id = buffer.get();
+                let axis_values = (0..axis_count)
+                    .map(|_| (buffer.get(), buffer.get()))
+                    .collect();
+                Self::Format4 {
+                    flags,
+                    value_name_id,
+                    axis_values,
+                }
+            }
+            _ => {
+                buffer.set_unknown_format(format);
+                Self::Format4 {
+                    flags: 0,
+                    value_name_id: 0,
+                    axis_values: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_stat() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 2, 0, 8, 0, 2, 0, 0, 0, 20, 0, 3, 0, 0, 0, 36, 1, 0x2F, // header
+        b'w', b'g', b'h', b't', 1, 0x10, 0, 0, // wght
+        b'w', b'd', b't', b'h', 1, 0x11, 0, 1, // wdth
+        0, 6, 0, 22, 0, 42, // axis value offsets
+        0, 3, 0, 0, 0, 2, 1, 0x2F, 1, 0x90, 0, 0, 2, 0xBC, 0, 0, // Regular (400) => 700
+        0, 2, 0, 1, 0, 0, 1, 0x30, 0, 100, 0, 0, 0, 75, 0, 0, 0, 125, 0, 0, // Normal (wdth)
+        0, 4, 0, 2, 0, 0, 1, 0x31, 0, 0, 2, 0xBC, 0, 0, 0, 1, 0, 100, 0, 0, // Bold Normal
+    ]);
+    let mut font = Font::default();
+    font.parse_STAT(&mut buffer).unwrap();
+    let stat = font.STAT.unwrap();
+    assert_eq!(stat.elided_fallback_name_id(), Some(0x12F));
+    assert_eq!(stat.design_axes[1].axis_ordering, 1);
+    let wght_values = stat.axis_values_for_tag(Tag::new(b"wght"));
+    assert_eq!(wght_values.len(), 2);
+    assert!(wght_values[0].is_elidable());
+    assert_eq!(wght_values[1].value_name_id(), 0x131);
+    assert_eq!(stat.axis_values_for_tag(Tag::new(b"wdth")).len(), 2);
+    assert!(stat.axis_values_for_tag(Tag::new(b"ital")).is_empty());
+}
@@ -0,0 +1,219 @@
+//! Tuple variation store, which is shared by the `gvar` and `cvar` tables.
+//!
+//! See: <https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats>.
+
+use crate::types::F2Dot14;
+use crate::util::Buffer;
+
+/// A set of deltas for the region of the variation space given by a tuple.
+#[derive(Debug)]
+pub struct TupleVariation {
+    pub peak_tuple: Vec<F2Dot14>,
+    /// The start and end tuples of an intermediate region.
+    pub intermediate_region: Option<(Vec<F2Dot14>, Vec<F2Dot14>)>,
+    /// Point numbers of the deltas, or `None` if the deltas apply to all the points.
+    pub point_numbers: Option<Vec<u16>>,
+    pub deltas: Vec<i32>,
+}
+
+impl TupleVariation {
+    /// Return the scalar of the tuple variation at the normalized coordinates `coords`.
+    /// Missing coordinates are treated as 0 (the default).
+    pub fn scalar(&self, coords: &[F2Dot14]) -> f32 {
+        let mut scalar = 1.0;
+        for (i, &peak) in self.peak_tuple.iter().enumerate() {
+            let peak = f32::from(peak);
+            let coord = coords.get(i).map_or(0.0, |&n| f32::from(n));
+            if peak == 0.0 || coord == peak {
+                continue;
+            }
+            match &self.intermediate_region {
+                Some((start_tuple, end_tuple)) => {
+                    let start = f32::from(start_tuple[i]);
+                    let end = f32::from(end_tuple[i]);
+                    if coord < start || coord > end {
+                        return 0.0;
+                    } else if coord < peak {
+                        scalar *= (coord - start) / (peak - start);
+                    } else {
+                        scalar *= (end - coord) / (end - peak);
+                    }
+                }
+                None => {
+                    if coord == 0.0 || coord < peak.min(0.0) || coord > peak.max(0.0) {
+                        return 0.0;
+                    }
+                    scalar *= coord / peak;
+                }
+            }
+        }
+        scalar
+    }
+
+    /// Return the `(point number, delta)` pairs. If the deltas apply to all the points, the
+    /// point numbers are the indices of the deltas.
+    pub fn point_deltas(&self) -> Vec<(u16, i32)> {
+        match &self.point_numbers {
+            Some(point_numbers) => point_numbers
+                .iter()
+                .copied()
+                .zip(self.deltas.iter().copied())
+                .collect(),
+            None => (0..).zip(self.deltas.iter().copied()).collect(),
+        }
+    }
+}
+
+/// Read the tuple variation store starting from the `tupleVariationCount` field, where
+/// `table_start` is the start of the table that `dataOffset` is relative to. Each tuple
+/// variation is assumed to have a single set of deltas, as in `cvar`.
+pub(crate) fn read_tuple_variation_store(
+    buffer: &mut Buffer,
+    table_start: usize,
+    axis_count: u16,
+) -> Vec<TupleVariation> {
+    const SHARED_POINT_NUMBERS: u16 = 0x8000;
+    const COUNT_MASK: u16 = 0x0FFF;
+    const EMBEDDED_PEAK_TUPLE: u16 = 0x8000;
+    const INTERMEDIATE_REGION: u16 = 0x4000;
+    const PRIVATE_POINT_NUMBERS: u16 = 0x2000;
+
+    let tuple_variation_count: u16 = buffer.get();
+    let data_offset: u16 = buffer.get();
+    let headers: Vec<_> = (0..tuple_variation_count & COUNT_MASK)
+        .map(|_| {
+            let variation_data_size: u16 = buffer.get();
+            let tuple_index: u16 = buffer.get();
+            // Shared tuples are only used by `gvar`. The tuple variations referring to them
+            // are skipped below, after their data are read.
+            let peak_tuple: Option<Vec<F2Dot14>> = if tuple_index & EMBEDDED_PEAK_TUPLE != 0 {
+                Some(buffer.get_vec(axis_count))
+            } else {
+                None
+            };
+            let intermediate_region = if tuple_index & INTERMEDIATE_REGION != 0 {
+                Some((buffer.get_vec(axis_count), buffer.get_vec(axis_count)))
+            } else {
+                None
+            };
+            let has_private_points = tuple_index & PRIVATE_POINT_NUMBERS != 0;
+            (
+                variation_data_size,
+                peak_tuple,
+                intermediate_region,
+                has_private_points,
+            )
+        })
+        .collect();
+
+    buffer.set_offset_from(table_start, data_offset);
+    let shared_point_numbers = if tuple_variation_count & SHARED_POINT_NUMBERS != 0 {
+        read_packed_point_numbers(buffer)
+    } else {
+        None
+    };
+    headers
+        .into_iter()
+        .filter_map(
+            |(variation_data_size, peak_tuple, intermediate_region, has_private_points)| {
+                let end = buffer.offset() + variation_data_size as usize;
+                let point_numbers = if has_private_points {
+                    read_packed_point_numbers(buffer)
+                } else {
+                    shared_point_numbers.clone()
+                };
+                let deltas = read_packed_deltas(buffer, end);
+                buffer.set_offset(end);
+                Some(TupleVariation {
+                    peak_tuple: peak_tuple?,
+                    intermediate_region,
+                    point_numbers,
+                    deltas,
+                })
+            },
+        )
+        .collect()
+}
+
+/// Read packed point numbers. Return `None` if all the points are referred to.
+fn read_packed_point_numbers(buffer: &mut Buffer) -> Option<Vec<u16>> {
+    const POINTS_ARE_WORDS: u8 = 0x80;
+    const POINT_RUN_COUNT_MASK: u8 = 0x7F;
+
+    let count = match buffer.get::<u8>() {
+        0 => return None,
+        n if n & POINTS_ARE_WORDS != 0 => {
+            (((n & POINT_RUN_COUNT_MASK) as usize) << 8) + buffer.get::<u8>() as usize
+        }
+        n => n as usize,
+    };
+    // Point numbers are stored as the differences from the previous ones
+    let mut point_numbers = Vec::with_capacity(count);
+    let mut point = 0u16;
+    while point_numbers.len() < count {
+        let control: u8 = buffer.get();
+        let run_count = (control & POINT_RUN_COUNT_MASK) as usize + 1;
+        for _ in 0..run_count {
+            let diff = if control & POINTS_ARE_WORDS != 0 {
+                buffer.get::<u16>()
+            } else {
+                buffer.get::<u8>() as u16
+            };
+            point = point.wrapping_add(diff);
+            point_numbers.push(point);
+        }
+    }
+    Some(point_numbers)
+}
+
+/// Read packed deltas until `end`.
+fn read_packed_deltas(buffer: &mut Buffer, end: usize) -> Vec<i32> {
+    const DELTAS_ARE_ZERO: u8 = 0x80;
+    const DELTAS_ARE_WORDS: u8 = 0x40;
+    const DELTA_RUN_COUNT_MASK: u8 = 0x3F;
+
+    let mut deltas = Vec::new();
+    while buffer.offset() < end {
+        let control: u8 = buffer.get();
+        let run_count = (control & DELTA_RUN_COUNT_MASK) as usize + 1;
+        for _ in 0..run_count {
+            let delta = if control & DELTAS_ARE_ZERO != 0 {
+                0
+            } else if control & DELTAS_ARE_WORDS != 0 {
+                buffer.get::<i16>() as i32
+            } else {
+                buffer.get::<i8>() as i32
+            };
+            deltas.push(delta);
+        }
+    }
+    deltas
+}
+
+#[test]
+fn test_packed_point_numbers_and_deltas() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        3, 2, 1, 2, 4, // 3 points: 1, 3, 7
+        0x01, 10, 0xF6, 0x80, // deltas: 10, -10, 0
+    ]);
+    assert_eq!(read_packed_point_numbers(&mut buffer), Some(vec![1, 3, 7]));
+    assert_eq!(read_packed_deltas(&mut buffer, 9), vec![10, -10, 0]);
+}
+
+#[test]
+fn test_shared_tuple_skipped() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 2, 0, 14, // 2 tuple variations, data at 14
+        0, 2, 0, 0, // shared tuple 0
+        0, 2, 0x80, 0, 0x40, 0, // embedded peak tuple: 1.0
+        0x00, 5, // delta of all points: 5
+        0x00, 7, // delta of all points: 7
+    ]);
+    let variations = read_tuple_variation_store(&mut buffer, 0, 1);
+    assert_eq!(variations.len(), 1);
+    assert_eq!(variations[0].peak_tuple[0], 0x4000);
+    assert_eq!(variations[0].point_numbers, None);
+    assert_eq!(variations[0].deltas, vec![7]);
+}
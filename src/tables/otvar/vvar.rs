@@ -0,0 +1,87 @@
+use crate::error::FontError;
+use crate::font::Font;
+use crate::tables::otvar::item_variation_store::{DeltaSetIndexMap, ItemVariationStore};
+use crate::types::F2Dot14;
+use crate::util::Buffer;
+
+/// ## `VVAR` &mdash; Vertical Metrics Variations Table
+///
+/// Specification: <https://docs.microsoft.com/en-us/typography/opentype/spec/vvar>.
+///
+/// The `VVAR` table is used in variable fonts to provide variations for
+/// vertical glyph metric values. This can be used to provide variation data
+/// for advance heights in the `vmtx` table. In fonts with TrueType outlines, it
+/// can also be used to provide variation data for top and bottom side bearings
+/// obtained from the `vmtx` table and glyph bounding box. In addition, it can be
+/// used in fonts that have CFF2 outlines to provide vertical-origin variation
+/// data.
+#[allow(non_camel_case_types)]
+#[derive(Debug)]
+pub struct Table_VVAR {
+    version: String,
+    pub item_variation_store: ItemVariationStore,
+    pub advance_height_mapping: Option<DeltaSetIndexMap>,
+    pub tsb_mapping: Option<DeltaSetIndexMap>,
+    pub bsb_mapping: Option<DeltaSetIndexMap>,
+    pub v_org_mapping: Option<DeltaSetIndexMap>,
+}
+
+impl Font {
+    #[allow(non_snake_case)]
+    pub fn parse_VVAR(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let vvar_start = buffer.offset();
+        let version = buffer.get_version::<u16>();
+        let item_variation_store_offset: u32 = buffer.get();
+        let advance_height_mapping_offset: u32 = buffer.get();
+        let tsb_mapping_offset: u32 = buffer.get();
+        let bsb_mapping_offset: u32 = buffer.get();
+        let v_org_mapping_offset: u32 = buffer.get();
+
+        buffer.set_offset_from(vvar_start, item_variation_store_offset);
+        self.VVAR = Some(Table_VVAR {
+            version,
+            item_variation_store: buffer.get(),
+            advance_height_mapping: buffer.get_or_none(vvar_start, advance_height_mapping_offset),
+            tsb_mapping: buffer.get_or_none(vvar_start, tsb_mapping_offset),
+            bsb_mapping: buffer.get_or_none(vvar_start, bsb_mapping_offset),
+            v_org_mapping: buffer.get_or_none(vvar_start, v_org_mapping_offset),
+        });
+        Ok(())
+    }
+}
+
+impl Table_VVAR {
+    /// Return the advance height delta of `glyph_id` at the normalized coordinates `coords`.
+    /// Without an advance height mapping, glyph IDs are used directly as inner indices of the
+    /// first item variation data. Return `None` if there is no variation data for the glyph.
+    pub fn advance_height_delta(&self, glyph_id: u16, coords: &[F2Dot14]) -> Option<f32> {
+        self.item_variation_store.glyph_delta(
+            self.advance_height_mapping.as_ref(),
+            glyph_id,
+            coords,
+        )
+    }
+}
+
+#[test]
+fn test_advance_height_delta() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 1, 0, 0, 0, 0, 0, 24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // header
+        0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22, // item variation store
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0, // region 0: [0, 1, 1]
+        0, 2, 0, 0, 0, 1, 0, 0, 10, 0xEC, // data 0: deltas 10, -20
+    ]);
+    let mut font = Font::default();
+    font.parse_VVAR(&mut buffer).unwrap();
+    let vvar = font.VVAR.unwrap();
+    assert_eq!(
+        vvar.advance_height_delta(0, &[F2Dot14::from(0.5)]),
+        Some(5.0)
+    );
+    assert_eq!(
+        vvar.advance_height_delta(1, &[F2Dot14::from(1.0)]),
+        Some(-20.0)
+    );
+    assert_eq!(vvar.advance_height_delta(2, &[F2Dot14::from(1.0)]), None);
+}
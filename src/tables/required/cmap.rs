@@ -1,8 +1,10 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::u24;
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
 
 /// ## `cmap` &mdash; Character to Glyph Index Mapping Table
 ///
@@ -11,26 +13,34 @@ use std::collections::HashMap;
 /// This table defines the mapping of character codes to the glyph index values
 /// used in the font. It may contain more than one subtable, in order to support
 /// more than one character encoding scheme.
-///
-/// TODO: map is planned to be a `HashMap` of `cid` => `gid`. Not finished yet.
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_cmap {
     version: u16,
     num_tables: u16,
     encodings: Vec<Encoding>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_pairs"))]
     subtables: HashMap<(u16, u16), CmapSubtable>,
+    /// Mappings from character codes to glyph IDs, of the subtables whose format is
+    /// supported.
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_pairs"))]
     pub maps: HashMap<Encoding, Map>,
+    /// Unicode variation sequences from the format 14 subtable, as
+    /// (code point, variation selector) => glyph ID. The value is `None` for default
+    /// variation sequences, which use the glyph of the code point in the Unicode subtable.
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_pairs"))]
+    pub variation_sequences: HashMap<(u32, u32), Option<u32>>,
 }
 
 impl Font {
-    pub fn parse_cmap(&mut self, buffer: &mut Buffer) {
+    pub fn parse_cmap(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let start = buffer.offset();
         let version = buffer.get();
         let num_tables = buffer.get();
         let encodings: Vec<Encoding> = buffer.get_vec(num_tables);
-        let subtables = encodings
+        let mut subtables: HashMap<(u16, u16), CmapSubtable> = encodings
             .iter()
             .map(|i| {
                 buffer.set_offset_from(start, i.offset);
@@ -38,8 +48,20 @@ impl Font {
             })
             .collect();
 
-        // TODO: parse maps
-        let maps: HashMap<Encoding, Map> = HashMap::new();
+        // Move the maps out of the subtables, so that they are not stored twice
+        let mut maps = HashMap::new();
+        let mut variation_sequences = HashMap::new();
+        for encoding in &encodings {
+            let key = (encoding.platform_id, encoding.encoding_id);
+            if let Some(subtable) = subtables.get_mut(&key) {
+                if let Some(map) = subtable.map_mut() {
+                    maps.insert(encoding.clone(), std::mem::take(map));
+                }
+                if let Some(data) = subtable.format_14_data.as_mut() {
+                    variation_sequences.extend(data.variation_sequences.drain());
+                }
+            }
+        }
 
         self.cmap = Some(Table_cmap {
             version,
@@ -47,11 +69,135 @@ impl Font {
             encodings,
             subtables,
             maps,
+            variation_sequences,
         });
+        Ok(())
+    }
+
+    /// Return the mapping from Unicode code points to glyph IDs, or `None` if the `cmap`
+    /// table is not parsed or there is no Unicode subtable. The Windows subtables (3, 10)
+    /// and (3, 1) are preferred to those of the Unicode platform (0).
+    pub fn char_map(&self) -> Option<&HashMap<u32, u32>> {
+        self.cmap.as_ref()?.unicode_map()
+    }
+
+    /// Return the glyph ID of the Unicode `codepoint`, or `None` if the `cmap` table is not
+    /// parsed or the code point is not mapped. See [`Table_cmap::lookup`].
+    pub fn glyph_id_for_codepoint(&self, codepoint: u32) -> Option<u32> {
+        self.cmap.as_ref()?.lookup(codepoint)
+    }
+
+    /// Return the sorted code points mapped to `gid`, using the same subtable as
+    /// [`Font::glyph_id_for_codepoint`].
+    pub fn codepoints_for_glyph_id(&self, gid: u32) -> Vec<u32> {
+        let map = match self.cmap.as_ref().and_then(Table_cmap::unicode_map) {
+            Some(map) => map,
+            None => return Vec::new(),
+        };
+        let mut codepoints: Vec<u32> = map
+            .iter()
+            .filter(|(_, &glyph_id)| glyph_id == gid)
+            .map(|(&codepoint, _)| codepoint)
+            .collect();
+        codepoints.sort_unstable();
+        codepoints
+    }
+
+    /// Return the sorted and merged inclusive ranges of the Unicode code points that are
+    /// mapped to a non-zero glyph ID. Return an empty vector if there is no Unicode subtable.
+    pub fn all_unicode_ranges(&self) -> Vec<(u32, u32)> {
+        let mut code_points: Vec<u32> = match self.char_map() {
+            Some(map) => map
+                .iter()
+                .filter(|(_, &gid)| gid != 0)
+                .map(|(&cid, _)| cid)
+                .collect(),
+            None => return Vec::new(),
+        };
+        code_points.sort_unstable();
+
+        let mut ranges: Vec<(u32, u32)> = Vec::new();
+        for cid in code_points {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == cid => *end = cid,
+                _ => ranges.push((cid, cid)),
+            }
+        }
+        ranges
     }
 }
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq, ReadBuffer)]
+impl Table_cmap {
+    /// Encodings of Unicode subtables, in the order of preference: the Windows subtables
+    /// (full repertoire first), and then the Unicode platform ones.
+    const UNICODE_ENCODINGS: &'static [(u16, u16)] = &[
+        (3, 10),
+        (3, 1),
+        (0, 6),
+        (0, 4),
+        (0, 3),
+        (0, 2),
+        (0, 1),
+        (0, 0),
+    ];
+
+    /// Return all the encoding records of the table.
+    pub fn all_encodings(&self) -> &[Encoding] {
+        &self.encodings
+    }
+
+    /// Return the encoding record with the largest repertoire among those of `platform_id`.
+    /// For platforms other than Unicode (0), Macintosh (1) and Windows (3), the first
+    /// encoding of the platform is returned.
+    pub fn best_encoding_for_platform(&self, platform_id: u16) -> Option<&Encoding> {
+        let preferred_encodings: &[u16] = match platform_id {
+            0 => &[6, 4, 3, 2, 1, 0],
+            1 => &[0],
+            3 => &[10, 1, 0],
+            _ => {
+                return self
+                    .encodings
+                    .iter()
+                    .find(|rec| rec.platform_id == platform_id)
+            }
+        };
+        preferred_encodings.iter().find_map(|&encoding_id| {
+            self.encodings
+                .iter()
+                .find(|rec| rec.platform_id == platform_id && rec.encoding_id == encoding_id)
+        })
+    }
+
+    /// Return the format of the subtable of (`platform_id`, `encoding_id`).
+    pub fn subtable_format(&self, platform_id: u16, encoding_id: u16) -> Option<u16> {
+        self.subtables
+            .get(&(platform_id, encoding_id))
+            .map(|subtable| subtable.format)
+    }
+
+    /// Return the glyph ID of the Unicode `codepoint`. The subtable is selected in the same
+    /// order of preference as [`Font::char_map`], i.e. (3, 10) first, then (3, 1), and then
+    /// the subtables of the Unicode platform (0).
+    pub fn lookup(&self, codepoint: u32) -> Option<u32> {
+        self.unicode_map()?.get(&codepoint).copied()
+    }
+
+    fn map(&self, (platform_id, encoding_id): (u16, u16)) -> Option<&Map> {
+        self.encodings
+            .iter()
+            .find(|rec| rec.platform_id == platform_id && rec.encoding_id == encoding_id)
+            .and_then(|rec| self.maps.get(rec))
+    }
+
+    fn unicode_map(&self) -> Option<&Map> {
+        Self::UNICODE_ENCODINGS
+            .iter()
+            .find_map(|&key| self.map(key))
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Encoding {
     pub platform_id: u16,
     pub encoding_id: u16,
@@ -59,6 +205,7 @@ pub struct Encoding {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapSubtable {
     format: u16,
     format_0_data: Option<CmapFormat0>,
@@ -72,6 +219,20 @@ struct CmapSubtable {
     format_14_data: Option<CmapFormat14>,
 }
 
+impl CmapSubtable {
+    fn map_mut(&mut self) -> Option<&mut Map> {
+        match self.format {
+            0 => self.format_0_data.as_mut().map(|data| &mut data.map),
+            4 => self.format_4_data.as_mut().map(|data| &mut data.map),
+            6 => self.format_6_data.as_mut().map(|data| &mut data.map),
+            10 => self.format_10_data.as_mut().map(|data| &mut data.map),
+            12 => self.format_12_data.as_mut().map(|data| &mut data.map),
+            13 => self.format_13_data.as_mut().map(|data| &mut data.map),
+            _ => None,
+        }
+    }
+}
+
 impl ReadBuffer for CmapSubtable {
     fn read(buffer: &mut Buffer) -> Self {
         let mut subtable = CmapSubtable {
@@ -88,13 +249,14 @@ impl ReadBuffer for CmapSubtable {
             12 => subtable.format_12_data = Some(buffer.get()),
             13 => subtable.format_13_data = Some(buffer.get()),
             14 => subtable.format_14_data = Some(buffer.get()),
-            _ => unreachable!(),
+            format => buffer.set_unknown_format(format),
         }
         subtable
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat0 {
     length: u16,
     language: u16,
@@ -121,6 +283,7 @@ impl ReadBuffer for CmapFormat0 {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat2 {
     length: u16,
     language: u16,
@@ -169,6 +332,7 @@ impl ReadBuffer for CmapFormat2 {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat4 {
     length: u16,
     language: u16,
@@ -218,12 +382,15 @@ impl ReadBuffer for CmapFormat4 {
                             // ... plus some offset
                             + (id_range_offset[i] as u32 + (c - start) * 2) as usize,
                         );
-                        ((buffer.get::<u16>() as i32 + id_delta[i] as i32) % 0xFFFF) as u32
+                        match buffer.get::<u16>() {
+                            0 => 0,
+                            gid => (gid as i32 + id_delta[i] as i32) as u32 & 0xFFFF,
+                        }
                     })
                     .collect()
             } else {
                 filtered_char_range
-                    .map(|c| ((c as i32 + id_delta[i] as i32) % 0xFFFF) as u32)
+                    .map(|c| (c as i32 + id_delta[i] as i32) as u32 & 0xFFFF)
                     .collect()
             };
             gid_seg_array.push(gid_seg.to_vec());
@@ -249,6 +416,7 @@ impl ReadBuffer for CmapFormat4 {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat6 {
     length: u16,
     language: u16,
@@ -264,19 +432,24 @@ impl ReadBuffer for CmapFormat6 {
         let language = buffer.get();
         let start_char_code = buffer.get();
         let entry_count = buffer.get();
-        let gid_array = buffer.get_vec(entry_count);
+        let gid_array: Vec<u16> = buffer.get_vec(entry_count);
+        let map = (u32::from(start_char_code)..)
+            .zip(gid_array.iter())
+            .map(|(cid, &gid)| (cid, u32::from(gid)))
+            .collect();
         Self {
             length,
             language,
             start_char_code,
             entry_count,
             gid_array,
-            map: Map::new(),
+            map,
         }
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat8 {
     length: u32,
     language: u32,
@@ -288,6 +461,8 @@ struct CmapFormat8 {
 
 impl ReadBuffer for CmapFormat8 {
     fn read(buffer: &mut Buffer) -> Self {
+        // The `reserved` field makes the format a 32-bit one. It is not padding: subtables are
+        // not necessarily 4-byte aligned, so `Buffer::align4` may skip nothing here.
         buffer.skip::<u16>(1);
         let length = buffer.get();
         let language = buffer.get();
@@ -306,6 +481,7 @@ impl ReadBuffer for CmapFormat8 {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat10 {
     length: u32,
     language: u32,
@@ -317,24 +493,29 @@ struct CmapFormat10 {
 
 impl ReadBuffer for CmapFormat10 {
     fn read(buffer: &mut Buffer) -> Self {
-        buffer.skip::<u16>(1);
+        buffer.skip::<u16>(1); // reserved
         let length = buffer.get();
         let language = buffer.get();
         let start_char_code = buffer.get();
         let entry_count = buffer.get();
-        let gid_array = buffer.get_vec(entry_count);
+        let gid_array: Vec<u16> = buffer.get_vec(entry_count);
+        let map = (start_char_code..)
+            .zip(gid_array.iter())
+            .map(|(cid, &gid)| (cid, u32::from(gid)))
+            .collect();
         Self {
             length,
             language,
             start_char_code,
             entry_count,
             gid_array,
-            map: Map::new(),
+            map,
         }
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat12 {
     length: u32,
     language: u32,
@@ -345,22 +526,35 @@ struct CmapFormat12 {
 
 impl ReadBuffer for CmapFormat12 {
     fn read(buffer: &mut Buffer) -> Self {
-        buffer.skip::<u16>(1);
+        buffer.skip::<u16>(1); // reserved
         let length = buffer.get();
         let language = buffer.get();
         let num_groups = buffer.get();
-        let groups = buffer.get_vec(num_groups);
+        let groups: Vec<SequentialMapGroup> = buffer.get_vec(num_groups);
+        let map = groups
+            .iter()
+            .flat_map(|group| {
+                char_code_range(group.start_char_code, group.end_char_code).map_while(move |cid| {
+                    let glyph_id = group
+                        .start_glyph_id
+                        .checked_add(cid - group.start_char_code)?;
+                    Some((cid, glyph_id))
+                })
+            })
+            .take(MAX_MAP_LEN)
+            .collect();
         Self {
             length,
             language,
             num_groups,
             groups,
-            map: Map::new(),
+            map,
         }
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat13 {
     length: u32,
     language: u32,
@@ -371,44 +565,83 @@ struct CmapFormat13 {
 
 impl ReadBuffer for CmapFormat13 {
     fn read(buffer: &mut Buffer) -> Self {
-        buffer.skip::<u16>(1);
+        buffer.skip::<u16>(1); // reserved
         let length = buffer.get();
         let language = buffer.get();
         let num_groups = buffer.get();
-        let groups = buffer.get_vec(num_groups);
+        let groups: Vec<ConstantMapGroup> = buffer.get_vec(num_groups);
+        let map = groups
+            .iter()
+            .flat_map(|group| {
+                char_code_range(group.start_char_code, group.end_char_code)
+                    .map(move |cid| (cid, group.glyph_id))
+            })
+            .take(MAX_MAP_LEN)
+            .collect();
         Self {
             length,
             language,
             num_groups,
             groups,
-            map: Map::new(),
+            map,
         }
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct CmapFormat14 {
     length: u32,
     num_var_selectors: u32,
     var_selectors: Vec<VariationSelector>,
-    map: Map,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_pairs"))]
+    variation_sequences: HashMap<(u32, u32), Option<u32>>,
 }
 
 impl ReadBuffer for CmapFormat14 {
     fn read(buffer: &mut Buffer) -> Self {
+        // The format field has already been read
+        let start = buffer.offset() - 2;
         let length = buffer.get();
         let num_var_selectors = buffer.get();
-        let var_selectors = buffer.get_vec(num_var_selectors);
+        let var_selectors: Vec<VariationSelector> = buffer.get_vec(num_var_selectors);
+
+        let mut variation_sequences = HashMap::new();
+        for rec in &var_selectors {
+            let selector = u32::from(rec.var_selector);
+            if rec.default_uvs_offset != 0 {
+                buffer.set_offset_from(start, rec.default_uvs_offset);
+                let num_unicode_value_ranges: u32 = buffer.get();
+                for _ in 0..num_unicode_value_ranges {
+                    let start_unicode_value = u32::from(buffer.get::<u24>());
+                    let additional_count: u8 = buffer.get();
+                    for cid in start_unicode_value..=start_unicode_value + additional_count as u32 {
+                        variation_sequences.insert((cid, selector), None);
+                    }
+                }
+            }
+            if rec.non_default_uvs_offset != 0 {
+                buffer.set_offset_from(start, rec.non_default_uvs_offset);
+                let num_uvs_mappings: u32 = buffer.get();
+                for _ in 0..num_uvs_mappings {
+                    let unicode_value = u32::from(buffer.get::<u24>());
+                    let glyph_id: u16 = buffer.get();
+                    variation_sequences.insert((unicode_value, selector), Some(glyph_id as u32));
+                }
+            }
+        }
+
         Self {
             length,
             num_var_selectors,
             var_selectors,
-            map: Map::new(),
+            variation_sequences,
         }
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct SubHeader {
     first_code: u16,
     entry_count: u16,
@@ -418,6 +651,7 @@ struct SubHeader {
 }
 
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct SequentialMapGroup {
     start_char_code: u32,
     end_char_code: u32,
@@ -425,6 +659,7 @@ struct SequentialMapGroup {
 }
 
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct ConstantMapGroup {
     start_char_code: u32,
     end_char_code: u32,
@@ -432,6 +667,7 @@ struct ConstantMapGroup {
 }
 
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct VariationSelector {
     var_selector: u24,
     default_uvs_offset: u32,
@@ -439,3 +675,75 @@ struct VariationSelector {
 }
 
 type Map = HashMap<u32, u32>;
+
+/// Maximum number of entries expanded from the groups of a format 12 or 13 subtable, i.e.
+/// the size of the Unicode code space. Valid groups do not overlap, so they never reach it;
+/// the limit bounds the work for malformed subtables with many large groups.
+const MAX_MAP_LEN: usize = 0x110000;
+
+/// Return the character codes of a group in format 12 or 13, which are limited to the Unicode
+/// range. A group with `start > end` is empty.
+fn char_code_range(start: u32, end: u32) -> RangeInclusive<u32> {
+    const MAX_CHAR_CODE: u32 = 0x10FFFF;
+    start..=end.min(MAX_CHAR_CODE)
+}
+
+#[test]
+fn test_cmap_format_14() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 14, 0, 0, 0, 39, 0, 0, 0, 1, // format, length, 1 selector
+        0x00, 0xFE, 0x00, 0, 0, 0, 21, 0, 0, 0, 29, // U+FE00
+        0, 0, 0, 1, 0x00, 0x4E, 0x00, 1, // default: U+4E00..U+4E01
+        0, 0, 0, 1, 0x00, 0x8F, 0xBB, 0, 42, // non-default: U+8FBB => 42
+    ]);
+    let subtable: CmapSubtable = buffer.get();
+    let variation_sequences = subtable.format_14_data.unwrap().variation_sequences;
+    assert_eq!(variation_sequences.len(), 3);
+    assert_eq!(variation_sequences[&(0x4E01, 0xFE00)], None);
+    assert_eq!(variation_sequences[&(0x8FBB, 0xFE00)], Some(42));
+}
+
+#[test]
+fn test_cmap_format_12_invalid_groups() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 0, 0, 0, 0, 52, 0, 0, 0, 0, 0, 0, 0, 3, // reserved, length, language, 3 groups
+        0, 0, 0, 0x41, 0, 0, 0, 0x42, 0, 0, 0, 10, // U+0041..U+0042 => 10
+        0, 0, 0, 0x50, 0, 0, 0, 0x40, 0, 0, 0, 20, // start > end
+        0, 0x10, 0xFF, 0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // beyond U+10FFFF
+    ]);
+    let subtable: CmapFormat12 = buffer.get();
+    assert_eq!(subtable.map.len(), 3);
+    assert_eq!(subtable.map[&0x42], 11);
+    // The glyph ID of U+10FFFF would overflow
+    assert_eq!(subtable.map[&0x10FFFE], 0xFFFF_FFFF);
+    assert!(!subtable.map.contains_key(&0x10FFFF));
+}
+
+#[test]
+fn test_cmap_format_13_overlapping_groups() {
+    // 3 groups of the whole code space, which would expand to 3 * 0x110000 entries
+    let mut data = vec![0, 0, 0, 0, 0, 52, 0, 0, 0, 0, 0, 0, 0, 3];
+    for glyph_id in 1..=3 {
+        data.extend(&[0, 0, 0, 0, 0, 0x10, 0xFF, 0xFF, 0, 0, 0, glyph_id]);
+    }
+    let subtable: CmapFormat13 = Buffer::new(data).get();
+    assert_eq!(subtable.map.len(), MAX_MAP_LEN);
+    assert_eq!(subtable.map[&0x10FFFF], 1);
+}
+
+#[test]
+fn test_unicode_encoding_priority() {
+    #[rustfmt::skip]
+    let mut buffer = Buffer::new(vec![
+        0, 0, 0, 2, // version, 2 tables
+        0, 0, 0, 4, 0, 0, 0, 20, // (0, 4)
+        0, 3, 0, 1, 0, 0, 0, 32, // (3, 1)
+        0, 6, 0, 12, 0, 0, 0, 0x41, 0, 1, 0, 5, // format 6: U+0041 => 5
+        0, 6, 0, 12, 0, 0, 0, 0x41, 0, 1, 0, 7, // format 6: U+0041 => 7
+    ]);
+    let mut font = Font::default();
+    font.parse_cmap(&mut buffer).unwrap();
+    assert_eq!(font.glyph_id_for_codepoint(0x41), Some(7));
+}
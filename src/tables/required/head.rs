@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::{Fixed, LongDateTime};
 use crate::util::Buffer;
@@ -12,6 +13,7 @@ use crate::util::Buffer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_head {
     version: String,
     pub font_revision: Fixed,
@@ -33,8 +35,8 @@ pub struct Table_head {
 }
 
 impl Font {
-    pub fn parse_head(&mut self, buffer: &mut Buffer) {
-        self.head = Some(Table_head {
+    pub fn parse_head(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let head = Table_head {
             version: buffer.get_version::<u16>(),
             font_revision: buffer.get(),
             checksum_adjustment: buffer.get(),
@@ -52,6 +54,23 @@ impl Font {
             font_direction_hint: buffer.get(),
             index_to_loc_format: buffer.get(),
             glyph_data_format: buffer.get(),
-        });
+        };
+        self.head = Some(head);
+        Ok(())
+    }
+}
+
+impl Table_head {
+    /// The `magic_number` field MUST be set to this value.
+    pub const MAGIC_NUMBER: u32 = 0x5F0F_3CF5;
+
+    /// Whether the `loca` table uses short (`Offset16`) offsets.
+    pub const fn is_short_loca(&self) -> bool {
+        self.index_to_loc_format == 0
+    }
+
+    /// Whether the `loca` table uses long (`Offset32`) offsets.
+    pub const fn is_long_loca(&self) -> bool {
+        self.index_to_loc_format == 1
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::Buffer;
 
@@ -13,6 +14,7 @@ use crate::util::Buffer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_hhea {
     version: String,
     pub ascender: i16,
@@ -31,7 +33,7 @@ pub struct Table_hhea {
 }
 
 impl Font {
-    pub fn parse_hhea(&mut self, buffer: &mut Buffer) {
+    pub fn parse_hhea(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         self.hhea = Some(Table_hhea {
             version: buffer.get_version::<u16>(),
             ascender: buffer.get(),
@@ -50,5 +52,6 @@ impl Font {
             },
             num_hor_metrics: buffer.get(),
         });
+        Ok(())
     }
 }
@@ -1,6 +1,9 @@
+use crate::error::FontError;
 use crate::font::Font;
+use crate::types::Tag;
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
+use std::convert::TryFrom;
 
 /// ## `hmtx` &mdash; Horizontal Metrics Table
 ///
@@ -14,23 +17,67 @@ use read_buffer_derive::ReadBuffer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_hmtx {
     pub hor_metrics: Vec<LongHorMetric>,
     pub left_side_bearings: Vec<i16>,
 }
 
 impl Font {
-    pub fn parse_hmtx(&mut self, buffer: &mut Buffer) {
-        let num_hor_metrics = self.hhea.as_ref().unwrap().num_hor_metrics;
-        let num_glyphs = self.maxp.as_ref().unwrap().num_glyphs;
+    pub fn parse_hmtx(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let num_hor_metrics = self
+            .hhea
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"hhea")))?
+            .num_hor_metrics;
+        let num_glyphs = self
+            .maxp
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"maxp")))?
+            .num_glyphs;
         self.hmtx = Some(Table_hmtx {
             hor_metrics: buffer.get_vec(num_hor_metrics),
-            left_side_bearings: buffer.get_vec(num_glyphs - num_hor_metrics),
+            left_side_bearings: buffer.get_vec(num_glyphs.saturating_sub(num_hor_metrics)),
         });
+        Ok(())
+    }
+
+    /// Return the advance width of `glyph_id`, or `None` if `hmtx` is not parsed or the
+    /// glyph is out of range.
+    pub fn advance_width(&self, glyph_id: usize) -> Option<u16> {
+        Some(self.hor_metric(glyph_id)?.0)
+    }
+
+    /// Return the left side bearing of `glyph_id`, or `None` if `hmtx` is not parsed or
+    /// the glyph is out of range.
+    pub fn lsb(&self, glyph_id: usize) -> Option<i16> {
+        Some(self.hor_metric(glyph_id)?.1)
+    }
+
+    fn hor_metric(&self, glyph_id: usize) -> Option<(u16, i16)> {
+        let glyph_id = u16::try_from(glyph_id).ok()?;
+        self.hmtx.as_ref()?.metric(glyph_id)
+    }
+}
+
+impl Table_hmtx {
+    /// Return the `(advance_width, left_side_bearing)` pair of `glyph_id`. Glyphs after the
+    /// last long metric record share its advance width.
+    pub fn metric(&self, glyph_id: u16) -> Option<(u16, i16)> {
+        let i = glyph_id as usize;
+        match self.hor_metrics.get(i) {
+            Some(rec) => Some((rec.advance_width, rec.left_side_bearing)),
+            None => {
+                let advance_width = self.hor_metrics.last()?.advance_width;
+                let lsb = *self.left_side_bearings.get(i - self.hor_metrics.len())?;
+                Some((advance_width, lsb))
+            }
+        }
     }
 }
 
 #[derive(Debug, ReadBuffer)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LongHorMetric {
     advance_width: u16,
     left_side_bearing: i16,
@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Fixed;
 use crate::util::Buffer;
@@ -13,6 +14,7 @@ use crate::util::Buffer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_maxp {
     version: Fixed,
     pub num_glyphs: u16,
@@ -32,14 +34,13 @@ pub struct Table_maxp {
 }
 
 impl Font {
-    pub fn parse_maxp(&mut self, buffer: &mut Buffer) {
+    pub fn parse_maxp(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let mut table = Table_maxp {
             version: buffer.get(),
             num_glyphs: buffer.get(),
             ..Default::default()
         };
-        // Version 1.0
-        if table.version == 0x0001_0000 {
+        if table.is_version_10() {
             table.max_points = Some(buffer.get());
             table.max_contours = Some(buffer.get());
             table.max_composite_points = Some(buffer.get());
@@ -55,5 +56,90 @@ impl Font {
             table.max_component_depth = Some(buffer.get());
         }
         self.maxp = Some(table);
+        Ok(())
+    }
+}
+
+impl Table_maxp {
+    /// Version 0.5, which only contains `num_glyphs`. It is used by fonts with CFF data.
+    pub fn is_version_05(&self) -> bool {
+        self.version == 0x0000_5000
+    }
+
+    /// Version 1.0, which is used by fonts with TrueType outlines.
+    pub fn is_version_10(&self) -> bool {
+        self.version == 0x0001_0000
+    }
+
+    /// The number of glyphs in the font, which is present in all versions.
+    pub const fn num_glyphs(&self) -> u16 {
+        self.num_glyphs
+    }
+
+    // The fields below are only present in version 1.0.
+
+    /// Maximum points in a non-composite glyph.
+    pub const fn max_points(&self) -> Option<u16> {
+        self.max_points
+    }
+
+    /// Maximum contours in a non-composite glyph.
+    pub const fn max_contours(&self) -> Option<u16> {
+        self.max_contours
+    }
+
+    /// Maximum points in a composite glyph.
+    pub const fn max_composite_points(&self) -> Option<u16> {
+        self.max_composite_points
+    }
+
+    /// Maximum contours in a composite glyph.
+    pub const fn max_composite_contours(&self) -> Option<u16> {
+        self.max_composite_contours
+    }
+
+    /// 1 if instructions do not use the twilight zone (Z0), or 2 if instructions do use Z0.
+    pub const fn max_zones(&self) -> Option<u16> {
+        self.max_zones
+    }
+
+    /// Maximum points used in Z0.
+    pub const fn max_twilight_points(&self) -> Option<u16> {
+        self.max_twilight_points
+    }
+
+    /// Number of Storage Area locations.
+    pub const fn max_storage(&self) -> Option<u16> {
+        self.max_storage
+    }
+
+    /// Number of FDEFs, equal to the highest function number + 1.
+    pub const fn max_function_defs(&self) -> Option<u16> {
+        self.max_function_defs
+    }
+
+    /// Number of IDEFs.
+    pub const fn max_instruction_defs(&self) -> Option<u16> {
+        self.max_instruction_defs
+    }
+
+    /// Maximum stack depth across Font Program, CVT Program and all glyph instructions.
+    pub const fn max_stack_elements(&self) -> Option<u16> {
+        self.max_stack_elements
+    }
+
+    /// Maximum byte count for glyph instructions.
+    pub const fn max_size_of_instructions(&self) -> Option<u16> {
+        self.max_size_of_instructions
+    }
+
+    /// Maximum number of components referenced at "top level" for any composite glyph.
+    pub const fn max_component_elements(&self) -> Option<u16> {
+        self.max_component_elements
+    }
+
+    /// Maximum levels of recursion; 1 for simple components.
+    pub const fn max_component_depth(&self) -> Option<u16> {
+        self.max_component_depth
     }
 }
@@ -1,5 +1,7 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::{Buffer, ReadBuffer};
+use std::fmt;
 
 use encoding_rs::{BIG5, EUC_KR, GB18030, GBK, MACINTOSH, SHIFT_JIS, UTF_16BE, X_MAC_CYRILLIC};
 
@@ -26,6 +28,7 @@ use encoding_rs::{BIG5, EUC_KR, GB18030, GBK, MACINTOSH, SHIFT_JIS, UTF_16BE, X_
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_name {
     format: u16,
     count: u16,
@@ -36,7 +39,8 @@ pub struct Table_name {
 }
 
 impl Font {
-    pub fn parse_name(&mut self, buffer: &mut Buffer) {
+    pub fn parse_name(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let name_start = buffer.offset();
         let format = buffer.get();
         let count = buffer.get();
         let string_offset = buffer.get();
@@ -54,13 +58,99 @@ impl Font {
             table.lang_tag_count = Some(lang_tag_count);
             table.lang_tags = Some(lang_tags);
         };
+        buffer.set_offset_from(name_start, string_offset);
         table.names.iter_mut().for_each(|x| x.parse(buffer));
+        if let Some(lang_tags) = table.lang_tags.as_mut() {
+            lang_tags.iter_mut().for_each(|x| x.parse(buffer));
+        }
+        // The sort is stable, so records with the same priority keep their order in the font.
+        table.names.sort_by_key(|name| name.platform_priority());
         self.name = Some(table);
+        Ok(())
+    }
+
+    /// Return the preferred record of `name_id` in the `name` table.
+    /// See [`Table_name::name_record`].
+    pub fn name_record(&self, name_id: u16) -> Option<&Name> {
+        self.name.as_ref()?.name_record(name_id)
+    }
+}
+
+impl Table_name {
+    /// Language IDs from `0x8000` refer to the language tag records.
+    const FIRST_LANG_TAG_ID: u16 = 0x8000;
+
+    /// Return the preferred record of `name_id`, according to the platform and encoding
+    /// (see [`Name::platform_priority`]). Records with empty strings are skipped, since some
+    /// fonts only have the actual names in the lower priority platforms.
+    pub fn name_record(&self, name_id: u16) -> Option<&Name> {
+        self.names
+            .iter()
+            .find(|name| name.name_id == name_id && !name.string.is_empty())
+    }
+
+    /// Return the string of the preferred record of `name_id`. See [`Table_name::name_record`].
+    pub fn best_string(&self, name_id: u16) -> Option<String> {
+        self.name_record(name_id).map(|name| name.string.clone())
+    }
+
+    /// Return the family name (name ID 1).
+    pub fn family_name(&self) -> Option<String> {
+        self.best_string(1)
+    }
+
+    /// Return the subfamily name (name ID 2).
+    pub fn subfamily_name(&self) -> Option<String> {
+        self.best_string(2)
+    }
+
+    /// Return the full font name (name ID 4).
+    pub fn full_name(&self) -> Option<String> {
+        self.best_string(4)
+    }
+
+    /// Return the PostScript name (name ID 6).
+    pub fn postscript_name(&self) -> Option<String> {
+        self.best_string(6)
+    }
+
+    /// Return the language tags of a format 1 table as `(language_id, tag)` pairs, where
+    /// the language IDs start from `0x8000`.
+    pub fn lang_tags(&self) -> Vec<(u16, String)> {
+        self.lang_tags.as_ref().map_or_else(Vec::new, |lang_tags| {
+            lang_tags
+                .iter()
+                .zip(Self::FIRST_LANG_TAG_ID..)
+                .map(|(lang_tag, id)| (id, lang_tag.tag.clone()))
+                .collect()
+        })
+    }
+
+    /// Return the IETF BCP 47 language tag at `index` of the language tag records.
+    pub fn lang_tag_string(&self, index: u16) -> Option<&str> {
+        self.lang_tags
+            .as_ref()?
+            .get(index as usize)
+            .map(|lang_tag| lang_tag.tag.as_str())
+    }
+
+    /// Return all the name records whose language is given by the language tag `tag`.
+    pub fn names_for_lang_tag(&self, tag: &str) -> Vec<&Name> {
+        self.names
+            .iter()
+            .filter(|name| {
+                name.language_id
+                    .checked_sub(Self::FIRST_LANG_TAG_ID)
+                    .and_then(|i| self.lang_tag_string(i))
+                    == Some(tag)
+            })
+            .collect()
     }
 }
 
 #[derive(Debug, Default)]
-struct Name {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Name {
     pub platform_id: u16,
     pub encoding_id: u16,
     pub language_id: u16,
@@ -71,6 +161,64 @@ struct Name {
 }
 
 impl Name {
+    /// Return the description of a predefined name ID.
+    pub fn name_id_description(id: u16) -> &'static str {
+        match id {
+            0 => "Copyright",
+            1 => "Family name",
+            2 => "Subfamily name",
+            3 => "Unique identifier",
+            4 => "Full font name",
+            5 => "Version string",
+            6 => "PostScript name",
+            7 => "Trademark",
+            8 => "Manufacturer name",
+            9 => "Designer",
+            10 => "Description",
+            11 => "Vendor URL",
+            12 => "Designer URL",
+            13 => "License description",
+            14 => "License info URL",
+            16 => "Typographic family name",
+            17 => "Typographic subfamily name",
+            18 => "Compatible full name",
+            19 => "Sample text",
+            20 => "PostScript CID findfont name",
+            21 => "WWS family name",
+            22 => "WWS subfamily name",
+            23 => "Light background palette",
+            24 => "Dark background palette",
+            25 => "Variations PostScript Name Prefix",
+            256..=32767 => "Font-specific",
+            _ => "Reserved",
+        }
+    }
+
+    /// Return the priority of the record when looking up a name, where a smaller value is
+    /// preferred: Windows Unicode full repertoire, Windows Unicode BMP, Macintosh Roman,
+    /// Unicode, and then all the others.
+    pub fn platform_priority(&self) -> u8 {
+        match (self.platform_id, self.encoding_id) {
+            (3, 10) => 0,
+            (3, 1) => 1,
+            (1, 0) => 2,
+            (0, _) => 3,
+            _ => 4,
+        }
+    }
+
+    /// Return the name of the platform of the record.
+    pub fn platform_name(&self) -> &'static str {
+        match self.platform_id {
+            0 => "Unicode",
+            1 => "Macintosh",
+            2 => "ISO",
+            3 => "Windows",
+            4 => "Custom",
+            _ => "Unknown",
+        }
+    }
+
     fn parse(&mut self, buffer: &mut Buffer) {
         let (start, end) = (self.offset, self.offset + self.length);
         let data = buffer.slice(start as usize, end as usize);
@@ -102,6 +250,31 @@ impl Name {
     }
 }
 
+/// Same as the debug output, but with the descriptions of the platform ID and the name ID.
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Name")
+            .field(
+                "platform_id",
+                &format_args!("{} ({})", self.platform_id, self.platform_name()),
+            )
+            .field("encoding_id", &self.encoding_id)
+            .field("language_id", &self.language_id)
+            .field(
+                "name_id",
+                &format_args!(
+                    "{} ({})",
+                    self.name_id,
+                    Self::name_id_description(self.name_id)
+                ),
+            )
+            .field("length", &self.length)
+            .field("offset", &self.offset)
+            .field("string", &self.string)
+            .finish()
+    }
+}
+
 impl ReadBuffer for Name {
     fn read(buffer: &mut Buffer) -> Self {
         Self {
@@ -116,13 +289,48 @@ impl ReadBuffer for Name {
     }
 }
 
+/// Show a name record with its `Display` output in a debug struct.
+struct DescribedName<'a>(&'a Name);
+
+impl fmt::Debug for DescribedName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
+
+/// Same as the debug output, but the name records are shown with their `Display` output.
+/// The alternate form (`{:#}`) is pretty-printed.
+impl fmt::Display for Table_name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<_> = self.names.iter().map(DescribedName).collect();
+        f.debug_struct("Table_name")
+            .field("format", &self.format)
+            .field("count", &self.count)
+            .field("string_offset", &self.string_offset)
+            .field("names", &names)
+            .field("lang_tag_count", &self.lang_tag_count)
+            .field("lang_tags", &self.lang_tags)
+            .finish()
+    }
+}
+
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct LangTag {
     length: u16,
     offset: u16,
     pub tag: String,
 }
 
+impl LangTag {
+    fn parse(&mut self, buffer: &mut Buffer) {
+        let (start, end) = (self.offset, self.offset + self.length);
+        let data = buffer.slice(start as usize, end as usize);
+        let (cow, _, _) = UTF_16BE.decode(data);
+        self.tag.push_str(&cow);
+    }
+}
+
 impl ReadBuffer for LangTag {
     fn read(buffer: &mut Buffer) -> Self {
         Self {
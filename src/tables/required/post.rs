@@ -1,6 +1,9 @@
+use crate::error::FontError;
 use crate::font::Font;
-use crate::types::Fixed;
+use crate::types::{Fixed, Tag};
 use crate::util::Buffer;
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// ## `post` &mdash; PostScript Table
 ///
@@ -12,6 +15,7 @@ use crate::util::Buffer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_post {
     version: Fixed,
     pub italic_angle: Fixed,
@@ -26,13 +30,15 @@ pub struct Table_post {
     pub num_glyphs: Option<u16>,
     // Version 2.0
     pub glyph_name_index: Option<Vec<u16>>,
-    pub names: Option<Vec<i8>>,
+    pub names: Option<Vec<String>>,
     // Version 2.5 (deprecated)
     pub offset: Option<Vec<i8>>,
+    glyph_ids: HashMap<String, u16>,
 }
 
 impl Font {
-    pub fn parse_post(&mut self, buffer: &mut Buffer) {
+    pub fn parse_post(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let start_offset = buffer.offset();
         let mut table = Table_post {
             version: buffer.get(),
             italic_angle: buffer.get(),
@@ -49,13 +55,110 @@ impl Font {
             let num_glyphs = buffer.get();
             table.num_glyphs = Some(num_glyphs);
             table.glyph_name_index = Some(buffer.get_vec(num_glyphs));
-            table.names = Some(buffer.get_vec(num_glyphs));
+            // For WOFF and WOFF2 fonts, the buffer only contains the decompressed table
+            let end = start_offset + self.get_table_len(Tag::new(b"post"));
+            let mut names = Vec::new();
+            while buffer.offset() < end {
+                names.push(buffer.get_pascal_string()?);
+            }
+            table.names = Some(names);
         }
         if table.version == 0x0002_5000 {
             let num_glyphs = buffer.get();
             table.num_glyphs = Some(num_glyphs);
             table.offset = Some(buffer.get_vec(num_glyphs));
         }
+        // Keep the first glyph of each name, for looking up glyphs by their names. The number
+        // of glyphs comes from the table itself, so that `maxp` need not be parsed first;
+        // version 1.0 tables name the 258 standard Macintosh glyphs.
+        let num_glyphs = match table.num_glyphs {
+            Some(num_glyphs) => num_glyphs,
+            None if table.version == 0x0001_0000 => STANDARD_MAC_GLYPH_NAMES.len() as u16,
+            None => 0,
+        };
+        let mut glyph_ids = HashMap::new();
+        (0..num_glyphs).for_each(|glyph_id| {
+            if let Some(name) = table.glyph_name(glyph_id) {
+                glyph_ids.entry(name.to_string()).or_insert(glyph_id);
+            }
+        });
+        table.glyph_ids = glyph_ids;
         self.post = Some(table);
+        Ok(())
+    }
+
+    /// Return the names of all the glyphs, from the `post` table or the charset of the `CFF `
+    /// table. Glyphs without names (e.g. in TrueType fonts with a version 3.0 `post` table)
+    /// are `None`.
+    pub fn all_glyph_names(&self) -> Vec<Option<String>> {
+        let num_glyphs = self.maxp.as_ref().map_or(0, |maxp| maxp.num_glyphs);
+        (0..num_glyphs)
+            .map(|glyph_id| self.stored_glyph_name(glyph_id).map(String::from))
+            .collect()
+    }
+
+    /// Return the name of `glyph_id`. The names in the `post` table are preferred to those
+    /// in the charset of the `CFF ` table; glyphs without names get generated ones like
+    /// `gid42`. Return `None` if the glyph is out of range.
+    pub fn glyph_name(&self, glyph_id: u16) -> Option<Cow<'_, str>> {
+        if let Some(maxp) = &self.maxp {
+            if glyph_id >= maxp.num_glyphs {
+                return None;
+            }
+        }
+        Some(match self.stored_glyph_name(glyph_id) {
+            Some(name) => Cow::Borrowed(name),
+            None => Cow::Owned(format!("gid{}", glyph_id)),
+        })
+    }
+
+    fn stored_glyph_name(&self, glyph_id: u16) -> Option<&str> {
+        self.post
+            .as_ref()
+            .and_then(|post| post.glyph_name(glyph_id))
+            .or_else(|| {
+                let cff_font = self.CFF_.as_ref()?.cff_fonts().first()?;
+                cff_font.glyph_name(glyph_id as usize)
+            })
     }
 }
+
+impl Table_post {
+    /// Return whether the font provides no glyph names, i.e. the table is of version 3.0.
+    pub fn is_no_name_font(&self) -> bool {
+        self.version == 0x0003_0000
+    }
+
+    /// Return the glyph name of `glyph_id`. Return `None` for version 3.0 tables, which
+    /// provide no glyph names.
+    pub fn glyph_name(&self, glyph_id: u16) -> Option<&str> {
+        const NUM_STANDARD_NAMES: u16 = 258;
+
+        let index = if self.version == 0x0001_0000 {
+            glyph_id
+        } else if self.version == 0x0002_0000 {
+            *self.glyph_name_index.as_ref()?.get(glyph_id as usize)?
+        } else if self.version == 0x0002_5000 {
+            let offset = *self.offset.as_ref()?.get(glyph_id as usize)?;
+            (glyph_id as i32 + offset as i32) as u16
+        } else {
+            return None;
+        };
+        match index.checked_sub(NUM_STANDARD_NAMES) {
+            None => Some(STANDARD_MAC_GLYPH_NAMES[index as usize]),
+            Some(i) => self.names.as_ref()?.get(i as usize).map(String::as_str),
+        }
+    }
+
+    /// Return the first glyph named `name`.
+    pub fn glyph_id(&self, name: &str) -> Option<u16> {
+        self.glyph_ids.get(name).copied()
+    }
+}
+
+/// The standard Macintosh glyph names (length = 258), used by version 1.0 tables and by
+/// the glyph name indices below 258 in version 2.0 tables.
+#[rustfmt::skip]
+const STANDARD_MAC_GLYPH_NAMES: &[&str] = &[
+    ".notdef", ".null", "nonmarkingreturn", "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand", "quotesingle", "parenleft", "parenright", "asterisk", "plus", "comma", "hyphen", "period", "slash", "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "colon", "semicolon", "less", "equal", "greater", "question", "at", "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S", "T", "U", "V", "W", "X", "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum", "underscore", "grave", "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s", "t", "u", "v", "w", "x", "y", "z", "braceleft", "bar", "braceright", "asciitilde", "Adieresis", "Aring", "Ccedilla", "Eacute", "Ntilde", "Odieresis", "Udieresis", "aacute", "agrave", "acircumflex", "adieresis", "atilde", "aring", "ccedilla", "eacute", "egrave", "ecircumflex", "edieresis", "iacute", "igrave", "icircumflex", "idieresis", "ntilde", "oacute", "ograve", "ocircumflex", "odieresis", "otilde", "uacute", "ugrave", "ucircumflex", "udieresis", "dagger", "degree", "cent", "sterling", "section", "bullet", "paragraph", "germandbls", "registered", "copyright", "trademark", "acute", "dieresis", "notequal", "AE", "Oslash", "infinity", "plusminus", "lessequal", "greaterequal", "yen", "mu", "partialdiff", "summation", "product", "pi", "integral", "ordfeminine", "ordmasculine", "Omega", "ae", "oslash", "questiondown", "exclamdown", "logicalnot", "radical", "florin", "approxequal", "Delta", "guillemotleft", "guillemotright", "ellipsis", "nonbreakingspace", "Agrave", "Atilde", "Otilde", "OE", "oe", "endash", "emdash", "quotedblleft", "quotedblright", "quoteleft", "quoteright", "divide", "lozenge", "ydieresis", "Ydieresis", "fraction", "currency", "guilsinglleft", "guilsinglright", "fi", "fl", "daggerdbl", "periodcentered", "quotesinglbase", "quotedblbase", "perthousand", "Acircumflex", "Ecircumflex", "Aacute", "Edieresis", "Egrave", "Iacute", "Icircumflex", "Idieresis", "Igrave", "Oacute", "Ocircumflex", "apple", "Ograve", "Uacute", "Ucircumflex", "Ugrave", "dotlessi", "circumflex", "tilde", "macron", "breve", "dotaccent", "ring", "cedilla", "hungarumlaut", "ogonek", "caron", "Lslash", "lslash", "Scaron", "scaron", "Zcaron", "zcaron", "brokenbar", "Eth", "eth", "Yacute", "yacute", "Thorn", "thorn", "minus", "multiply", "onesuperior", "twosuperior", "threesuperior", "onehalf", "onequarter", "threequarters", "franc", "Gbreve", "gbreve", "Idotaccent", "Scedilla", "scedilla", "Cacute", "cacute", "Ccaron", "ccaron", "dcroat",
+];
@@ -1,5 +1,6 @@
 use std::mem::size_of;
 
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::Buffer;
@@ -19,10 +20,18 @@ pub struct Table_cvt_ {
 }
 
 impl Font {
-    pub fn parse_cvt_(&mut self, buffer: &mut Buffer) {
+    pub fn parse_cvt_(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let num = self.get_table_len(Tag::new(b"cvt ")) / size_of::<i16>();
         self.cvt_ = Some(Table_cvt_ {
             values: buffer.get_vec(num),
         });
+        Ok(())
+    }
+
+    /// Return the size of the control value table in bytes, or 0 if there is no `cvt ` table.
+    pub fn cvt_size(&self) -> usize {
+        self.cvt_
+            .as_ref()
+            .map_or(0, |cvt_| cvt_.values.len() * size_of::<i16>())
     }
 }
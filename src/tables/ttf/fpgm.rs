@@ -1,5 +1,6 @@
 use std::mem::size_of;
 
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::Buffer;
@@ -19,10 +20,16 @@ pub struct Table_fpgm {
 }
 
 impl Font {
-    pub fn parse_fpgm(&mut self, buffer: &mut Buffer) {
+    pub fn parse_fpgm(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let num = self.get_table_len(Tag::new(b"fpgm")) / size_of::<u8>();
         self.fpgm = Some(Table_fpgm {
             values: buffer.get_vec(num),
         });
+        Ok(())
+    }
+
+    /// Return the size of the font program in bytes, or 0 if there is no `fpgm` table.
+    pub fn fpgm_size(&self) -> usize {
+        self.fpgm.as_ref().map_or(0, |fpgm| fpgm.values.len())
     }
 }
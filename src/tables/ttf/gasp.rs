@@ -1,3 +1,4 @@
+use crate::error::FontError;
 use crate::font::Font;
 use crate::util::{Buffer, ReadBuffer};
 use read_buffer_derive::ReadBuffer;
@@ -20,7 +21,7 @@ pub struct Table_gasp {
 }
 
 impl Font {
-    pub fn parse_gasp(&mut self, buffer: &mut Buffer) {
+    pub fn parse_gasp(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let version = buffer.get();
         let num_ranges = buffer.get();
         let gasp_ranges = buffer.get_vec(num_ranges);
@@ -29,6 +30,28 @@ impl Font {
             num_ranges,
             gasp_ranges,
         });
+        Ok(())
+    }
+}
+
+impl Table_gasp {
+    /// Return the rasterization behavior for the given size in pixels per em.
+    ///
+    /// The ranges are sorted by `range_max_ppem`, and the first range whose upper limit is
+    /// greater than or equal to `ppem` applies. If there is no such range, all flags are unset.
+    pub fn behavior_for_ppem(&self, ppem: u16) -> GaspBehavior {
+        self.gasp_ranges
+            .iter()
+            .find(|range| ppem <= range.range_max_ppem)
+            .map(|range| GaspBehavior::from(range.range_gasp_behavior))
+            .unwrap_or_default()
+    }
+
+    /// Return an iterator over the `(range_max_ppem, range_gasp_behavior)` pairs.
+    pub fn ranges(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.gasp_ranges
+            .iter()
+            .map(|range| (range.range_max_ppem, range.range_gasp_behavior))
     }
 }
 
@@ -37,3 +60,27 @@ struct GaspRange {
     range_max_ppem: u16,
     range_gasp_behavior: u16,
 }
+
+/// Rasterization flags of a `gasp` range.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GaspBehavior {
+    /// Use gridfitting (`0x0001`).
+    pub gridfit: bool,
+    /// Use grayscale rendering (`0x0002`).
+    pub dogray: bool,
+    /// Use gridfitting with ClearType symmetric smoothing (`0x0004`, version 1 only).
+    pub symmetric_gridfit: bool,
+    /// Use smoothing along multiple axes with ClearType (`0x0008`, version 1 only).
+    pub symmetric_smoothing: bool,
+}
+
+impl From<u16> for GaspBehavior {
+    fn from(flags: u16) -> Self {
+        Self {
+            gridfit: flags & 0x0001 != 0,
+            dogray: flags & 0x0002 != 0,
+            symmetric_gridfit: flags & 0x0004 != 0,
+            symmetric_smoothing: flags & 0x0008 != 0,
+        }
+    }
+}
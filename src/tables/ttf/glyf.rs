@@ -1,7 +1,8 @@
 // use std::fmt;
 
-use crate::font::Font;
-use crate::types::F2Dot14;
+use crate::error::FontError;
+use crate::font::{Font, OutlineType};
+use crate::types::{F2Dot14, Tag};
 use crate::util::{Buffer, ReadBuffer};
 
 /// ## `glyf` &mdash; Glyph Data
@@ -13,33 +14,119 @@ use crate::util::{Buffer, ReadBuffer};
 
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table_glyf {
     pub glyphs: Vec<Glyph>,
 }
 
 impl Font {
-    pub fn parse_glyf(&mut self, buffer: &mut Buffer) {
+    pub fn parse_glyf(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let start = buffer.offset();
-        let loca_offsets = &self.loca.as_ref().unwrap().offsets;
+        let loca_offsets = &self
+            .loca
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"loca")))?
+            .offsets;
         self.glyf = Some(Table_glyf {
+            // The last offset only marks the end of the last glyph. A glyph with the same
+            // offset as its successor has no outline.
             glyphs: loca_offsets
-                .iter()
-                .map(|&i| {
-                    buffer.set_offset_from(start, i);
-                    buffer.get()
+                .windows(2)
+                .map(|w| {
+                    if w[0] == w[1] {
+                        Default::default()
+                    } else {
+                        buffer.set_offset_from(start, w[0]);
+                        buffer.get()
+                    }
                 })
                 .collect(),
         });
+        Ok(())
+    }
+
+    /// Return the bounding box of `glyph_id` as `(x_min, y_min, x_max, y_max)`.
+    ///
+    /// For TrueType outlines, the bounding box is read from the glyph itself. For CFF outlines,
+    /// the font bounding box is returned as an approximation.
+    pub fn glyph_bounding_box(&self, glyph_id: u16) -> Option<(i16, i16, i16, i16)> {
+        if let Some(glyf) = &self.glyf {
+            return glyf.get_glyph(glyph_id).map(Glyph::bounding_box);
+        }
+        if glyph_id >= self.maxp.as_ref()?.num_glyphs {
+            return None;
+        }
+        let (x_min, y_min, x_max, y_max) = self.CFF_.as_ref()?.font_bbox()?;
+        Some((x_min as i16, y_min as i16, x_max as i16, y_max as i16))
+    }
+
+    /// Return whether `glyph_id` is a valid glyph with outline data, i.e. it has a non-empty
+    /// entry in `loca` (TrueType) or a non-empty charstring (CFF). For CFF2 fonts only the
+    /// number of glyphs is checked. The `.notdef` glyph (0) is always valid.
+    pub fn contains_glyph(&self, glyph_id: u16) -> bool {
+        match &self.maxp {
+            Some(maxp) if glyph_id < maxp.num_glyphs() => {
+                glyph_id == 0 || self.has_outline_data(glyph_id)
+            }
+            _ => false,
+        }
+    }
+
+    /// Return whether `glyph_id` has zero advance width and no outline data, as unmapped
+    /// placeholder glyphs usually do.
+    pub fn is_blank_glyph(&self, glyph_id: u16) -> bool {
+        let advance_width = self.hmtx.as_ref().and_then(|hmtx| hmtx.metric(glyph_id));
+        advance_width.is_some_and(|(advance_width, _)| advance_width == 0)
+            && !self.has_outline_data(glyph_id)
+    }
+
+    fn has_outline_data(&self, glyph_id: u16) -> bool {
+        let i = glyph_id as usize;
+        match self.outline_type() {
+            OutlineType::TrueType => self.loca.as_ref().is_some_and(|loca| {
+                matches!((loca.offsets.get(i), loca.offsets.get(i + 1)), (Some(a), Some(b)) if a != b)
+            }),
+            OutlineType::Cff1 => self
+                .CFF_
+                .as_ref()
+                .and_then(|cff| cff.cff_fonts().first())
+                .is_some_and(|cff_font| cff_font.char_string_len(i) > 0),
+            OutlineType::Cff2 => true,
+        }
+    }
+
+    /// Return whether the font has TrueType hinting instructions, either in `fpgm`, `prep`
+    /// and `cvt ` or in any glyph. CFF fonts are never considered hinted here, since their
+    /// hints are part of the charstrings.
+    pub fn hinted(&self) -> bool {
+        if self.outline_type() != OutlineType::TrueType {
+            return false;
+        }
+        self.fpgm_size() > 0
+            || self.prep_size() > 0
+            || self.cvt_size() > 0
+            || self.glyf.as_ref().is_some_and(|glyf| {
+                glyf.glyphs
+                    .iter()
+                    .any(|glyph| glyph.instruction_length() > 0)
+            })
+    }
+}
+
+impl Table_glyf {
+    pub fn get_glyph(&self, glyph_id: u16) -> Option<&Glyph> {
+        self.glyphs.get(glyph_id as usize)
     }
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Glyph {
     number_of_contours: i16,
-    x_min: i16,
-    y_min: i16,
-    x_max: i16,
-    y_max: i16,
+    pub x_min: i16,
+    pub y_min: i16,
+    pub x_max: i16,
+    pub y_max: i16,
     contours: Vec<Vec<Point>>,
     components: Vec<Component>,
     instruction_length: u16,
@@ -90,6 +177,16 @@ impl Glyph {
     // const SCALED_COMPONENT_OFFSET: u16 = 0x0800;
     // const UNSCALED_COMPONENT_OFFSET: u16 = 0x1000;
 
+    /// Return the length of the glyph instructions in bytes.
+    pub fn instruction_length(&self) -> u16 {
+        self.instruction_length
+    }
+
+    /// Return the bounding box as `(x_min, y_min, x_max, y_max)`.
+    pub fn bounding_box(&self) -> (i16, i16, i16, i16) {
+        (self.x_min, self.y_min, self.x_max, self.y_max)
+    }
+
     fn parse_simple_glyph(&mut self, buffer: &mut Buffer, number_of_contours: i16) {
         let end_points_of_contours = buffer.get_vec(number_of_contours);
         self.instruction_length = buffer.get();
@@ -148,12 +245,11 @@ impl Glyph {
             } else {
                 // Arguments are unsigned point numbers
                 // TODO: not used
-                let (outer, inner) = if flags & Self::ARG_1_AND_2_ARE_WORDS != 0 {
+                let _point_numbers: (u16, u16) = if flags & Self::ARG_1_AND_2_ARE_WORDS != 0 {
                     (buffer.get(), buffer.get())
                 } else {
                     (buffer.get::<u8>() as u16, buffer.get::<u8>() as u16)
                 };
-                println!("[DEBUG] (outer, inner) = {:?}", (outer, inner));
             }
 
             // Scale
@@ -191,6 +287,7 @@ impl Glyph {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     x: i16,
     y: i16,
@@ -199,6 +296,7 @@ pub struct Point {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Component {
     glyph_index: u16,
     x: i16,
@@ -1,5 +1,6 @@
 use std::mem::size_of;
 
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::Buffer;
@@ -19,28 +20,42 @@ pub struct Table_loca {
 }
 
 impl Font {
-    pub fn parse_loca(&mut self, buffer: &mut Buffer) {
-        let index_to_loc_format = self.head.as_ref().unwrap().index_to_loc_format;
+    pub fn parse_loca(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
+        let head = self
+            .head
+            .as_ref()
+            .ok_or(FontError::TableNotFound(Tag::new(b"head")))?;
         let loca_len = self.get_table_len(Tag::new(b"loca"));
-        let offset_size = match index_to_loc_format {
-            0 => size_of::<u16>(),
-            1 => size_of::<u32>(),
-            _ => unreachable!(),
+        let offset_size = if head.is_short_loca() {
+            size_of::<u16>()
+        } else if head.is_long_loca() {
+            size_of::<u32>()
+        } else {
+            return Err(FontError::UnknownTableFormat {
+                tag: Tag::new(b"head"),
+                format: head.index_to_loc_format as u16,
+            });
         };
-        let num_glyphs = loca_len / offset_size - 1;
-        let maxp_num_glyphs = self.maxp.as_ref().unwrap().num_glyphs as usize;
-        if maxp_num_glyphs != num_glyphs {
-            eprintln!("Table 'loca' corrupted.");
-        }
-        let offsets = match index_to_loc_format {
-            0 => (0..num_glyphs)
+        // A mismatch with `maxp` (e.g. from a wrong `indexToLocFormat`) is reported by
+        // `Font::cross_validate`.
+        let num_glyphs =
+            (loca_len / offset_size)
+                .checked_sub(1)
+                .ok_or_else(|| FontError::Malformed {
+                    table: Tag::new(b"loca"),
+                    msg: format!("the table is too short ({} bytes)", loca_len),
+                })?;
+        // There are `num_glyphs + 1` offsets, the last one being the end of the last glyph.
+        let offsets = if head.is_short_loca() {
+            (0..=num_glyphs)
                 .map(|_| buffer.get::<u16>() as usize * 2)
-                .collect(),
-            1 => (0..num_glyphs)
+                .collect()
+        } else {
+            (0..=num_glyphs)
                 .map(|_| buffer.get::<u32>() as usize)
-                .collect(),
-            _ => unreachable!(),
+                .collect()
         };
         self.loca = Some(Table_loca { offsets });
+        Ok(())
     }
 }
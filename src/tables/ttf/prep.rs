@@ -1,5 +1,6 @@
 use std::mem::size_of;
 
+use crate::error::FontError;
 use crate::font::Font;
 use crate::types::Tag;
 use crate::util::Buffer;
@@ -22,10 +23,16 @@ pub struct Table_prep {
 }
 
 impl Font {
-    pub fn parse_prep(&mut self, buffer: &mut Buffer) {
+    pub fn parse_prep(&mut self, buffer: &mut Buffer) -> Result<(), FontError> {
         let num = self.get_table_len(Tag::new(b"prep")) / size_of::<u8>();
         self.prep = Some(Table_prep {
             values: buffer.get_vec(num),
         });
+        Ok(())
+    }
+
+    /// Return the size of the control value program in bytes, or 0 if there is no `prep` table.
+    pub fn prep_size(&self) -> usize {
+        self.prep.as_ref().map_or(0, |prep| prep.values.len())
     }
 }
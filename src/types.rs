@@ -19,11 +19,13 @@
 //!
 //! See: <https://docs.microsoft.com/en-us/typography/opentype/spec/otff#data-types>.
 
+use crate::error::FontError;
 use crate::util::{Buffer, ReadBuffer};
 use chrono::NaiveDateTime;
 use read_buffer_derive::ReadBuffer;
 use std::convert::TryInto;
 use std::fmt;
+use std::ops;
 use std::str;
 
 /// 24-bit unsigned integer.
@@ -43,16 +45,104 @@ impl From<u24> for usize {
     }
 }
 
+impl From<u24> for u32 {
+    fn from(num: u24) -> Self {
+        ((num.0 as u32) << 8) + (num.1 as u32)
+    }
+}
+
 /// 32-bit signed fixed-point number (16.16).
-#[derive(Clone, Copy, Default, ReadBuffer)]
+///
+/// The arithmetic operators saturate at the bounds instead of overflowing.
+///
+/// # Examples
+///
+/// ```
+/// # use rustotf::Fixed;
+/// let x = Fixed::from(3) / Fixed::from(2);
+/// assert_eq!(f64::from(x), 1.5);
+/// assert_eq!(x * Fixed::from(2), Fixed::from(3));
+/// assert!(x - Fixed::ONE < Fixed::ONE);
+/// assert_eq!(Fixed::from(i16::MAX as i32) + Fixed::ONE, Fixed::MAX);
+/// ```
+#[derive(Clone, Copy, Default, Eq, Ord, PartialEq, PartialOrd, ReadBuffer)]
 pub struct Fixed(i32);
 
+impl Fixed {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(0x0001_0000);
+    pub const MIN: Self = Self(i32::MIN);
+    pub const MAX: Self = Self(i32::MAX);
+
+    fn saturate(n: i64) -> Self {
+        Self(n.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
+
+impl ops::Add for Fixed {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+}
+
+impl ops::Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+}
+
+impl ops::Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::saturate((self.0 as i64 * other.0 as i64) >> 16)
+    }
+}
+
+impl ops::Div for Fixed {
+    type Output = Self;
+
+    /// Division by zero gives [`Fixed::MAX`] or [`Fixed::MIN`], depending on the sign of
+    /// the dividend.
+    fn div(self, other: Self) -> Self {
+        match other.0 {
+            0 if self.0 < 0 => Self::MIN,
+            0 => Self::MAX,
+            _ => Self::saturate(((self.0 as i64) << 16) / other.0 as i64),
+        }
+    }
+}
+
+impl From<i32> for Fixed {
+    /// Construct a fixed-point number from the integer `n`, saturating if it is out of the
+    /// range of the integer part.
+    fn from(n: i32) -> Self {
+        Self::saturate((n as i64) << 16)
+    }
+}
+
+impl From<Fixed> for f64 {
+    fn from(num: Fixed) -> Self {
+        f64::from(num.0) / 65536.0
+    }
+}
+
 impl fmt::Debug for Fixed {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:.3}", f64::from(self.0) / 65536.0)
     }
 }
 
+impl From<Fixed> for f32 {
+    fn from(num: Fixed) -> Self {
+        num.0 as f32 / 65536.0
+    }
+}
+
 impl PartialEq<i32> for Fixed {
     fn eq(&self, other: &i32) -> bool {
         self.0 == *other
@@ -69,6 +159,29 @@ impl fmt::Debug for F2Dot14 {
     }
 }
 
+impl From<F2Dot14> for f32 {
+    fn from(num: F2Dot14) -> Self {
+        num.0 as f32 / 16384.0
+    }
+}
+
+impl From<f32> for F2Dot14 {
+    fn from(num: f32) -> Self {
+        Self(
+            (num * 16384.0)
+                .round()
+                .clamp(i16::MIN as f32, i16::MAX as f32) as i16,
+        )
+    }
+}
+
+/// The raw value in `F2Dot14` units, i.e. the value multiplied by 16384.
+impl From<F2Dot14> for i16 {
+    fn from(num: F2Dot14) -> Self {
+        num.0
+    }
+}
+
 impl PartialEq<i16> for F2Dot14 {
     fn eq(&self, other: &i16) -> bool {
         self.0 == *other
@@ -99,7 +212,7 @@ impl fmt::Debug for LongDateTime {
 ///
 /// **Note:** In Rust, `char` is a *Unicode scalar value* with a size of 4 bytes
 /// rather than 1, so it can't be used here.
-#[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
 pub struct Tag([u8; 4]);
 
 impl Tag {
@@ -140,6 +253,34 @@ impl Tag {
     pub fn to_str(&self) -> &str {
         str::from_utf8(&self.0).unwrap()
     }
+
+    /// Construct a tag from a 32-bit unsigned integer `n`, where the first byte of the tag
+    /// is the most significant byte of `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustotf::Tag;
+    /// let tag = Tag::from_be_u32(0x676C7966);
+    /// assert_eq!(tag, Tag::new(b"glyf"));
+    /// ```
+    pub const fn from_be_u32(n: u32) -> Self {
+        Self(n.to_be_bytes())
+    }
+
+    /// Convert the tag to a 32-bit unsigned integer, the inverse of [`Tag::from_be_u32`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustotf::Tag;
+    /// let tag = Tag::new(b"glyf");
+    /// assert_eq!(tag.as_be_u32(), 0x676C7966);
+    /// assert_eq!(Tag::from_be_u32(tag.as_be_u32()), tag);
+    /// ```
+    pub const fn as_be_u32(&self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
 }
 
 impl From<u32> for Tag {
@@ -153,13 +294,36 @@ impl From<u32> for Tag {
     /// assert_eq!(tag, Tag::new(b"cvt "));
     /// ```
     fn from(n: u32) -> Self {
-        let bytes = &[
-            (n >> 24 & 0xFF) as u8,
-            (n >> 16 & 0xFF) as u8,
-            (n >> 8 & 0xFF) as u8,
-            (n & 0xFF) as u8,
-        ];
-        Tag::new(bytes)
+        Tag::from_be_u32(n)
+    }
+}
+
+impl From<Tag> for u32 {
+    /// Convert the tag `tag` to a 32-bit unsigned integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustotf::Tag;
+    /// assert_eq!(u32::from(Tag::new(b"OS/2")), 0x4F532F32);
+    /// ```
+    fn from(tag: Tag) -> Self {
+        tag.as_be_u32()
+    }
+}
+
+impl From<Tag> for [u8; 4] {
+    /// Convert the tag `tag` to its underlying `u8` array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustotf::Tag;
+    /// let bytes: [u8; 4] = Tag::new(b"name").into();
+    /// assert_eq!(&bytes, b"name");
+    /// ```
+    fn from(tag: Tag) -> Self {
+        tag.0
     }
 }
 
@@ -223,6 +387,29 @@ impl fmt::Debug for u16_var {
     }
 }
 
+impl ReadBuffer for u16_var {
+    fn read(buffer: &mut Buffer) -> Self {
+        const WORD_CODE: u8 = 253;
+        const ONE_MORE_BYTE_CODE2: u8 = 254;
+        const ONE_MORE_BYTE_CODE1: u8 = 255;
+        const LOWEST_U_CODE: u16 = 253;
+
+        let code: u8 = buffer.get();
+        match code {
+            WORD_CODE => Self(buffer.get()),
+            ONE_MORE_BYTE_CODE1 => Self(buffer.get::<u8>() as u16 + LOWEST_U_CODE),
+            ONE_MORE_BYTE_CODE2 => Self(buffer.get::<u8>() as u16 + LOWEST_U_CODE * 2),
+            _ => Self(code as u16),
+        }
+    }
+}
+
+impl From<u16_var> for u16 {
+    fn from(n: u16_var) -> Self {
+        n.0
+    }
+}
+
 /// `UIntBase128` in WOFF2 specification. Variable-length encoding of a 32-bit unsigned integer
 /// for optimized intermediate font data storage.
 #[allow(non_camel_case_types)]
@@ -241,26 +428,31 @@ impl fmt::Debug for u32_var {
     }
 }
 
-impl ReadBuffer for u32_var {
-    fn read(buffer: &mut Buffer) -> Self {
+impl u32_var {
+    /// Read a `UIntBase128` value. Unlike other types, the encoding may be invalid, so it is
+    /// not read by [`ReadBuffer`].
+    pub fn read(buffer: &mut Buffer) -> Result<Self, FontError> {
+        let start = buffer.offset();
+        let invalid =
+            || FontError::Decompression(format!("invalid UIntBase128 at offset {}", start));
         let mut res = 0;
         for i in 0..5 {
             let byte: u8 = buffer.get();
             // No leading 0's
             if i == 0 && byte == 0x80 {
-                panic!()
+                return Err(invalid());
             }
             // If any of top 7 bits are set then << 7 would overflow
             if res & 0xFE00_0000 != 0 {
-                panic!()
+                return Err(invalid());
             }
             res = (res << 7) | ((byte & 0x7F) as u32);
             // Spin until most significant bit of data byte is false
             if byte & 0x80 == 0 {
-                return Self(res);
+                return Ok(Self(res));
             }
         }
-        panic!()
+        Err(invalid())
     }
 }
 
@@ -270,15 +462,145 @@ impl From<u32_var> for u32 {
     }
 }
 
+/// Serialize the types into readable forms rather than their raw values: tags as strings,
+/// fixed numbers as floats and dates in ISO 8601.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::*;
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::ser::{Serialize, Serializer};
+
+    const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+    impl Serialize for u24 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u32(u32::from(*self))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for u24 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let n = u32::deserialize(deserializer)?;
+            if n > 0xFF_FFFF {
+                return Err(de::Error::custom(format!(
+                    "{} is out of the range of u24",
+                    n
+                )));
+            }
+            Ok(Self((n >> 8) as u16, n as u8))
+        }
+    }
+
+    impl Serialize for Fixed {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_f64(f64::from(*self))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Fixed {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let n = f64::deserialize(deserializer)?;
+            Ok(Self::saturate((n * 65536.0).round() as i64))
+        }
+    }
+
+    impl Serialize for F2Dot14 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_f32(f32::from(*self))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for F2Dot14 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            f32::deserialize(deserializer).map(Self::from)
+        }
+    }
+
+    impl Serialize for LongDateTime {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let timestamp = self.num - Self::DATE_TIME_OFFSET;
+            match chrono::DateTime::from_timestamp(timestamp, 0) {
+                Some(date_time) => {
+                    serializer.collect_str(&date_time.naive_utc().format(DATE_TIME_FORMAT))
+                }
+                None => Err(serde::ser::Error::custom("date out of range")),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for LongDateTime {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            let date_time =
+                NaiveDateTime::parse_from_str(&s, DATE_TIME_FORMAT).map_err(de::Error::custom)?;
+            Ok(Self {
+                num: date_time.and_utc().timestamp() + Self::DATE_TIME_OFFSET,
+            })
+        }
+    }
+
+    impl Serialize for Tag {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&String::from_utf8_lossy(&self.0))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Tag {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            let bytes: [u8; 4] = s
+                .as_bytes()
+                .try_into()
+                .map_err(|_| de::Error::invalid_length(s.len(), &"a 4-byte tag"))?;
+            Ok(Self(bytes))
+        }
+    }
+}
+
+#[test]
+fn test_u16_var() {
+    let mut buffer = Buffer::new(vec![0xFC, 0xFF, 0x00, 0xFE, 0x01, 0xFD, 0x12, 0x34]);
+    assert_eq!(u16::from(buffer.get::<u16_var>()), 252);
+    assert_eq!(u16::from(buffer.get::<u16_var>()), 253);
+    assert_eq!(u16::from(buffer.get::<u16_var>()), 507);
+    assert_eq!(u16::from(buffer.get::<u16_var>()), 0x1234);
+}
+
 #[test]
 fn test_u32_var_success() {
     let mut buffer = Buffer::new(vec![0x3F]);
-    assert_eq!(buffer.get::<u32_var>(), 63);
+    assert_eq!(u32_var::read(&mut buffer).unwrap(), 63);
 }
 
 #[test]
-#[should_panic]
-fn test_u32_var_panic() {
+fn test_u32_var_leading_zero() {
     let mut buffer = Buffer::new(vec![0x80, 0x3F]);
-    assert_eq!(buffer.get::<u32_var>(), 63);
+    assert!(u32_var::read(&mut buffer).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let tag: Tag = serde_json::from_str("\"OS/2\"").unwrap();
+    assert_eq!(tag, Tag::new(b"OS/2"));
+    assert_eq!(serde_json::to_string(&tag).unwrap(), "\"OS/2\"");
+    assert!(serde_json::from_str::<Tag>("\"CFF\"").is_err());
+
+    assert_eq!(
+        serde_json::to_string(&(Fixed::from(3) / Fixed::from(2))).unwrap(),
+        "1.5"
+    );
+    assert_eq!(
+        serde_json::from_str::<Fixed>("-0.5").unwrap(),
+        Fixed(-0x8000)
+    );
+    assert_eq!(serde_json::to_string(&F2Dot14(-0x2000)).unwrap(), "-0.5");
+
+    let date = LongDateTime { num: 3_600_000_000 };
+    let json = serde_json::to_string(&date).unwrap();
+    assert_eq!(json, "\"2018-01-28T16:00:00\"");
+    assert_eq!(
+        serde_json::from_str::<LongDateTime>(&json).unwrap().num,
+        date.num
+    );
 }
@@ -1,18 +1,37 @@
+use crate::error::FontError;
+use crate::types::{u24, Tag};
 use byteorder::{BigEndian, ByteOrder};
 use flate2::read::{GzDecoder, ZlibDecoder};
+use std::collections::HashMap;
 use std::fmt;
+use std::hash::Hash;
 use std::io::{Read, Result};
 use std::mem;
+use std::str::{self, Utf8Error};
 
 pub struct Buffer {
     bytes: Vec<u8>,
     offset: usize,
+    /// The first undefined format met when reading subtables, see [`Buffer::set_unknown_format`].
+    unknown_format: Option<u16>,
+    /// The first read beyond the end of the buffer, see [`Buffer::take_error`].
+    error: Option<FontError>,
 }
 
 impl Buffer {
     /// Create a new `Buffer`.
     pub fn new(bytes: Vec<u8>) -> Self {
-        Self { bytes, offset: 0 }
+        Self {
+            bytes,
+            offset: 0,
+            unknown_format: None,
+            error: None,
+        }
+    }
+
+    /// Consume the buffer and return the underlying bytes, e.g. to reuse the allocation.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
     }
 
     /// Return the length of the buffer.
@@ -20,6 +39,57 @@ impl Buffer {
         self.bytes.len()
     }
 
+    /// Record an undefined subtable `format`. Since [`ReadBuffer::read`] cannot fail, the
+    /// subtable is read as an empty one, and the error is reported after the whole table has
+    /// been read (see [`Buffer::take_unknown_format`]).
+    pub fn set_unknown_format(&mut self, format: u16) {
+        self.unknown_format.get_or_insert(format);
+    }
+
+    /// Return the first undefined format recorded by [`Buffer::set_unknown_format`], if any,
+    /// and clear it.
+    pub fn take_unknown_format(&mut self) -> Option<u16> {
+        self.unknown_format.take()
+    }
+
+    /// Record an error in the data of a subtable, e.g. a cycle of offsets. Like
+    /// [`Buffer::set_unknown_format`], only the first one is kept, and it is reported after
+    /// the whole table has been read (see [`Buffer::take_error`]).
+    pub fn set_error(&mut self, err: FontError) {
+        self.error.get_or_insert(err);
+    }
+
+    /// Return the number of bytes after the current offset.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.offset)
+    }
+
+    /// Return the first overflow met when reading the buffer (or the first error recorded by
+    /// [`Buffer::set_error`]), if any, and clear it. Reading beyond the end of the buffer does
+    /// not panic: the value is read as zero and the error is recorded here, so that truncated
+    /// data can be detected after a table has been read.
+    pub fn take_error(&mut self) -> Option<FontError> {
+        self.error.take()
+    }
+
+    /// Return `size` bytes from the current offset and advance the offset, or `None` (with
+    /// the error recorded) if they exceed the end of the buffer.
+    fn read_bytes(&mut self, size: usize) -> Option<&[u8]> {
+        let offset = self.offset;
+        self.offset = offset.saturating_add(size);
+        if self.offset > self.len() {
+            if self.error.is_none() {
+                self.error = Some(FontError::BufferOverflow {
+                    offset,
+                    length: size,
+                    hex_dump: self.error_hex_dump(offset),
+                });
+            }
+            return None;
+        }
+        Some(&self.bytes[offset..self.offset])
+    }
+
     /// Get a value as type `T` from the buffer.
     pub fn get<T: ReadBuffer>(&mut self) -> T {
         ReadBuffer::read(self)
@@ -30,6 +100,26 @@ impl Buffer {
         (0..n.as_usize()).map(|_| ReadBuffer::read(self)).collect()
     }
 
+    /// Get a map of `n` key-value pairs from the buffer, where each value follows its key.
+    pub fn get_map<K, V, N>(&mut self, n: N) -> HashMap<K, V>
+    where
+        K: ReadBuffer + Eq + Hash,
+        V: ReadBuffer,
+        N: AsUsize,
+    {
+        (0..n.as_usize())
+            .map(|_| (self.get(), self.get()))
+            .collect()
+    }
+
+    /// Get a vector of `n` tag-value pairs from the buffer, where each value follows its tag.
+    /// Unlike [`Buffer::get_map`], the order (and any duplicated tags) are kept.
+    pub fn get_tagged_list<V: ReadBuffer, N: AsUsize>(&mut self, n: N) -> Vec<(Tag, V)> {
+        (0..n.as_usize())
+            .map(|_| (self.get(), self.get()))
+            .collect()
+    }
+
     /// Get an option of type `T` values from the buffer.
     /// If `offset` is 0 (i.e. NULL), then it will return a `None`.
     pub fn get_or_none<T: ReadBuffer, N: AsUsize>(&mut self, start: usize, offset: N) -> Option<T> {
@@ -48,11 +138,49 @@ impl Buffer {
         format!("{}.{}", self.get::<T>(), self.get::<T>())
     }
 
+    /// Get `len` raw bytes from the buffer.
+    pub fn get_bytes(&mut self, len: usize) -> Vec<u8> {
+        self.get_vec(len)
+    }
+
+    /// Get a UTF-8 string of `len` bytes from the buffer.
+    /// The offset is advanced by `len` even if the bytes are not valid UTF-8.
+    pub fn get_string_utf8(&mut self, len: usize) -> std::result::Result<String, Utf8Error> {
+        let bytes = self.get_bytes(len);
+        str::from_utf8(&bytes).map(String::from)
+    }
+
+    /// Get a Pascal string (a length byte followed by that many bytes) from the buffer.
+    /// Pascal strings in OpenType fonts are usually ASCII, so the bytes are decoded as UTF-8.
+    pub fn get_pascal_string(&mut self) -> std::result::Result<String, Utf8Error> {
+        let len: u8 = self.get();
+        self.get_string_utf8(len as usize)
+    }
+
     /// Skip `n` * `size_of<T>` bytes for `offset`.
     pub fn skip<T>(&mut self, n: usize) {
         self.offset += n * mem::size_of::<T>();
     }
 
+    /// Skip the padding bytes so that `offset` is a multiple of `alignment`, which should be a
+    /// power of 2. Note that the alignment is relative to the beginning of the buffer (i.e.
+    /// the font file), not to the beginning of a table. Reserved fields, which take their
+    /// space regardless of the offset, should be skipped with [`Buffer::skip`] instead.
+    pub fn align(&mut self, alignment: usize) {
+        debug_assert!(alignment.is_power_of_two());
+        self.offset = (self.offset + alignment - 1) & !(alignment - 1);
+    }
+
+    /// Skip the padding bytes to the next 2-byte boundary.
+    pub fn align2(&mut self) {
+        self.align(2)
+    }
+
+    /// Skip the padding bytes to the next 4-byte boundary.
+    pub fn align4(&mut self) {
+        self.align(4)
+    }
+
     /// Return the offset of the buffer.
     pub fn offset(&self) -> usize {
         self.offset
@@ -70,8 +198,61 @@ impl Buffer {
         self.offset = start + offset.as_usize()
     }
 
+    /// Return the bytes from `start` to `end`, relative to the current offset. The range is
+    /// truncated at the end of the buffer.
     pub fn slice(&self, start: usize, end: usize) -> &[u8] {
-        &self.bytes[(self.offset + start)..(self.offset + end)]
+        let end = (self.offset + end).min(self.len());
+        let start = (self.offset + start).min(end);
+        &self.bytes[start..end]
+    }
+
+    /// Return a hex dump of `len` bytes from `start`, in the format of `xxd`: 16 bytes per
+    /// row, with the offset, the hex values and the ASCII characters (`.` for non-printable
+    /// ones). The range is truncated at the end of the buffer.
+    pub fn dump_hex(&self, start: usize, len: usize) -> String {
+        const BYTES_PER_ROW: usize = 16;
+
+        let start = start.min(self.len());
+        let end = start.saturating_add(len).min(self.len());
+        self.bytes[start..end]
+            .chunks(BYTES_PER_ROW)
+            .enumerate()
+            .map(|(i, row)| {
+                let hex: Vec<String> = row
+                    .chunks(2)
+                    .map(|pair| pair.iter().map(|b| format!("{:02x}", b)).collect())
+                    .collect();
+                let ascii: String = row
+                    .iter()
+                    .map(|&b| match b {
+                        0x20..=0x7E => b as char,
+                        _ => '.',
+                    })
+                    .collect();
+                format!(
+                    "{:08x}: {:<40} {}",
+                    start + i * BYTES_PER_ROW,
+                    hex.join(" "),
+                    ascii
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Return a hex dump of the bytes around `offset`, with up to `context_bytes` on each
+    /// side. See [`Buffer::dump_hex`].
+    pub fn dump_hex_at(&self, offset: usize, context_bytes: usize) -> String {
+        let start = offset.saturating_sub(context_bytes);
+        self.dump_hex(start, (offset - start).saturating_add(context_bytes))
+    }
+
+    /// Return a hex dump of the bytes around `offset` for error messages, or `None` if
+    /// `offset` is far beyond the end of the buffer.
+    pub(crate) fn error_hex_dump(&self, offset: usize) -> Option<String> {
+        const CONTEXT_BYTES: usize = 32;
+
+        Some(self.dump_hex_at(offset, CONTEXT_BYTES)).filter(|hex_dump| !hex_dump.is_empty())
     }
 
     pub fn zlib_decompress(&self, comp_len: usize) -> Result<Self> {
@@ -81,6 +262,13 @@ impl Buffer {
         Ok(Self::new(orig_buffer))
     }
 
+    pub fn brotli_decompress(&self, comp_len: usize) -> Result<Self> {
+        let comp_buffer = self.slice(0, comp_len);
+        let mut orig_buffer = Vec::new();
+        brotli::Decompressor::new(comp_buffer, 4096).read_to_end(&mut orig_buffer)?;
+        Ok(Self::new(orig_buffer))
+    }
+
     pub fn gz_decompress(&self, comp_len: usize) -> Result<Self> {
         let comp_buffer = self.slice(0, comp_len);
         let mut orig_buffer = Vec::new();
@@ -88,25 +276,55 @@ impl Buffer {
         Ok(Self::new(orig_buffer))
     }
 
-    // pub fn calc_checksum(&self, offset: u32, length: u32) -> u32 {
-    //     let offset = offset as usize;
-    //     let padded_length = ((length + 3) & !3) as usize;
-    //     (0..padded_length).step_by(4).fold(0, |acc, i| {
-    //         acc.wrapping_add(BigEndian::read_u32(
-    //             &self.buffer[_offset + i.._offset + i + 4],
-    //         ))
-    //     })
-    // }
+    /// Decompress `comp_len` bytes of zlib data from the current offset into `dest`. Unlike
+    /// [`Buffer::zlib_decompress`], the allocation of `dest` is reused: it is cleared before
+    /// decompressing, so that only the decompressed data is kept.
+    pub fn zlib_decompress_into(&self, comp_len: usize, dest: &mut Vec<u8>) -> Result<()> {
+        dest.clear();
+        ZlibDecoder::new(self.slice(0, comp_len)).read_to_end(dest)?;
+        Ok(())
+    }
+
+    /// Decompress `comp_len` bytes of gzip data from the current offset into `dest`, which is
+    /// cleared first. See [`Buffer::zlib_decompress_into`].
+    pub fn gz_decompress_into(&self, comp_len: usize, dest: &mut Vec<u8>) -> Result<()> {
+        dest.clear();
+        GzDecoder::new(self.slice(0, comp_len)).read_to_end(dest)?;
+        Ok(())
+    }
+
+    /// Decompress `comp_len` bytes of zlib data at `offset`, without moving the current
+    /// offset.
+    pub fn zlib_decompress_at(&self, offset: usize, comp_len: usize) -> Result<Self> {
+        let end = offset.saturating_add(comp_len).min(self.bytes.len());
+        let comp_buffer = self.bytes.get(offset..end).unwrap_or_default();
+        let mut orig_buffer = Vec::new();
+        ZlibDecoder::new(comp_buffer).read_to_end(&mut orig_buffer)?;
+        Ok(Self::new(orig_buffer))
+    }
+
+    /// Calculate the checksum of `length` bytes at `offset`, i.e. the sum of them as `u32`s.
+    /// The data are padded with zeros to a multiple of 4 bytes, and so are the bytes beyond
+    /// the end of the buffer.
+    pub fn calc_checksum(&self, offset: usize, length: usize) -> u32 {
+        let end = offset.saturating_add(length).min(self.bytes.len());
+        let bytes = self.bytes.get(offset..end).unwrap_or_default();
+        bytes.chunks(4).fold(0, |acc, chunk| {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            acc.wrapping_add(BigEndian::read_u32(&word))
+        })
+    }
 }
 
 impl fmt::Debug for Buffer {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Buffer {{len: {}, elems: [{}, ..., {}]}}",
+            "Buffer {{len: {}, elems: [{:?}, ..., {:?}]}}",
             self.bytes.len(),
-            self.bytes.first().unwrap(),
-            self.bytes.last().unwrap(),
+            self.bytes.first(),
+            self.bytes.last(),
         )
     }
 }
@@ -135,23 +353,29 @@ generate_as_usize!(i16);
 generate_as_usize!(i32);
 generate_as_usize!(i64);
 
+impl AsUsize for u24 {
+    fn as_usize(&self) -> usize {
+        usize::from(*self)
+    }
+}
+
 pub trait ReadBuffer {
     fn read(buffer: &mut Buffer) -> Self;
 }
 
 impl ReadBuffer for u8 {
     fn read(buffer: &mut Buffer) -> Self {
-        let offset = buffer.offset();
-        buffer.offset += mem::size_of::<u8>();
-        buffer.bytes[offset]
+        buffer
+            .read_bytes(mem::size_of::<u8>())
+            .map_or(0, |bytes| bytes[0])
     }
 }
 
 impl ReadBuffer for i8 {
     fn read(buffer: &mut Buffer) -> Self {
-        let offset = buffer.offset();
-        buffer.offset += mem::size_of::<i8>();
-        buffer.bytes[offset] as i8
+        buffer
+            .read_bytes(mem::size_of::<i8>())
+            .map_or(0, |bytes| bytes[0] as i8)
     }
 }
 
@@ -160,9 +384,7 @@ macro_rules! generate_read {
     ($t:ty, $f:expr) => {
         impl ReadBuffer for $t {
             fn read(buffer: &mut Buffer) -> Self {
-                let offset = buffer.offset();
-                buffer.offset += mem::size_of::<$t>();
-                $f(&buffer.bytes[offset..buffer.offset])
+                buffer.read_bytes(mem::size_of::<$t>()).map_or(0, $f)
             }
         }
     };
@@ -174,3 +396,157 @@ generate_read!(u64, BigEndian::read_u64);
 generate_read!(i16, BigEndian::read_i16);
 generate_read!(i32, BigEndian::read_i32);
 generate_read!(i64, BigEndian::read_i64);
+
+#[test]
+fn test_buffer_align() {
+    let mut buffer = Buffer::new(vec![0; 16]);
+    buffer.align4();
+    assert_eq!(buffer.offset(), 0);
+    buffer.skip::<u8>(1);
+    buffer.align2();
+    assert_eq!(buffer.offset(), 2);
+    buffer.align4();
+    assert_eq!(buffer.offset(), 4);
+    buffer.skip::<u8>(1);
+    buffer.align(8);
+    assert_eq!(buffer.offset(), 8);
+}
+
+#[test]
+fn test_buffer_overflow() {
+    let mut buffer = Buffer::new(vec![0x12, 0x34, 0x56]);
+    assert_eq!(buffer.get::<u16>(), 0x1234);
+    assert_eq!(buffer.remaining(), 1);
+    assert!(buffer.take_error().is_none());
+    assert_eq!(buffer.get::<u32>(), 0);
+    assert_eq!(buffer.get::<u8>(), 0);
+    assert!(matches!(
+        buffer.take_error(),
+        Some(FontError::BufferOverflow {
+            offset: 2,
+            length: 4,
+            hex_dump: Some(_),
+        })
+    ));
+    assert!(buffer.take_error().is_none());
+    assert_eq!(buffer.remaining(), 0);
+
+    buffer.set_offset(1);
+    assert_eq!(buffer.get_vec::<u16, _>(2), vec![0x3456, 0]);
+    assert!(buffer.take_error().is_some());
+    assert_eq!(buffer.slice(0, 4), &[] as &[u8]);
+}
+
+#[test]
+fn test_buffer_zlib_decompress_into() {
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(b"rustotf").unwrap();
+    let comp_bytes = encoder.finish().unwrap();
+    let comp_len = comp_bytes.len();
+    let buffer = Buffer::new(comp_bytes);
+
+    let mut dest = b"stale data".to_vec();
+    buffer.zlib_decompress_into(comp_len, &mut dest).unwrap();
+    assert_eq!(dest, b"rustotf");
+}
+
+#[test]
+fn test_read_buffer_derive() {
+    use read_buffer_derive::ReadBuffer;
+
+    #[derive(ReadBuffer)]
+    struct Named {
+        a: u16,
+        #[read_buffer(default)]
+        computed: Option<u32>,
+        b: i8,
+    }
+
+    #[derive(ReadBuffer)]
+    struct Unnamed(u8, #[read_buffer(default)] Vec<u8>, u8);
+
+    let mut buffer = Buffer::new(vec![0x01, 0x02, 0xFF, 0x03, 0x04]);
+    let named: Named = buffer.get();
+    assert_eq!((named.a, named.computed, named.b), (0x0102, None, -1));
+    let unnamed: Unnamed = buffer.get();
+    assert_eq!((unnamed.0, unnamed.1, unnamed.2), (3, Vec::new(), 4));
+    assert_eq!(buffer.offset(), 5);
+}
+
+/// Serialize a map as a sequence of `(key, value)` pairs sorted by keys, for keys that can't
+/// be object keys in formats like JSON (e.g. tuples).
+#[cfg(feature = "serde")]
+pub(crate) mod serde_pairs {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Ord + Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        let mut pairs: Vec<_> = map.iter().collect();
+        pairs.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        serializer.collect_seq(pairs)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Eq + Hash + Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
+
+#[test]
+fn test_calc_checksum() {
+    let buffer = Buffer::new(vec![0, 0, 0, 1, 0xFF, 0xFF, 0xFF, 0xFF, 0x12, 0x34]);
+    assert_eq!(buffer.calc_checksum(0, 8), 0);
+    assert_eq!(buffer.calc_checksum(0, 10), 0x1234_0000);
+    assert_eq!(buffer.calc_checksum(8, 4), 0x1234_0000);
+    assert_eq!(buffer.calc_checksum(20, 4), 0);
+}
+
+#[test]
+fn test_dump_hex() {
+    let buffer = Buffer::new(b"OTTO\x00\x0b\x00\x80\x00\x03\x000CFF \xff".to_vec());
+    assert_eq!(
+        buffer.dump_hex(0, 17),
+        "00000000: 4f54 544f 000b 0080 0003 0030 4346 4620  OTTO.......0CFF \n\
+         00000010: ff                                       ."
+    );
+    assert_eq!(
+        buffer.dump_hex(10, 4),
+        "0000000a: 0030 4346                                .0CF"
+    );
+    // Ranges are truncated at the end of the buffer
+    assert_eq!(buffer.dump_hex(16, 100), buffer.dump_hex(16, 1));
+    assert_eq!(buffer.dump_hex(17, 1), "");
+    assert_eq!(buffer.dump_hex(usize::MAX, usize::MAX), "");
+}
+
+#[test]
+fn test_dump_hex_at() {
+    let buffer = Buffer::new((0..64).collect());
+    assert_eq!(buffer.dump_hex_at(0, 2), buffer.dump_hex(0, 2));
+    assert_eq!(buffer.dump_hex_at(20, 4), buffer.dump_hex(16, 8));
+    assert_eq!(buffer.dump_hex_at(63, 4), buffer.dump_hex(59, 5));
+    assert_eq!(buffer.dump_hex_at(0, usize::MAX), buffer.dump_hex(0, 64));
+    assert_eq!(
+        buffer.dump_hex_at(usize::MAX, usize::MAX),
+        buffer.dump_hex(0, 64)
+    );
+    assert_eq!(buffer.dump_hex_at(usize::MAX, 4), "");
+
+    assert_eq!(buffer.error_hex_dump(70), Some(buffer.dump_hex(38, 26)));
+    assert_eq!(buffer.error_hex_dump(100), None);
+}
@@ -0,0 +1,413 @@
+//! Reconstruction of the tables transformed in WOFF2 fonts.
+//!
+//! See: <https://www.w3.org/TR/WOFF2/#table_tranforms>.
+
+use crate::error::FontError;
+use crate::types::u16_var;
+use crate::util::Buffer;
+
+// Flags of simple glyphs
+const ON_CURVE_POINT: u8 = 0x01;
+const X_SHORT_VECTOR: u8 = 0x02;
+const Y_SHORT_VECTOR: u8 = 0x04;
+const X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR: u8 = 0x10;
+const Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR: u8 = 0x20;
+const OVERLAP_SIMPLE: u8 = 0x40;
+
+// Flags of composite glyphs
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+
+/// The decompressed table data of a WOFF2 font, together with the tables reconstructed from
+/// it, which are kept so that the tables can be parsed one by one.
+#[derive(Debug)]
+pub(crate) struct Woff2Tables {
+    pub table_data: Buffer,
+    pub glyf: Option<ReconstructedGlyf>,
+}
+
+/// The `glyf` and `loca` tables reconstructed from the transformed `glyf` table.
+#[derive(Debug)]
+pub(crate) struct ReconstructedGlyf {
+    pub glyf: Vec<u8>,
+    pub loca: Vec<u8>,
+    /// The `xMin` of each glyph (0 for empty glyphs), which is needed by the `hmtx` transform.
+    pub x_mins: Vec<i16>,
+}
+
+fn invalid(msg: String) -> FontError {
+    FontError::Decompression(msg)
+}
+
+/// Reconstruct the `glyf` and `loca` tables from the transformed `glyf` table, which
+/// splits the glyph data into streams and stores the point coordinates as deltas.
+pub(crate) fn reconstruct_glyf_loca(data: &[u8]) -> Result<ReconstructedGlyf, FontError> {
+    let mut buffer = Buffer::new(data.to_vec());
+    let _reserved: u16 = buffer.get();
+    let option_flags: u16 = buffer.get();
+    let num_glyphs: u16 = buffer.get();
+    let index_format: u16 = buffer.get();
+    let stream_sizes: Vec<u32> = buffer.get_vec(7);
+    let mut next_stream = |size: u32| {
+        let stream = buffer.get_bytes(size as usize);
+        Buffer::new(stream)
+    };
+    let mut n_contour_stream = next_stream(stream_sizes[0]);
+    let mut n_points_stream = next_stream(stream_sizes[1]);
+    let mut flag_stream = next_stream(stream_sizes[2]);
+    let mut glyph_stream = next_stream(stream_sizes[3]);
+    let mut composite_stream = next_stream(stream_sizes[4]);
+    let mut bbox_stream = next_stream(stream_sizes[5]);
+    let mut instruction_stream = next_stream(stream_sizes[6]);
+    let overlap_bitmap = if option_flags & 1 != 0 {
+        next_stream((num_glyphs as u32).div_ceil(8)).into_bytes()
+    } else {
+        Vec::new()
+    };
+    let bbox_bitmap: Vec<u8> = bbox_stream.get_vec(4 * (num_glyphs as usize).div_ceil(32));
+    let is_set = |bitmap: &[u8], i: usize| {
+        bitmap
+            .get(i >> 3)
+            .is_some_and(|b| b & (0x80 >> (i & 7)) != 0)
+    };
+
+    let mut glyf = Vec::new();
+    let mut offsets = Vec::with_capacity(num_glyphs as usize + 1);
+    let mut x_mins = Vec::with_capacity(num_glyphs as usize);
+    for glyph_id in 0..num_glyphs as usize {
+        offsets.push(glyf.len());
+        let n_contours: i16 = n_contour_stream.get();
+        let explicit_bbox = if is_set(&bbox_bitmap, glyph_id) {
+            Some([
+                bbox_stream.get(),
+                bbox_stream.get(),
+                bbox_stream.get(),
+                bbox_stream.get(),
+            ])
+        } else {
+            None
+        };
+        match n_contours {
+            0 => x_mins.push(0),
+            // Composite glyphs must have an explicit bounding box.
+            -1 => {
+                let bbox: [i16; 4] = explicit_bbox.unwrap_or_default();
+                x_mins.push(bbox[0]);
+                glyf.extend(n_contours.to_be_bytes());
+                bbox.iter().for_each(|n| glyf.extend(n.to_be_bytes()));
+                if read_composite_glyph(&mut composite_stream, &mut glyf) {
+                    let instruction_length = u16::from(glyph_stream.get::<u16_var>());
+                    glyf.extend(instruction_length.to_be_bytes());
+                    glyf.extend(instruction_stream.get_bytes(instruction_length as usize));
+                }
+            }
+            1..=i16::MAX => {
+                let mut end_pts_of_contours = Vec::with_capacity(n_contours as usize);
+                let mut num_points: u16 = 0;
+                for _ in 0..n_contours {
+                    let end_pt = u16::from(n_points_stream.get::<u16_var>())
+                        .checked_add(num_points)
+                        .and_then(|n| {
+                            num_points = n;
+                            n.checked_sub(1)
+                        })
+                        .ok_or_else(|| {
+                            invalid(format!("invalid number of points in glyph {}", glyph_id))
+                        })?;
+                    end_pts_of_contours.push(end_pt);
+                }
+                let points: Vec<(i32, i32, bool)> = (0..num_points)
+                    .map(|_| read_triplet(&mut flag_stream, &mut glyph_stream))
+                    .collect();
+                let instruction_length = u16::from(glyph_stream.get::<u16_var>());
+
+                let bbox = explicit_bbox.unwrap_or_else(|| compute_bbox(&points));
+                x_mins.push(bbox[0]);
+                glyf.extend(n_contours.to_be_bytes());
+                bbox.iter().for_each(|n| glyf.extend(n.to_be_bytes()));
+                end_pts_of_contours
+                    .iter()
+                    .for_each(|n| glyf.extend(n.to_be_bytes()));
+                glyf.extend(instruction_length.to_be_bytes());
+                glyf.extend(instruction_stream.get_bytes(instruction_length as usize));
+                write_simple_glyph_points(&points, is_set(&overlap_bitmap, glyph_id), &mut glyf);
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "invalid number of contours {} in glyph {}",
+                    n_contours, glyph_id
+                )))
+            }
+        }
+        // Pad the glyph to 4-byte boundary, so that the offsets are valid in both formats.
+        glyf.resize((glyf.len() + 3) & !3, 0);
+    }
+    offsets.push(glyf.len());
+    let mut streams = [
+        &mut n_contour_stream,
+        &mut n_points_stream,
+        &mut flag_stream,
+        &mut glyph_stream,
+        &mut composite_stream,
+        &mut bbox_stream,
+        &mut instruction_stream,
+    ];
+    if let Some(err) = streams.iter_mut().find_map(|stream| stream.take_error()) {
+        return Err(err.context("reconstructing 'glyf'"));
+    }
+
+    let loca = match index_format {
+        0 => offsets
+            .iter()
+            .flat_map(|&offset| ((offset / 2) as u16).to_be_bytes())
+            .collect(),
+        _ => offsets
+            .iter()
+            .flat_map(|&offset| (offset as u32).to_be_bytes())
+            .collect(),
+    };
+    Ok(ReconstructedGlyf { glyf, loca, x_mins })
+}
+
+/// Reconstruct the `hmtx` table from the transformed one, where the left side bearings may
+/// be omitted if they are equal to the `xMin` of the glyphs.
+pub(crate) fn reconstruct_hmtx(
+    data: &[u8],
+    num_hor_metrics: u16,
+    x_mins: &[i16],
+) -> Result<Vec<u8>, FontError> {
+    const NO_PROPORTIONAL_LSBS: u8 = 0x01;
+    const NO_MONOSPACE_LSBS: u8 = 0x02;
+
+    let num_hor_metrics = num_hor_metrics as usize;
+    if num_hor_metrics == 0 || num_hor_metrics > x_mins.len() {
+        return Err(invalid(format!(
+            "invalid number of horizontal metrics {}",
+            num_hor_metrics
+        )));
+    }
+    let mut buffer = Buffer::new(data.to_vec());
+    let flags: u8 = buffer.get();
+    let advance_widths: Vec<u16> = buffer.get_vec(num_hor_metrics);
+    let proportional_lsbs: Vec<i16> = if flags & NO_PROPORTIONAL_LSBS != 0 {
+        x_mins[..num_hor_metrics].to_vec()
+    } else {
+        buffer.get_vec(num_hor_metrics)
+    };
+    let monospace_lsbs: Vec<i16> = if flags & NO_MONOSPACE_LSBS != 0 {
+        x_mins[num_hor_metrics..].to_vec()
+    } else {
+        buffer.get_vec(x_mins.len() - num_hor_metrics)
+    };
+    if let Some(err) = buffer.take_error() {
+        return Err(err.context("reconstructing 'hmtx'"));
+    }
+
+    let mut hmtx = Vec::with_capacity(num_hor_metrics * 2 + x_mins.len() * 2);
+    for (advance_width, lsb) in advance_widths.iter().zip(&proportional_lsbs) {
+        hmtx.extend(advance_width.to_be_bytes());
+        hmtx.extend(lsb.to_be_bytes());
+    }
+    monospace_lsbs
+        .iter()
+        .for_each(|lsb| hmtx.extend(lsb.to_be_bytes()));
+    Ok(hmtx)
+}
+
+/// Read a point from the flag stream and the glyph stream. Return the coordinate deltas
+/// and whether the point is on curve.
+fn read_triplet(flag_stream: &mut Buffer, glyph_stream: &mut Buffer) -> (i32, i32, bool) {
+    let with_sign = |flag: u8, n: i32| if flag & 1 != 0 { n } else { -n };
+
+    let flag: u8 = flag_stream.get();
+    let on_curve = flag & 0x80 == 0;
+    let flag = flag & 0x7F;
+    let data_len = match flag {
+        0..=83 => 1,
+        84..=119 => 2,
+        120..=123 => 3,
+        _ => 4,
+    };
+    let data: Vec<i32> = glyph_stream
+        .get_bytes(data_len)
+        .into_iter()
+        .map(i32::from)
+        .collect();
+    let flag_i32 = flag as i32;
+    let (dx, dy) = match flag {
+        0..=9 => (0, with_sign(flag, ((flag_i32 & 14) << 7) + data[0])),
+        10..=19 => (with_sign(flag, (((flag_i32 - 10) & 14) << 7) + data[0]), 0),
+        20..=83 => {
+            let b0 = flag_i32 - 20;
+            (
+                with_sign(flag, 1 + (b0 & 0x30) + (data[0] >> 4)),
+                with_sign(flag >> 1, 1 + ((b0 & 0x0C) << 2) + (data[0] & 0x0F)),
+            )
+        }
+        84..=119 => {
+            let b0 = flag_i32 - 84;
+            (
+                with_sign(flag, 1 + ((b0 / 12) << 8) + data[0]),
+                with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + data[1]),
+            )
+        }
+        120..=123 => (
+            with_sign(flag, (data[0] << 4) + (data[1] >> 4)),
+            with_sign(flag >> 1, ((data[1] & 0x0F) << 8) + data[2]),
+        ),
+        _ => (
+            with_sign(flag, (data[0] << 8) + data[1]),
+            with_sign(flag >> 1, (data[2] << 8) + data[3]),
+        ),
+    };
+    (dx, dy, on_curve)
+}
+
+/// Compute the bounding box of the points, whose coordinates are given as deltas.
+fn compute_bbox(points: &[(i32, i32, bool)]) -> [i16; 4] {
+    if points.is_empty() {
+        return [0; 4];
+    }
+    let (mut x, mut y) = (0, 0);
+    let (mut x_min, mut y_min, mut x_max, mut y_max) = (i32::MAX, i32::MAX, i32::MIN, i32::MIN);
+    for &(dx, dy, _) in points {
+        x += dx;
+        y += dy;
+        x_min = x_min.min(x);
+        y_min = y_min.min(y);
+        x_max = x_max.max(x);
+        y_max = y_max.max(y);
+    }
+    [x_min as i16, y_min as i16, x_max as i16, y_max as i16]
+}
+
+/// Write the flags and coordinates of a simple glyph. Flags are not repeated.
+fn write_simple_glyph_points(points: &[(i32, i32, bool)], overlap: bool, glyf: &mut Vec<u8>) {
+    let mut flags = Vec::with_capacity(points.len());
+    let mut x_coordinates = Vec::new();
+    let mut y_coordinates = Vec::new();
+    for (i, &(dx, dy, on_curve)) in points.iter().enumerate() {
+        let mut flag = if on_curve { ON_CURVE_POINT } else { 0 };
+        if overlap && i == 0 {
+            flag |= OVERLAP_SIMPLE;
+        }
+        flag |= write_coordinate(
+            dx,
+            X_SHORT_VECTOR,
+            X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR,
+            &mut x_coordinates,
+        );
+        flag |= write_coordinate(
+            dy,
+            Y_SHORT_VECTOR,
+            Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR,
+            &mut y_coordinates,
+        );
+        flags.push(flag);
+    }
+    glyf.extend(flags);
+    glyf.extend(x_coordinates);
+    glyf.extend(y_coordinates);
+}
+
+/// Write a coordinate delta in the shortest form, and return the flags of the form.
+fn write_coordinate(delta: i32, short_flag: u8, same_flag: u8, coordinates: &mut Vec<u8>) -> u8 {
+    if delta == 0 {
+        same_flag
+    } else if delta.abs() < 256 {
+        coordinates.push(delta.unsigned_abs() as u8);
+        if delta > 0 {
+            short_flag | same_flag
+        } else {
+            short_flag
+        }
+    } else {
+        coordinates.extend((delta as i16).to_be_bytes());
+        0
+    }
+}
+
+/// Copy the components of a composite glyph. Return whether the glyph has instructions.
+fn read_composite_glyph(composite_stream: &mut Buffer, glyf: &mut Vec<u8>) -> bool {
+    let mut has_instructions = false;
+    loop {
+        let flags: u16 = composite_stream.get();
+        // Glyph index and arguments
+        let mut len = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+            6
+        } else {
+            4
+        };
+        if flags & WE_HAVE_A_SCALE != 0 {
+            len += 2;
+        } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+            len += 4;
+        } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+            len += 8;
+        }
+        glyf.extend(flags.to_be_bytes());
+        glyf.extend(composite_stream.get_bytes(len));
+        has_instructions |= flags & WE_HAVE_INSTRUCTIONS != 0;
+        if flags & MORE_COMPONENTS == 0 {
+            return has_instructions;
+        }
+    }
+}
+
+#[test]
+fn test_reconstruct_glyf_loca() {
+    #[rustfmt::skip]
+    let data = vec![
+        0, 0, 0, 0, 0, 3, 0, 0, // 3 glyphs, short loca
+        0, 0, 0, 6, 0, 0, 0, 1, 0, 0, 0, 3, // nContour, nPoints, flag stream sizes
+        0, 0, 0, 4, 0, 0, 0, 6, 0, 0, 0, 12, // glyph, composite, bbox stream sizes
+        0, 0, 0, 0, // instruction stream size
+        0, 0, 0, 1, 0xFF, 0xFF, // nContours: empty, 1 contour, composite
+        3, // 3 points
+        3, 0x80 | 23, 12, // (0, +256), (+1, +1) off curve, (-256, 0)
+        0, 0, 0, 0, // triplet data, instruction length
+        0, 2, 0, 1, 10, 20, // glyph 1 offset by (10, 20)
+        0x20, 0, 0, 0, // bbox bitmap: glyph 2
+        0, 10, 0, 20, 0, 11, 0, 21, // bbox of glyph 2
+    ];
+    let ReconstructedGlyf { glyf, loca, x_mins } = reconstruct_glyf_loca(&data).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(glyf, vec![
+        0, 1, 0xFF, 0x01, 1, 0, 0, 1, 1, 1, // 1 contour, bbox (-255, 256, 1, 257)
+        0, 2, 0, 0, // end point 2, no instructions
+        0x11, 0x36, 0x21, // flags
+        1, 0xFF, 0, // x: 0, +1, -256
+        1, 0, 1, // y: +256, +1, 0
+        0, // padding
+        0xFF, 0xFF, 0, 10, 0, 20, 0, 11, 0, 21, // composite, bbox
+        0, 2, 0, 1, 10, 20, // component
+    ]);
+    assert_eq!(loca, vec![0, 0, 0, 0, 0, 12, 0, 20]);
+    assert_eq!(x_mins, vec![0, -255, 10]);
+
+    // Negative number of contours other than -1
+    let mut invalid_data = data.clone();
+    invalid_data[36..38].copy_from_slice(&[0xFF, 0xFE]);
+    assert!(reconstruct_glyf_loca(&invalid_data).is_err());
+    // A contour of 0 points
+    let mut invalid_data = data;
+    invalid_data[42] = 0;
+    assert!(reconstruct_glyf_loca(&invalid_data).is_err());
+}
+
+#[test]
+fn test_reconstruct_hmtx() {
+    let x_mins = [0, -255, 10];
+    // Left side bearings of both the proportional and monospaced glyphs are omitted
+    let hmtx = reconstruct_hmtx(&[0x03, 0, 100, 1, 0], 2, &x_mins).unwrap();
+    assert_eq!(hmtx, vec![0, 100, 0, 0, 1, 0, 0xFF, 0x01, 0, 10]);
+    // Only the proportional ones are omitted
+    let hmtx = reconstruct_hmtx(&[0x01, 0, 100, 1, 0, 0, 20], 2, &x_mins).unwrap();
+    assert_eq!(hmtx, vec![0, 100, 0, 0, 1, 0, 0xFF, 0x01, 0, 20]);
+    assert!(reconstruct_hmtx(&[0x01, 0, 100, 1, 0], 2, &x_mins).is_err());
+    assert!(reconstruct_hmtx(&[0x03], 4, &x_mins).is_err());
+}
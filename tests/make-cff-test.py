@@ -0,0 +1,165 @@
+#!/usr/bin/env python3
+"""Generate `tests/fixtures/CffTest.otf`, a minimal OpenType font with CFF outlines.
+
+The font has two glyphs, `.notdef` and `A` (a triangle drawn with a local subroutine), and
+only the required tables. Run from the root of the repository:
+
+    python3 tests/make-cff-test.py
+"""
+
+import struct
+
+
+def num(v):
+    """Encode an integer operand of a charstring or DICT."""
+    if -107 <= v <= 107:
+        return bytes([v + 139])
+    if 108 <= v <= 1131:
+        v -= 108
+        return bytes([(v >> 8) + 247, v & 0xFF])
+    if -1131 <= v <= -108:
+        v = -v - 108
+        return bytes([(v >> 8) + 251, v & 0xFF])
+    raise ValueError(v)
+
+
+def dict_int(v):
+    """Encode a DICT integer with 5 bytes, so that offsets can be computed in advance."""
+    return b"\x1d" + struct.pack(">i", v)
+
+
+def index(items):
+    """Encode a CFF INDEX with 1-byte offsets."""
+    if not items:
+        return b"\x00\x00"
+    offsets = [1]
+    for item in items:
+        offsets.append(offsets[-1] + len(item))
+    assert offsets[-1] < 256
+    return struct.pack(">HB", len(items), 1) + bytes(offsets) + b"".join(items)
+
+
+def cff_table():
+    rmoveto, rlineto, callsubr, ret, endchar = 21, 5, 10, 11, 14
+    # "-200 -700 rlineto return"
+    subr = num(-200) + num(-700) + bytes([rlineto, ret])
+    notdef = bytes([endchar])
+    # "50 100 0 rmoveto 400 0 -200 700 rlineto -107 callsubr endchar", with width 50
+    glyph_a = (
+        num(50) + num(100) + num(0) + bytes([rmoveto])
+        + num(400) + num(0) + num(-200) + num(700) + bytes([rlineto])
+        + num(-107) + bytes([callsubr, endchar])
+    )
+    # defaultWidthX 500, nominalWidthX 550, and the local subroutines right after
+    private = num(500) + b"\x14" + num(550) + b"\x15"
+    private += num(len(private) + 2) + b"\x13"
+    subrs = index([subr])
+
+    header = bytes([1, 0, 4, 1])
+    name_index = index([b"CFFTest-Regular"])
+    string_index = index([b"CFF Test Regular"])
+    global_subrs = index([])
+    charset = bytes([0]) + struct.pack(">H", 34)  # Format 0: SID 34 is "A"
+    char_strings = index([notdef, glyph_a])
+
+    top_dict_len = 3 + 6 + 6 + 11
+    start = len(header) + len(name_index) + len(index([bytes(top_dict_len)]))
+    charset_offset = start + len(string_index) + len(global_subrs)
+    char_strings_offset = charset_offset + len(charset)
+    private_offset = char_strings_offset + len(char_strings)
+    top_dict = (
+        num(391) + b"\x02"  # FullName: the first custom string
+        + dict_int(charset_offset) + b"\x0f"
+        + dict_int(char_strings_offset) + b"\x11"
+        + dict_int(len(private)) + dict_int(private_offset) + b"\x12"
+    )
+    assert len(top_dict) == top_dict_len
+    return (
+        header + name_index + index([top_dict]) + string_index + global_subrs
+        + charset + char_strings + private + subrs
+    )
+
+
+def name_table():
+    names = [
+        (1, "CFF Test"),
+        (2, "Regular"),
+        (4, "CFF Test Regular"),
+        (6, "CFFTest-Regular"),
+    ]
+    records, strings = b"", b""
+    for name_id, string in names:
+        data = string.encode("utf-16-be")
+        records += struct.pack(">6H", 3, 1, 0x409, name_id, len(data), len(strings))
+        strings += data
+    return struct.pack(">3H", 0, len(names), 6 + len(records)) + records + strings
+
+
+def cmap_table():
+    # Format 4 with the segments U+0041 => 1 and the final U+FFFF
+    subtable = struct.pack(
+        ">7H2HH2H2h2H",
+        4, 32, 0, 4, 4, 1, 0,
+        0x41, 0xFFFF, 0, 0x41, 0xFFFF, 1 - 0x41, 1, 0, 0,
+    )
+    return struct.pack(">2H2HI", 0, 1, 3, 1, 12) + subtable
+
+
+def os_2_table():
+    return (
+        struct.pack(">HhHHH11h", 4, 550, 400, 5, 0, 650, 600, 0, 75, 650, 600, 0, 350, 50, 250, 0)
+        + bytes([2, 0, 5, 3, 0, 0, 0, 0, 0, 0])  # PANOSE
+        + struct.pack(">4I", 1, 0, 0, 0)  # Basic Latin
+        + b"NONE"
+        + struct.pack(">3H3h2H2I5H", 0x40, 0x41, 0x41, 800, -200, 0, 800, 200, 1, 0,
+                      500, 700, 0, 0x20, 1)
+    )
+
+
+def checksum(data):
+    data += bytes(-len(data) % 4)
+    return sum(struct.unpack(">%dI" % (len(data) // 4), data)) & 0xFFFFFFFF
+
+
+def font():
+    head = struct.pack(
+        ">2IIIHHqq4hHHhhh",
+        0x00010000, 0x00010000, 0, 0x5F0F3CF5, 3, 1000, 0, 0,
+        0, -200, 600, 700, 0, 8, 2, 0, 0,
+    )
+    tables = {
+        b"CFF ": cff_table(),
+        b"OS/2": os_2_table(),
+        b"cmap": cmap_table(),
+        b"head": head,
+        b"hhea": struct.pack(">I3hH3h3h4hhH", 0x00010000, 800, -200, 0, 600, 0, 0, 600,
+                             1, 0, 0, 0, 0, 0, 0, 0, 2),
+        b"hmtx": struct.pack(">HhHh", 500, 0, 600, 50),
+        b"maxp": struct.pack(">IH", 0x00005000, 2),
+        b"name": name_table(),
+        b"post": struct.pack(">Iihh5I", 0x00030000, 0, -100, 50, 0, 0, 0, 0, 0),
+    }
+
+    num_tables = len(tables)
+    search_range = 1 << (num_tables.bit_length() - 1)
+    header = b"OTTO" + struct.pack(
+        ">4H", num_tables, search_range * 16, search_range.bit_length() - 1,
+        (num_tables - search_range) * 16,
+    )
+    offset = len(header) + 16 * num_tables
+    records, data = b"", b""
+    for tag, table in sorted(tables.items()):
+        records += tag + struct.pack(">3I", checksum(table), offset + len(data), len(table))
+        data += table + bytes(-len(table) % 4)
+    font = bytearray(header + records + data)
+
+    # checkSumAdjustment of `head`
+    head_offset = offset + data.index(head)
+    adjustment = (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF
+    font[head_offset + 8:head_offset + 12] = struct.pack(">I", adjustment)
+    return bytes(font)
+
+
+if __name__ == "__main__":
+    with open("tests/fixtures/CffTest.otf", "wb") as f:
+        f.write(font())
@@ -1,5 +1,13 @@
-use rustotf::FontContainer;
-use std::io::Result;
+use rustotf::layout_tags::{feature, script};
+use rustotf::{
+    cff_subr_bias, CharStringOperator, ContainerFormat, Font, FontContainer, FontError, FontFlavor,
+    GlyphClass, KernCorner, OutlineType, Table_OS_2, Table_gasp, Table_glyf, Table_maxp,
+    Table_post, Tag,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, FontError>;
 
 const FONTS_PATH: &str = "./tests/fonts/";
 
@@ -39,7 +47,6 @@ const WOFF_FONTS: &[&str] = &[
     "SourceSerif4Variable-Italic.otf.woff",
 ];
 
-#[allow(dead_code)]
 const WOFF2_FONTS: &[&str] = &[
     "SourceCodePro-Medium.otf.woff2",
     "SourceCodeVariable-Italic.ttf.woff2",
@@ -51,7 +58,7 @@ fn check_font(font_file_path: &str, flag: &str) -> Result<()> {
     let mut font_container = FontContainer::read(font_file_path)?;
     assert_ne!(font_container.len(), 0);
 
-    font_container.parse();
+    font_container.parse()?;
 
     macro_rules! _assert {
         ($font:ident, $t:ident, $s:expr) => {
@@ -129,3 +136,1102 @@ fn check_woff() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn check_woff2() -> Result<()> {
+    for i in WOFF2_FONTS {
+        let font_file_name = [FONTS_PATH, i].join("");
+        check_font(&font_file_name, "")?;
+    }
+    Ok(())
+}
+
+// Tests against the fonts committed under `tests/fixtures/`.
+
+const FIXTURES_PATH: &str = "./tests/fixtures/";
+
+fn read_fixture(font_file_name: &str) -> Result<FontContainer> {
+    let mut font_container = FontContainer::read(&[FIXTURES_PATH, font_file_name].join(""))?;
+    font_container.parse()?;
+    Ok(font_container)
+}
+
+#[test]
+fn cff_subr_bias_thresholds() {
+    assert_eq!(cff_subr_bias(0), 107);
+    assert_eq!(cff_subr_bias(1239), 107);
+    assert_eq!(cff_subr_bias(1240), 1131);
+    assert_eq!(cff_subr_bias(33899), 1131);
+    assert_eq!(cff_subr_bias(33900), 32768);
+    assert_eq!(cff_subr_bias(65535), 32768);
+}
+
+#[test]
+fn fixture_container() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    assert_eq!(font_container.len(), 1);
+    assert_eq!(font_container.format(), ContainerFormat::Sfnt);
+    assert!(!font_container.is_collection());
+    assert_eq!(
+        font_container.get(0).unwrap().flavor(),
+        FontFlavor::TrueType
+    );
+    assert_eq!(
+        font_container.get(0).unwrap().outline_type(),
+        OutlineType::TrueType
+    );
+    assert!(font_container.get(0).unwrap().has_outlines());
+
+    let summary = font_container.to_string();
+    assert!(summary.starts_with("Format: SFNT\nFonts:  1\n"));
+    assert!(summary.ends_with("0    DejaVuSans       TrueType    6253   2048"));
+    let unparsed = FontContainer::read(&[FIXTURES_PATH, "DejaVuSans.ttf"].join(""))?;
+    assert!(unparsed.to_string().contains("<unparsed>"));
+    Ok(())
+}
+
+#[test]
+fn fixture_container_mut() -> Result<()> {
+    let mut font_container = read_fixture("DejaVuSans.ttf")?;
+    font_container.get_mut(0).unwrap().GDEF = None;
+    assert!(font_container.get(0).unwrap().GDEF.is_none());
+    assert!(font_container.get_mut(1).is_none());
+
+    for font in &mut font_container {
+        font.post = None;
+    }
+    assert!(font_container
+        .fonts_mut()
+        .iter()
+        .all(|font| font.post.is_none()));
+    Ok(())
+}
+
+#[test]
+fn fixture_font_info() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let infos = font_container.infos();
+    assert_eq!(infos.len(), 1);
+
+    let info = &infos[0];
+    assert_eq!(info.family.as_deref(), Some("DejaVu Sans"));
+    assert_eq!(info.postscript_name.as_deref(), Some("DejaVuSans"));
+    assert_eq!(info.units_per_em, Some(2048));
+    assert_eq!(info.outline_type, OutlineType::TrueType);
+    assert!(!info.is_variable);
+    assert!(info.script_count > 0);
+    assert!(info
+        .to_string()
+        .starts_with("Family:          DejaVu Sans\n"));
+
+    // Tables in SFNT fonts are never compressed.
+    let font = font_container.get(0).unwrap();
+    assert!(!font.is_table_compressed(Tag::new(b"glyf")));
+    assert!(!font.is_table_compressed(Tag::new(b"CFF ")));
+    assert!(font.fmt_font_info("").contains("Compressed"));
+    Ok(())
+}
+
+#[test]
+fn fixture_required_tables() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+
+    let head = font.head.as_ref().unwrap();
+    assert_eq!(head.units_per_em, 2048);
+    assert_eq!(head.magic_number, 0x5F0F_3CF5);
+    assert!(head.is_long_loca() && !head.is_short_loca());
+    assert_eq!((head.x_min, head.y_min), (-2090, -948));
+    assert_eq!((head.x_max, head.y_max), (3673, 2524));
+
+    let hhea = font.hhea.as_ref().unwrap();
+    assert_eq!(hhea.ascender, 1901);
+    assert_eq!(hhea.descender, -483);
+    assert_eq!(hhea.num_hor_metrics, 6238);
+
+    let maxp = font.maxp.as_ref().unwrap();
+    assert_eq!(maxp.num_glyphs(), 6253);
+    assert!(maxp.is_version_10() && !maxp.is_version_05());
+    assert_eq!(maxp.max_points(), Some(852));
+    assert_eq!(maxp.max_stack_elements(), Some(1045));
+    assert_eq!(maxp.max_component_depth(), Some(4));
+
+    let os_2 = font.OS_2.as_ref().unwrap();
+    assert_eq!(os_2.us_weight_class, 400);
+    assert_eq!(os_2.us_width_class, 5);
+    assert_eq!(os_2.us_first_char_index, 0x20);
+    assert_eq!(font.computed_unicode_ranges(), os_2.unicode_ranges());
+    assert!(font.cross_validate().is_empty());
+    assert_eq!(Table_OS_2::unicode_range_for_codepoint(0x41), Some(0));
+    assert_eq!(Table_OS_2::unicode_range_for_codepoint(0x1D400), Some(89));
+    assert_eq!(Table_OS_2::unicode_range_for_codepoint(0x0860), None);
+
+    assert!(font.cmap.is_some());
+    assert!(font.name.is_some());
+    assert!(font.post.is_some());
+    Ok(())
+}
+
+#[test]
+fn fixture_cross_validate_head_loca() -> Result<()> {
+    let mut font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get_mut(0).unwrap();
+    font.head.as_mut().unwrap().magic_number = 0;
+    font.head.as_mut().unwrap().index_to_loc_format = 0;
+    assert_eq!(
+        font.cross_validate(),
+        [
+            "head magicNumber is 0x00000000, but should be 0x5F0F3CF5",
+            "head indexToLocFormat is 0, but the size of loca (25016 bytes) matches the other format",
+        ]
+    );
+    font.head.as_mut().unwrap().magic_number = 0x5F0F_3CF5;
+    font.head.as_mut().unwrap().index_to_loc_format = 1;
+    font.maxp.as_mut().unwrap().num_glyphs = 6000;
+    assert_eq!(
+        font.cross_validate(),
+        ["loca is 25016 bytes, but maxp numGlyphs is 6000"]
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn fixture_serde() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+
+    let json = serde_json::to_string(&font.head).unwrap();
+    assert!(json.contains(r#""units_per_em":2048"#));
+    assert!(json.contains(r#""magic_number":1594834165"#));
+    let head: Option<rustotf::Table_head> = serde_json::from_str(&json).unwrap();
+    assert_eq!(head.unwrap().x_min, -2090);
+
+    let json = serde_json::to_string(&font.GSUB).unwrap();
+    assert!(json.contains(r#""script_tag":"latn""#));
+    let gsub: Option<rustotf::Table_GSUB> = serde_json::from_str(&json).unwrap();
+    assert_eq!(gsub.unwrap().lookup_count(), 40);
+
+    let tables = font.tables_to_json(&["maxp"])?;
+    assert_eq!(tables["maxp"]["num_glyphs"], 6253);
+    assert!(font.tables_to_json(&["maxp", "kern"]).is_err());
+    assert!(font.tables_to_json(&[])?.get("GPOS").is_some());
+    Ok(())
+}
+
+#[test]
+fn fixture_dump_table_to_writer() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+    let mut output = Vec::new();
+    font.dump_table_to_writer(Tag::from("maxp"), &mut output)?;
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("Table_maxp {"));
+    assert!(output.contains("num_glyphs: 6253"));
+    assert_eq!(output, font.fmt_tables(&["maxp"]));
+    assert!(font
+        .dump_table_to_writer(Tag::from("gvar"), &mut Vec::new())
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn fixture_unicode_ranges() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+    let ranges = font.all_unicode_ranges();
+    assert_eq!(ranges.len(), 281);
+    assert_eq!(ranges[..3], [(0x20, 0x7E), (0xA0, 0x2E9), (0x2EC, 0x2EE)]);
+
+    let cmap = font.cmap.as_ref().unwrap();
+    assert_eq!(cmap.all_encodings().len(), 5);
+    let best_encoding = |platform_id| {
+        cmap.best_encoding_for_platform(platform_id)
+            .map(|rec| rec.encoding_id)
+    };
+    assert_eq!(best_encoding(0), Some(4));
+    assert_eq!(best_encoding(1), Some(0));
+    assert_eq!(best_encoding(3), Some(10));
+    assert_eq!(best_encoding(2), None);
+    assert_eq!(cmap.subtable_format(3, 10), Some(12));
+    assert_eq!(cmap.subtable_format(1, 0), Some(6));
+    assert_eq!(cmap.subtable_format(3, 0), None);
+    assert_eq!(cmap.maps.len(), 5);
+    assert_eq!(cmap.lookup(0x41), Some(36));
+    assert_eq!(cmap.lookup(0x10FFFF), None);
+    assert_eq!(font.glyph_id_for_codepoint(0x41), Some(36));
+    assert_eq!(font.codepoints_for_glyph_id(36), vec![0x41]);
+    assert!(font.codepoints_for_glyph_id(0xFFFF).is_empty());
+    assert_eq!(Font::default().glyph_id_for_codepoint(0x41), None);
+    Ok(())
+}
+
+#[test]
+fn fixture_glyf() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+
+    let glyphs = &font.glyf.as_ref().unwrap().glyphs;
+    assert_eq!(glyphs.len(), 6253);
+
+    // Glyph 36 is "A".
+    let glyph = &glyphs[36];
+    assert_eq!((glyph.x_min, glyph.y_min), (16, 0));
+    assert_eq!((glyph.x_max, glyph.y_max), (1384, 1493));
+
+    assert_eq!(font.advance_width(36), Some(1401));
+    assert_eq!(font.lsb(36), Some(glyph.x_min));
+    // Glyphs after the last long metric record share its advance width
+    assert_eq!(font.advance_width(6252), Some(1508));
+    assert_eq!(font.lsb(6253), None);
+    assert_eq!(font.advance_height(36), None);
+    Ok(())
+}
+
+#[test]
+fn fixture_gasp() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let gasp = font_container.get(0).unwrap().gasp.as_ref().unwrap();
+    assert_eq!(gasp.ranges().collect::<Vec<_>>(), vec![(8, 2), (0xFFFF, 3)]);
+
+    let small = gasp.behavior_for_ppem(8);
+    assert!(small.dogray && !small.gridfit);
+    let large = gasp.behavior_for_ppem(9);
+    assert!(large.dogray && large.gridfit);
+    Ok(())
+}
+
+#[test]
+fn fixture_gsub() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let gsub = font_container.get(0).unwrap().GSUB.as_ref().unwrap();
+
+    assert!(gsub
+        .script_list
+        .iter()
+        .any(|rec| rec.script_tag == script::LATN));
+    let liga: Vec<_> = gsub
+        .feature_list
+        .iter()
+        .filter(|rec| rec.feature_tag == feature::LIGA)
+        .collect();
+    assert!(!liga.is_empty());
+    liga.iter().for_each(|rec| {
+        assert!(!rec.feature.lookup_list_indices.is_empty());
+        rec.feature
+            .lookup_list_indices
+            .iter()
+            .for_each(|&i| assert!((i as usize) < gsub.lookup_list.len()));
+    });
+
+    // Lookup 30 is an alternate substitution (type 3) for glyph 44 ("I").
+    assert_eq!(gsub.lookup(30).unwrap().lookup_type_name(), "Alternate");
+    assert!(gsub.lookup(u16::MAX).is_none());
+    assert_eq!(gsub.alternate_glyphs(30, 44), Some(vec![6015]));
+    assert_eq!(gsub.alternate_glyphs(30, 36), None);
+    assert_eq!(gsub.multiple_substitute(30, 44), None);
+
+    // Single substitutions: lookup 0 (`case`) uses format 2 and lookup 34 uses format 1.
+    assert_eq!(gsub.lookup(0).unwrap().lookup_type_name(), "Single");
+    assert_eq!(gsub.single_substitute(0, 99), Some(6213));
+    assert_eq!(gsub.single_substitute(0, 4696), Some(6215));
+    assert_eq!(gsub.single_substitute(0, 36), None);
+    assert_eq!(gsub.single_substitute(34, 679), Some(4945));
+    assert_eq!(gsub.single_substitute(34, 682), Some(4948));
+    assert_eq!(gsub.single_substitute(30, 44), None);
+    assert_eq!(gsub.apply_single(99), Some(6213));
+    assert_eq!(gsub.apply_single(36), None);
+
+    // Lookup 18 replaces "f" (glyph 73) and "i" (glyph 76) with "fi".
+    assert_eq!(gsub.lookup(18).unwrap().lookup_type_name(), "Ligature");
+    assert_eq!(gsub.ligature_substitute(18, &[73, 76, 36]), Some((5042, 2)));
+    assert_eq!(gsub.ligature_substitute(18, &[73, 36]), None);
+
+    assert_eq!(gsub.script_count(), gsub.script_list.len());
+    assert_eq!(gsub.lookup_count(), gsub.lookup_list.len());
+    assert!(gsub.feature_by_tag(feature::LIGA).is_some());
+    assert!(gsub.feature_by_tag(feature::KERN).is_none());
+
+    // No feature variations in version 1.0
+    assert!(gsub.feature_variations.is_none());
+    let active_features = gsub.active_features(&HashMap::new(), None);
+    assert!(active_features.contains(&feature::LIGA));
+    let coords = [(Tag::new(b"wght"), 1.0)].iter().copied().collect();
+    assert_eq!(active_features, gsub.active_features(&coords, None));
+
+    // Lookup 17 (`liga`) skips marks, e.g. the combining acute accent (glyph 690).
+    let gdef = font_container.get(0).unwrap().GDEF.as_ref().unwrap();
+    let lookup = gsub.lookup(17).unwrap();
+    assert!(lookup.flags().ignore_marks);
+    assert!(!lookup.flags().use_mark_filtering_set);
+    assert_eq!(lookup.mark_filtering_set, None);
+    assert!(!lookup.effective_for_glyph(690, gdef));
+    assert!(lookup.effective_for_glyph(36, gdef));
+    Ok(())
+}
+
+#[test]
+fn fixture_gsub_chained_context() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let gsub = font_container.get(0).unwrap().GSUB.as_ref().unwrap();
+
+    // Lookup 3 replaces "i" (glyph 76) before a combining acute accent (glyph 690).
+    let records = gsub.context_substitute(3, &[76, 690], 0);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].glyph_sequence_index, 0);
+    assert_eq!(records[0].lookup_list_index, 33);
+    assert!(gsub.context_substitute(3, &[76, 36], 0).is_empty());
+
+    // `ccmp` refers to the chained context lookups 3 and 4, while `liga` has none.
+    assert!(gsub.has_contextual_rules_for_feature(feature::CCMP));
+    assert!(!gsub.has_contextual_rules_for_feature(feature::LIGA));
+    assert!(!gsub.has_contextual_rules_for_feature(feature::KERN));
+    assert!(gsub.has_mark_substitution(font_container.get(0).unwrap().GDEF.as_ref().unwrap()));
+    Ok(())
+}
+
+#[test]
+fn fixture_gpos_mark_attachment() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let gpos = font_container.get(0).unwrap().GPOS.as_ref().unwrap();
+
+    // U+0301 (glyph 690) on "A" (glyph 36)
+    let (mark_anchor, base_anchor) = gpos.mark_base_attachment(690, 36).unwrap();
+    assert_eq!(mark_anchor.coordinate(), (-512, 1147));
+    assert_eq!(base_anchor.coordinate(), (700, 1520));
+    let (_, mark2_anchor) = gpos.mark_mark_attachment(690, 690).unwrap();
+    assert_eq!(mark2_anchor.coordinate(), (-512, 1640));
+    assert!(gpos.mark_base_attachment(36, 690).is_none());
+    Ok(())
+}
+
+#[test]
+fn fixture_gpos_mark_ligature_attachment() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let gpos = font_container.get(0).unwrap().GPOS.as_ref().unwrap();
+
+    let (mark_anchor, ligature_anchor) = gpos.mark_ligature_attachment(1297, 5359, 1).unwrap();
+    assert_eq!(mark_anchor.coordinate(), (655, 0));
+    assert_eq!(ligature_anchor.coordinate(), (200, -150));
+    assert!(gpos.mark_ligature_attachment(1297, 5359, 9).is_none());
+    assert_eq!(gpos.lookup(5).unwrap().lookup_type, 5);
+    Ok(())
+}
+
+#[test]
+fn fixture_gpos_kern_for_pair() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let gpos = font_container.get(0).unwrap().GPOS.as_ref().unwrap();
+
+    assert_eq!(gpos.kern_for_pair(36, 57), -131); // "AV"
+    assert_eq!(gpos.kern_for_pair(55, 82), -348); // "To"
+    assert_eq!(gpos.kern_for_pair(690, 36), 0);
+    assert!(gpos.feature_by_tag(feature::KERN).is_some());
+    Ok(())
+}
+
+#[test]
+fn fixture_gdef() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let gdef = font_container.get(0).unwrap().GDEF.as_ref().unwrap();
+
+    assert_eq!(gdef.glyph_class(36), Some(GlyphClass::Base)); // A
+    assert_eq!(gdef.glyph_class(690), Some(GlyphClass::Mark)); // U+0301
+    assert_eq!(gdef.glyph_class(5042), Some(GlyphClass::Ligature)); // U+FB01
+    assert_eq!(gdef.glyph_class(0), None); // .notdef
+    assert!(gdef.attach_list.is_none());
+    assert!(gdef.lig_carets(5042).is_none());
+    Ok(())
+}
+
+#[test]
+fn fixture_glyph_bounding_box() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+
+    let (x_min, y_min, x_max, y_max) = font.glyph_bounding_box(36).unwrap(); // A
+    assert!(x_min < x_max && y_min < y_max);
+    assert_eq!(y_min, 0);
+    assert_eq!(font.glyph_bounding_box(3), Some((0, 0, 0, 0))); // space
+    assert_eq!(font.glyph_bounding_box(u16::MAX), None);
+    Ok(())
+}
+
+#[test]
+fn fixture_contains_glyph() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+    assert!(font.contains_glyph(0));
+    assert!(font.contains_glyph(36)); // A
+    assert!(!font.contains_glyph(3)); // space
+    assert!(!font.contains_glyph(6253));
+
+    // Glyph 1 is an empty glyph with zero advance width
+    assert!(font.is_blank_glyph(1));
+    assert!(!font.is_blank_glyph(3));
+    assert!(!font.is_blank_glyph(36));
+    Ok(())
+}
+
+#[test]
+fn fixture_hinted() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let font = font_container.get(0).unwrap();
+    assert!(font.hinted());
+    assert_eq!(font.fpgm_size(), 171);
+    assert_eq!(font.prep_size(), 1384);
+    assert_eq!(font.cvt_size(), 510);
+
+    // Parsing only the table directory does not read any instructions
+    let unparsed = FontContainer::read(&[FIXTURES_PATH, "DejaVuSans.ttf"].join(""))?;
+    assert!(!unparsed.get(0).unwrap().hinted());
+    Ok(())
+}
+
+#[test]
+fn fixture_post_glyph_name() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let post = font_container.get(0).unwrap().post.as_ref().unwrap();
+
+    assert!(!post.is_no_name_font());
+    assert_eq!(post.glyph_name(194), Some("Amacron"));
+    // Standard Macintosh glyph names
+    assert_eq!(post.glyph_name(36), Some("A"));
+    assert_eq!(post.glyph_name(5042), Some("fi"));
+    assert_eq!(post.glyph_id("Amacron"), Some(194));
+    assert_eq!(post.glyph_id("no-such-glyph"), None);
+
+    let names = font_container.get(0).unwrap().all_glyph_names();
+    assert_eq!(names.len(), 6253);
+    assert_eq!(names[0].as_deref(), Some(".notdef"));
+    assert_eq!(names[194].as_deref(), Some("Amacron"));
+
+    let font = font_container.get(0).unwrap();
+    assert_eq!(font.glyph_name(194).as_deref(), Some("Amacron"));
+    assert_eq!(font.glyph_name(6253), None);
+    assert_eq!(Font::default().glyph_name(42).as_deref(), Some("gid42"));
+    Ok(())
+}
+
+#[test]
+fn fixture_math() -> Result<()> {
+    let font_container = read_fixture("DejaVuMathTeXGyre.ttf")?;
+    let font = font_container.get(0).unwrap();
+    assert_eq!(font.head.as_ref().unwrap().units_per_em, 1000);
+
+    let math_constants = &font.MATH.as_ref().unwrap().math_constants;
+    assert_eq!(math_constants.script_percent_scale_down, 80);
+    assert_eq!(math_constants.axis_height.value, 275);
+
+    // The font has no math kern info
+    let math = font.MATH.as_ref().unwrap();
+    assert_eq!(math.kern_value(36, KernCorner::TopRight, 0), None);
+
+    let math_glyph_info = &math.math_glyph_info;
+    assert_eq!(math_glyph_info.italics_correction(73), Some(88));
+    assert_eq!(math_glyph_info.top_accent_attachment(36), Some(359));
+    assert_eq!(math_glyph_info.top_accent_attachment(11), None);
+    assert!(math_glyph_info.is_extended_shape(4229));
+    assert!(!math_glyph_info.is_extended_shape(36));
+    Ok(())
+}
+
+#[test]
+fn fixture_parse_from_ttc_index() -> Result<()> {
+    let path = [FIXTURES_PATH, "DejaVuSans.ttf"].join("");
+    let font = Font::parse_from_ttc_index(&path, 0)?;
+    assert_eq!(font.head.as_ref().unwrap().units_per_em, 2048);
+    assert!(Font::parse_from_ttc_index(&path, 1).is_err());
+    Ok(())
+}
+
+#[test]
+fn fixture_parse_nth_table() -> Result<()> {
+    let path = [FIXTURES_PATH, "DejaVuSans.ttf"].join("");
+    let mut font_container = FontContainer::read(&path)?;
+    assert!(font_container.contains_font(0));
+    assert!(!font_container.contains_font(1));
+
+    assert!(font_container.parse_nth_table(0, Tag::new(b"gasp")).is_ok());
+    assert!(font_container.get(0).unwrap().gasp.is_some());
+    assert!(matches!(
+        font_container.parse_nth_table(1, Tag::new(b"gasp")),
+        Err(FontError::FontIndexOutOfRange { index: 1, len: 1 })
+    ));
+    assert!(matches!(
+        font_container.parse_nth_table(0, Tag::new(b"CFF ")),
+        Err(FontError::TableNotFound(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn fixture_parse_and_get_table() -> Result<()> {
+    let path = [FIXTURES_PATH, "DejaVuSans.ttf"].join("");
+    let mut font_container = FontContainer::read(&path)?;
+    let maxp_tag = Tag::new(b"maxp");
+    let maxp = font_container.parse_and_get_table::<Table_maxp>(0, maxp_tag);
+    assert_eq!(maxp.unwrap().num_glyphs, 6253);
+    assert!(font_container
+        .get(0)
+        .unwrap()
+        .get_table::<Table_maxp>(maxp_tag)
+        .is_some());
+
+    assert!(font_container
+        .parse_and_get_table::<Table_gasp>(0, maxp_tag)
+        .is_none());
+    assert!(font_container
+        .parse_and_get_table::<Table_maxp>(1, maxp_tag)
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn fixture_cff() -> Result<()> {
+    use CharStringOperator::*;
+
+    // Generated by `tests/make-cff-test.py`
+    let font_container = read_fixture("CffTest.otf")?;
+    let font = font_container.get(0).unwrap();
+    assert_eq!(font.outline_type(), OutlineType::Cff1);
+    assert!(font.cross_validate().is_empty());
+
+    let cff = &font.CFF_.as_ref().unwrap().cff_fonts()[0];
+    assert_eq!(cff.name(), "CFFTest-Regular");
+    assert_eq!(cff.full_name(), "CFF Test Regular");
+    assert_eq!(cff.glyph_name(1), Some("A"));
+    assert_eq!(cff.local_subrs_count(), 1);
+    assert_eq!(cff.char_string(0).unwrap().width(), None);
+
+    // "50 100 0 rmoveto 400 0 -200 700 rlineto -107 callsubr endchar", where the subroutine
+    // is "-200 -700 rlineto return"
+    let char_string = cff.char_string(1).unwrap();
+    assert_eq!(char_string.width(), Some(50.0));
+    let commands: Vec<(CharStringOperator, &[f64])> = char_string
+        .commands()
+        .iter()
+        .map(|command| (command.operator, &command.args[..]))
+        .collect();
+    assert_eq!(
+        commands,
+        [
+            (Rmoveto, &[100.0, 0.0][..]),
+            (Rlineto, &[400.0, 0.0, -200.0, 700.0]),
+            (Rlineto, &[-200.0, -700.0]),
+            (Endchar, &[]),
+        ]
+    );
+    assert_eq!(font.advance_width(1), Some(600));
+    Ok(())
+}
+
+#[test]
+fn fixture_lazy() -> Result<()> {
+    let path = [FIXTURES_PATH, "DejaVuSans.ttf"].join("");
+    let mut font_container = FontContainer::read(&path)?;
+    font_container.set_lazy(true);
+    assert!(font_container.is_lazy());
+    font_container.parse()?;
+    assert!(font_container.get(0).unwrap().head.is_none());
+
+    // `glyf` depends on `loca`, which depends on `head` and `maxp`
+    let glyf = font_container.parse_and_get_table::<Table_glyf>(0, Tag::new(b"glyf"));
+    assert!(glyf.is_some());
+    let font = font_container.get(0).unwrap();
+    assert!(font.loca.is_some() && font.head.is_some() && font.maxp.is_some());
+    assert!(font.hhea.is_none() && font.cmap.is_none());
+
+    font_container.parse_table(Tag::new(b"hmtx"))?;
+    assert_eq!(font_container.get(0).unwrap().advance_width(36), Some(1401));
+
+    // `post` does not need `maxp` for looking up glyphs by their names
+    let mut font_container = FontContainer::read(&path)?;
+    font_container.set_lazy(true);
+    let post = font_container.parse_and_get_table::<Table_post>(0, Tag::new(b"post"));
+    assert_eq!(post.unwrap().glyph_id("Amacron"), Some(194));
+    assert!(font_container.get(0).unwrap().maxp.is_none());
+    Ok(())
+}
+
+#[test]
+fn fixture_read_from_path() -> Result<()> {
+    let path = Path::new(FIXTURES_PATH).join("DejaVuSans.ttf");
+    assert_eq!(FontContainer::read_from_path(&path)?.len(), 1);
+    assert_eq!(
+        FontContainer::read_from_path_lossy(path.as_os_str())?.len(),
+        1
+    );
+    assert!(FontContainer::read_from_path(path.with_extension("otf")).is_err());
+    Ok(())
+}
+
+#[test]
+fn fixture_from_bytes() -> Result<()> {
+    let bytes = std::fs::read([FIXTURES_PATH, "DejaVuSans.ttf"].join(""))?;
+    let mut font_container = FontContainer::from_reader(bytes.as_slice())?;
+    font_container.parse_nth_table(0, Tag::new(b"head"))?;
+    let font = font_container.get(0).unwrap();
+    assert_eq!(font.head.as_ref().unwrap().units_per_em, 2048);
+    assert_eq!(FontContainer::from_bytes(bytes)?.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn fixture_malformed() -> Result<()> {
+    let bytes = std::fs::read([FIXTURES_PATH, "DejaVuSans.ttf"].join(""))?;
+    let path = std::env::temp_dir().join("rustotf-fixture-malformed.ttf");
+
+    let mut bad_signature = bytes.clone();
+    bad_signature[..4].copy_from_slice(b"abcd");
+    std::fs::write(&path, bad_signature)?;
+    assert!(matches!(
+        FontContainer::read_from_path(&path),
+        Err(FontError::InvalidMagic(0x6162_6364))
+    ));
+
+    // The table directory is kept, but the tables are cut off
+    std::fs::write(&path, &bytes[..1024])?;
+    let mut font_container = FontContainer::read_from_path(&path)?;
+    assert!(matches!(
+        font_container.parse(),
+        Err(FontError::InvalidOffset { .. })
+    ));
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn fixture_verify_checksums() -> Result<()> {
+    let path = [FIXTURES_PATH, "DejaVuSans.ttf"].join("");
+    let font_container = FontContainer::read(&path)?;
+    assert_eq!(font_container.verify_checksums(), vec![Vec::<Tag>::new()]);
+
+    // Corrupt the first byte of `maxp`, whose offset is in the table directory
+    let mut bytes = std::fs::read(&path)?;
+    let num_tables = u16::from_be_bytes([bytes[4], bytes[5]]) as usize;
+    let record = (0..num_tables)
+        .map(|i| 12 + i * 16)
+        .find(|&rec| &bytes[rec..rec + 4] == b"maxp")
+        .unwrap();
+    let offset = bytes[record + 8..record + 12]
+        .iter()
+        .fold(0, |acc, &b| acc << 8 | b as usize);
+    bytes[offset] ^= 0xFF;
+    let mut font_container = FontContainer::from_bytes(bytes)?;
+    assert_eq!(
+        font_container.verify_checksums(),
+        vec![vec![Tag::new(b"maxp")]]
+    );
+    // Checksum mismatches don't prevent parsing
+    font_container.parse()?;
+    Ok(())
+}
+
+#[test]
+fn fixture_truncated() -> Result<()> {
+    assert!(matches!(
+        FontContainer::from_bytes(Vec::new()),
+        Err(FontError::BufferOverflow { offset: 0, .. })
+    ));
+    // The table directory is truncated
+    let bytes = std::fs::read([FIXTURES_PATH, "DejaVuSans.ttf"].join(""))?;
+    assert!(matches!(
+        FontContainer::from_bytes(bytes[..100].to_vec()),
+        Err(FontError::BufferOverflow { offset: 100, .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn fixture_iter_fonts() -> Result<()> {
+    let mut font_container = read_fixture("DejaVuSans.ttf")?;
+    assert_eq!(font_container.iter_fonts().count(), 1);
+    assert_eq!(font_container.fonts().len(), font_container.len());
+    assert!(font_container.as_single_font().is_some());
+
+    font_container
+        .iter_fonts_mut()
+        .for_each(|font| font.gasp = None);
+    let fonts = font_container.into_fonts();
+    assert!(matches!(fonts.as_slice(), [font] if font.gasp.is_none()));
+
+    let font = read_fixture("DejaVuSans.ttf")?.into_font().ok().unwrap();
+    assert_eq!(font.head.as_ref().unwrap().units_per_em, 2048);
+    Ok(())
+}
+
+#[test]
+fn fixture_embed_svg_glyph() -> Result<()> {
+    let path = [FIXTURES_PATH, "DejaVuSans.ttf"].join("");
+    let mut font = Font::parse_from_ttc_index(&path, 0)?;
+    assert!(font.SVG_.is_none());
+
+    font.embed_svg_glyph(36, "<svg/>");
+    let svg = font.SVG_.as_mut().unwrap();
+    svg.set_glyph(40, 50, "<svg></svg>");
+    assert_eq!(svg.glyph_ids(), vec![36..=36, 40..=50]);
+    assert!(svg.remove_glyph(45));
+    assert!(!svg.remove_glyph(45));
+    assert_eq!(svg.glyph_ids(), vec![36..=36, 40..=44, 46..=50]);
+    assert_eq!(svg.document_count(), 3);
+    assert_eq!(svg.doc_records[2].svg_doc, "<svg></svg>");
+    Ok(())
+}
+
+#[test]
+fn fixture_name() -> Result<()> {
+    let font_container = read_fixture("DejaVuSans.ttf")?;
+    let name = font_container.get(0).unwrap().name.as_ref().unwrap();
+    let family_name = name.name_record(1).unwrap();
+    assert_eq!(family_name.platform_id, 3);
+    assert_eq!(family_name.string, "DejaVu Sans");
+    assert!(format!("{}", family_name).contains("name_id: 1 (Family name)"));
+    assert!(format!("{:?}", family_name).contains("name_id: 1,"));
+    let mut output = Vec::new();
+    font_container
+        .get(0)
+        .unwrap()
+        .dump_table_to_writer(Tag::from("name"), &mut output)?;
+    assert_eq!(String::from_utf8(output).unwrap(), format!("{:#}\n", name));
+    assert!(name.name_record(25).is_none());
+    assert_eq!(name.family_name().as_deref(), Some("DejaVu Sans"));
+    assert_eq!(name.best_string(6).as_deref(), Some("DejaVuSans"));
+    Ok(())
+}
+
+/// Return the offset and length of the table `tag` in an SFNT font.
+fn sfnt_table(sfnt: &[u8], tag: &[u8]) -> (usize, usize) {
+    let read_u32 = |i: usize| u32::from_be_bytes([sfnt[i], sfnt[i + 1], sfnt[i + 2], sfnt[i + 3]]);
+    let num_tables = u16::from_be_bytes([sfnt[4], sfnt[5]]) as usize;
+    let record = (0..num_tables)
+        .map(|i| 12 + 16 * i)
+        .find(|&record| &sfnt[record..record + 4] == tag)
+        .unwrap();
+    (
+        read_u32(record + 8) as usize,
+        read_u32(record + 12) as usize,
+    )
+}
+
+/// Transform the `glyf` table of an SFNT font as in WOFF2. All the glyphs are stored with
+/// explicit bounding boxes, and all the points with the 4-byte triplets.
+fn transform_glyf(sfnt: &[u8]) -> Vec<u8> {
+    fn read_u16(bytes: &[u8], i: usize) -> u16 {
+        u16::from_be_bytes([bytes[i], bytes[i + 1]])
+    }
+    fn push_u16_var(stream: &mut Vec<u8>, n: u16) {
+        if n < 253 {
+            stream.push(n as u8);
+        } else {
+            stream.push(253);
+            stream.extend(&n.to_be_bytes());
+        }
+    }
+    fn read_coordinates(
+        glyph: &[u8],
+        i: &mut usize,
+        flags: &[u8],
+        short: u8,
+        same: u8,
+    ) -> Vec<i32> {
+        flags
+            .iter()
+            .map(|&flag| {
+                if flag & short != 0 {
+                    *i += 1;
+                    let n = glyph[*i - 1] as i32;
+                    if flag & same != 0 {
+                        n
+                    } else {
+                        -n
+                    }
+                } else if flag & same != 0 {
+                    0
+                } else {
+                    *i += 2;
+                    read_u16(glyph, *i - 2) as i16 as i32
+                }
+            })
+            .collect()
+    }
+
+    let (head, _) = sfnt_table(sfnt, b"head");
+    let (maxp, _) = sfnt_table(sfnt, b"maxp");
+    let (loca, _) = sfnt_table(sfnt, b"loca");
+    let (glyf, glyf_len) = sfnt_table(sfnt, b"glyf");
+    let index_format = read_u16(sfnt, head + 50);
+    let num_glyphs = read_u16(sfnt, maxp + 4) as usize;
+    let offsets: Vec<usize> = (0..=num_glyphs)
+        .map(|i| match index_format {
+            0 => read_u16(sfnt, loca + 2 * i) as usize * 2,
+            _ => {
+                read_u16(sfnt, loca + 4 * i) as usize * 65536
+                    + read_u16(sfnt, loca + 4 * i + 2) as usize
+            }
+        })
+        .collect();
+    let glyf = &sfnt[glyf..glyf + glyf_len];
+
+    let (mut n_contour_stream, mut n_points_stream, mut flag_stream) = (vec![], vec![], vec![]);
+    let (mut glyph_stream, mut composite_stream, mut instruction_stream) = (vec![], vec![], vec![]);
+    let mut bbox_stream = vec![0; 4 * num_glyphs.div_ceil(32)];
+    for glyph_id in 0..num_glyphs {
+        let glyph = &glyf[offsets[glyph_id]..offsets[glyph_id + 1]];
+        if glyph.is_empty() {
+            n_contour_stream.extend(&[0, 0]);
+            continue;
+        }
+        n_contour_stream.extend(&glyph[0..2]);
+        bbox_stream[glyph_id >> 3] |= 0x80 >> (glyph_id & 7);
+        let mut instructions: &[u8] = &[];
+        let n_contours = read_u16(glyph, 0) as i16;
+        if n_contours < 0 {
+            let mut i = 10;
+            let mut has_instructions = false;
+            loop {
+                let flags = read_u16(glyph, i);
+                let mut len = if flags & 0x0001 != 0 { 8 } else { 6 };
+                if flags & 0x0008 != 0 {
+                    len += 2;
+                } else if flags & 0x0040 != 0 {
+                    len += 4;
+                } else if flags & 0x0080 != 0 {
+                    len += 8;
+                }
+                composite_stream.extend(&glyph[i..i + len]);
+                i += len;
+                has_instructions |= flags & 0x0100 != 0;
+                if flags & 0x0020 == 0 {
+                    break;
+                }
+            }
+            if has_instructions {
+                instructions = &glyph[i + 2..i + 2 + read_u16(glyph, i) as usize];
+            }
+        } else {
+            let mut num_points = 0;
+            for contour in 0..n_contours as usize {
+                let end_point = read_u16(glyph, 10 + 2 * contour) + 1;
+                push_u16_var(&mut n_points_stream, end_point - num_points);
+                num_points = end_point;
+            }
+            let mut i = 10 + 2 * n_contours as usize;
+            instructions = &glyph[i + 2..i + 2 + read_u16(glyph, i) as usize];
+            i += 2 + instructions.len();
+            let mut flags = Vec::new();
+            while flags.len() < num_points as usize {
+                let flag = glyph[i];
+                flags.push(flag);
+                i += 1;
+                // Repeated flags
+                if flag & 0x08 != 0 {
+                    flags.extend(std::iter::repeat_n(flag, glyph[i] as usize));
+                    i += 1;
+                }
+            }
+            let xs = read_coordinates(glyph, &mut i, &flags, 0x02, 0x10);
+            let ys = read_coordinates(glyph, &mut i, &flags, 0x04, 0x20);
+            for ((dx, dy), flag) in xs.into_iter().zip(ys).zip(flags) {
+                let off_curve = if flag & 0x01 == 0 { 0x80 } else { 0 };
+                flag_stream.push(off_curve | 124 | (dx >= 0) as u8 | ((dy >= 0) as u8) << 1);
+                glyph_stream.extend(&(dx.unsigned_abs() as u16).to_be_bytes());
+                glyph_stream.extend(&(dy.unsigned_abs() as u16).to_be_bytes());
+            }
+        }
+        if n_contours >= 0 || !instructions.is_empty() {
+            push_u16_var(&mut glyph_stream, instructions.len() as u16);
+            instruction_stream.extend(instructions);
+        }
+        bbox_stream.extend(&glyph[2..10]);
+    }
+
+    let mut transformed = vec![0, 0, 0, 0];
+    transformed.extend(&(num_glyphs as u16).to_be_bytes());
+    transformed.extend(&index_format.to_be_bytes());
+    let streams = [
+        n_contour_stream,
+        n_points_stream,
+        flag_stream,
+        glyph_stream,
+        composite_stream,
+        bbox_stream,
+        instruction_stream,
+    ];
+    for stream in &streams {
+        transformed.extend(&(stream.len() as u32).to_be_bytes());
+    }
+    streams.iter().for_each(|stream| transformed.extend(stream));
+    transformed
+}
+
+/// Repackage an SFNT font into WOFF2, or into a WOFF2 collection of `num_fonts` fonts sharing
+/// all the tables. The `glyf` and `loca` tables are transformed if `transform` is `true`, or
+/// stored with the null transform otherwise.
+fn sfnt_to_woff2(sfnt: &[u8], num_fonts: u16, transform: bool) -> Vec<u8> {
+    let read_u32 = |i: usize| u32::from_be_bytes([sfnt[i], sfnt[i + 1], sfnt[i + 2], sfnt[i + 3]]);
+    // UIntBase128
+    let write_u32_var = |directory: &mut Vec<u8>, n: usize| {
+        let mut bytes = vec![(n & 0x7F) as u8];
+        let mut n = n >> 7;
+        while n != 0 {
+            bytes.push((n & 0x7F) as u8 | 0x80);
+            n >>= 7;
+        }
+        directory.extend(bytes.iter().rev());
+    };
+    let num_tables = u16::from_be_bytes([sfnt[4], sfnt[5]]);
+    let mut directory = Vec::new();
+    let mut table_data = Vec::new();
+    for i in 0..num_tables as usize {
+        let record = 12 + 16 * i;
+        let tag = &sfnt[record..record + 4];
+        let (offset, length) = (
+            read_u32(record + 8) as usize,
+            read_u32(record + 12) as usize,
+        );
+        let data = &sfnt[offset..offset + length];
+        // Arbitrary tag, with the transform version 0 or the null transform (version 3) for
+        // `glyf` and `loca`
+        match (tag, transform) {
+            (b"glyf", true) => {
+                let transformed = transform_glyf(sfnt);
+                directory.push(0x3F);
+                directory.extend(tag);
+                write_u32_var(&mut directory, length);
+                write_u32_var(&mut directory, transformed.len());
+                table_data.extend(transformed);
+            }
+            (b"loca", true) => {
+                directory.push(0x3F);
+                directory.extend(tag);
+                write_u32_var(&mut directory, length);
+                write_u32_var(&mut directory, 0);
+            }
+            (b"glyf", false) | (b"loca", false) => {
+                directory.push(0xFF);
+                directory.extend(tag);
+                write_u32_var(&mut directory, length);
+                table_data.extend(data);
+            }
+            _ => {
+                directory.push(0x3F);
+                directory.extend(tag);
+                write_u32_var(&mut directory, length);
+                table_data.extend(data);
+            }
+        }
+    }
+    if num_fonts > 1 {
+        directory.extend(&[0, 1, 0, 0, num_fonts as u8]);
+        for _ in 0..num_fonts {
+            directory.push(num_tables as u8);
+            directory.extend(&sfnt[0..4]);
+            directory.extend(0..num_tables as u8);
+        }
+    }
+    let mut compressed = Vec::new();
+    {
+        let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+        std::io::Write::write_all(&mut writer, &table_data).unwrap();
+    }
+
+    let flavor: &[u8] = if num_fonts > 1 { b"ttcf" } else { &sfnt[0..4] };
+    let mut woff2 = b"wOF2".to_vec();
+    woff2.extend(flavor);
+    woff2.extend(&((48 + directory.len() + compressed.len()) as u32).to_be_bytes());
+    woff2.extend(&num_tables.to_be_bytes());
+    woff2.extend(&[0, 0]);
+    woff2.extend(&(sfnt.len() as u32).to_be_bytes());
+    woff2.extend(&(compressed.len() as u32).to_be_bytes());
+    woff2.extend(&[0, 1, 0, 0]);
+    woff2.extend(&[0; 20]);
+    woff2.extend(directory);
+    woff2.extend(compressed);
+    woff2
+}
+
+#[test]
+fn fixture_woff() -> Result<()> {
+    // The WOFF version of `DejaVuSans.ttf` used by the benchmarks
+    let mut font_container = FontContainer::read("./benches/fonts/DejaVuSans.ttf.woff")?;
+    font_container.parse()?;
+    let font = font_container.get(0).unwrap();
+    assert_eq!(font_container.format(), ContainerFormat::Woff);
+    assert_eq!(font.info().glyph_count, 6253);
+    assert_eq!(font.glyph_bounding_box(36), Some((16, 0, 1384, 1493))); // A
+    let post = font.post.as_ref().unwrap();
+    assert_eq!(post.glyph_name(36), Some("A"));
+    assert_eq!(
+        post.glyph_name(6252),
+        read_fixture("DejaVuSans.ttf")?
+            .get(0)
+            .unwrap()
+            .post
+            .as_ref()
+            .unwrap()
+            .glyph_name(6252)
+    );
+    Ok(())
+}
+
+#[test]
+fn fixture_woff2() -> Result<()> {
+    let sfnt = std::fs::read([FIXTURES_PATH, "DejaVuSans.ttf"].join(""))?;
+    let ttf = read_fixture("DejaVuSans.ttf")?.get(0).unwrap().info();
+
+    for num_fonts in [1, 2] {
+        let path = std::env::temp_dir().join(format!("rustotf-fixture-{}.woff2", num_fonts));
+        std::fs::write(&path, sfnt_to_woff2(&sfnt, num_fonts, false))?;
+        let mut font_container = FontContainer::read_from_path(&path)?;
+        font_container.parse()?;
+        let last_font = Font::parse_from_ttc_index(path.to_str().unwrap(), num_fonts as usize - 1)?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(font_container.format(), ContainerFormat::Woff2);
+        assert_eq!(font_container.len(), num_fonts as usize);
+        for font in font_container.fonts().iter().chain([&last_font]) {
+            let info = font.info();
+            assert_eq!(info.glyph_count, ttf.glyph_count);
+            assert_eq!(info.postscript_name, ttf.postscript_name);
+            assert_eq!(font.glyph_bounding_box(36), Some((16, 0, 1384, 1493))); // A
+            let char_map = font.char_map().unwrap();
+            assert_eq!(char_map.get(&0x41), Some(&36));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn fixture_woff2_transformed() -> Result<()> {
+    let sfnt = std::fs::read([FIXTURES_PATH, "DejaVuSans.ttf"].join(""))?;
+    let ttf_container = read_fixture("DejaVuSans.ttf")?;
+    let ttf = ttf_container.get(0).unwrap();
+    let woff2 = sfnt_to_woff2(&sfnt, 1, true);
+
+    let mut font_container = FontContainer::from_bytes(woff2.clone())?;
+    font_container.parse()?;
+    let font = font_container.get(0).unwrap();
+    let (ttf_glyf, glyf) = (ttf.glyf.as_ref().unwrap(), font.glyf.as_ref().unwrap());
+    assert_eq!(font.info().glyph_count, 6253);
+    for glyph_id in [0, 3, 36, 197, 5042, 6252] {
+        assert_eq!(
+            format!("{:?}", glyf.get_glyph(glyph_id)),
+            format!("{:?}", ttf_glyf.get_glyph(glyph_id))
+        );
+    }
+
+    // In lazy mode, the glyphs are reconstructed from the cached data
+    let mut font_container = FontContainer::from_bytes(woff2)?;
+    font_container.set_lazy(true);
+    let glyf_tag = Tag::new(b"glyf");
+    let glyf = font_container.parse_and_get_table::<Table_glyf>(0, glyf_tag);
+    assert_eq!(
+        glyf.and_then(|glyf| glyf.get_glyph(36))
+            .map(|glyph| glyph.bounding_box()),
+        Some((16, 0, 1384, 1493))
+    );
+    font_container.parse_nth_table(0, Tag::new(b"hmtx"))?;
+    assert_eq!(font_container.get(0).unwrap().advance_width(36), Some(1401));
+    Ok(())
+}